@@ -1,54 +1,191 @@
 use std::rc::Rc;
-use crate::compiler::backend::arch::{Isa, Register};
+use crate::compiler::backend::arch::{Isa, ObjectFormat, Register};
 use crate::compiler::backend::assembly::AssemblyInstruction;
 
 #[derive(Clone, Eq, Hash, PartialEq)]
 pub enum Aarch64Asm {
     MoveReg(Register, Register),
     MoveImm(Register, i64),
-    Load(Register, Register, u8),
+    Load(Register, Register, u8, bool),
     Store(Register, Register, u8),
+    Extend(Register, u8, bool),
     AddReg(Register, Register),
     AddImm(Register, i64),
     SubReg(Register, Register),
     SubImm(Register, i64),
     MulReg(Register, Register),
-    DivReg(Register, Register),
+    MulHighReg(Register, Register, bool),
+    DivReg(Register, Register, bool),
+    RemReg(Register, Register, Register, bool),
+    And(Register, Register),
+    Or(Register, Register),
+    Xor(Register, Register),
+    Shl(Register, Register),
+    Shr(Register, Register),
+    Sar(Register, Register),
     StackLoad(Register, u64),
     StackStore(Register, u64),
-    Exit(Register),
+    StackAddress(Register, u64),
+    SystemCall(u16),
     Call(Rc<String>),
+    Return,
     Label(Rc<String>),
     Compare(Register, Register),
     Jump(Rc<String>),
     JumpEqual(Rc<String>),
     JumpNotEqual(Rc<String>),
+    JumpGreater(Rc<String>),
+    JumpGreaterOrEqual(Rc<String>),
+    JumpCarry(Rc<String>),
+    JumpNotCarry(Rc<String>),
+    JumpNotOverflow(Rc<String>),
+    JumpUnsignedLess(Rc<String>),
+    JumpTable(Register, Rc<String>, Vec<Rc<String>>),
+    Trap(u16),
+    LoadAddress(Register, Rc<String>, ObjectFormat),
+    StaticData(Rc<String>, Vec<u8>, u8),
+    ReadOnlyData(Rc<String>, Vec<u8>, u8, ObjectFormat),
+    InlineAssembly(Vec<String>),
 }
 
 
+/// The name of the 32-bit view of a register (e.g. `w0` for `x0`), used
+/// for operations on values that are at most four bytes wide.
+fn word_name(register: &Register) -> String {
+    register.name.replacen('x', "w", 1)
+}
+
+/// Moves an immediate into a register. Immediates that don't fit into a single `mov`
+/// are put together from their 16-bit parts, starting out with all bits cleared (`movz`)
+/// or, if most parts are all ones (e.g. large negative values), with all bits set (`movn`).
+fn move_immediate(register: &Register, value: i64) -> String {
+    if (-0x10000..=0xffff).contains(&value) {
+        return format!("\tmov\t{}, #{}\n", register.name, value);
+    }
+
+    let parts: Vec<u16> = (0..4).map(|i| (value >> (i * 16)) as u16).collect();
+    let is_inverted = parts.iter().filter(|&&x| x == 0xffff).count() > parts.iter().filter(|&&x| x == 0).count();
+    let initial_part = if is_inverted { 0xffff } else { 0 };
+
+    let mut instructions = String::new();
+
+    for (i, part) in parts.into_iter().enumerate() {
+        if part == initial_part { continue; }
+
+        let (mnemonic, immediate) = match (instructions.is_empty(), is_inverted) {
+            (true, true) => ("movn", !part),
+            (true, false) => ("movz", part),
+            (false, _) => ("movk", part),
+        };
+
+        instructions += &format!("\t{}\t{}, #{}, lsl #{}\n", mnemonic, register.name, immediate, i * 16);
+    }
+
+    instructions
+}
+
 impl Isa for Aarch64Asm {
     fn to_string(&self) -> String {
         use Aarch64Asm as A;
         match self {
             A::MoveReg(a, b) => format!("\tmov\t{}, {}\n", a.name, b.name),
-            A::MoveImm(a, b) => format!("\tmov\t{}, #{}\n", a.name, b),
-            A::Load(_, _, _) => todo!(),
-            A::Store(_, _, _) => todo!(),
-            A::AddReg(a, b) => format!("\tadd\t{}, {}, {}\n", a.name, a.name, b.name),
+            A::MoveImm(a, b) => move_immediate(a, *b),
+            A::Load(a, b, size, signed) => match (size, signed) {
+                (1, false) => format!("\tldrb\t{}, [{}]\n", word_name(a), b.name),
+                (1, true) => format!("\tldrsb\t{}, [{}]\n", a.name, b.name),
+                (2, false) => format!("\tldrh\t{}, [{}]\n", word_name(a), b.name),
+                (2, true) => format!("\tldrsh\t{}, [{}]\n", a.name, b.name),
+                (4, false) => format!("\tldr\t{}, [{}]\n", word_name(a), b.name),
+                (4, true) => format!("\tldrsw\t{}, [{}]\n", a.name, b.name),
+                (8, _) => format!("\tldr\t{}, [{}]\n", a.name, b.name),
+                _ => unreachable!("Can't load {} bytes at once", size),
+            },
+            A::Store(a, b, size) => match size {
+                1 => format!("\tstrb\t{}, [{}]\n", word_name(a), b.name),
+                2 => format!("\tstrh\t{}, [{}]\n", word_name(a), b.name),
+                4 => format!("\tstr\t{}, [{}]\n", word_name(a), b.name),
+                8 => format!("\tstr\t{}, [{}]\n", a.name, b.name),
+                _ => unreachable!("Can't store {} bytes at once", size),
+            },
+            A::Extend(a, size, signed) => match (size, signed) {
+                (1, false) => format!("\tuxtb\t{}, {}\n", word_name(a), word_name(a)),
+                (1, true) => format!("\tsxtb\t{}, {}\n", a.name, word_name(a)),
+                (2, false) => format!("\tuxth\t{}, {}\n", word_name(a), word_name(a)),
+                (2, true) => format!("\tsxth\t{}, {}\n", a.name, word_name(a)),
+                // Writing to the lower half clears the upper one
+                (4, false) => format!("\tmov\t{}, {}\n", word_name(a), word_name(a)),
+                (4, true) => format!("\tsxtw\t{}, {}\n", a.name, word_name(a)),
+                (8, _) => String::new(),
+                _ => unreachable!("Can't extend a value of {} bytes", size),
+            },
+            // Additions and subtractions set the flags, so overflows can be checked afterward
+            A::AddReg(a, b) => format!("\tadds\t{}, {}, {}\n", a.name, a.name, b.name),
             A::AddImm(a, b) => format!("\tadd\t{}, {}, #{}\n", a.name, a.name, b),
-            A::SubReg(a, b) => format!("\tsub\t{}, {}, {}\n", a.name, a.name, b.name),
+            A::SubReg(a, b) => format!("\tsubs\t{}, {}, {}\n", a.name, a.name, b.name),
             A::SubImm(a, b) => format!("\tsub\t{}, {}, #{}\n", a.name, a.name, b),
             A::MulReg(a, b) => format!("\tmul\t{}, {}, {}\n", a.name, a.name, b.name),
-            A::DivReg(a, b) => format!("\tdiv\t{}, {}, {}\n", a.name, a.name, b.name),
+            A::MulHighReg(a, b, signed) => format!("\t{}\t{}, {}, {}\n", if *signed { "smulh" } else { "umulh" }, a.name, a.name, b.name),
+            A::DivReg(a, b, signed) => format!("\t{}\t{}, {}, {}\n", if *signed { "sdiv" } else { "udiv" }, a.name, a.name, b.name),
+            A::RemReg(a, b, quotient, signed) => format!(
+                "\t{}\t{}, {}, {}\n\tmsub\t{}, {}, {}, {}\n",
+                if *signed { "sdiv" } else { "udiv" }, quotient.name, a.name, b.name,
+                a.name, quotient.name, b.name, a.name
+            ),
+            A::And(a, b) => format!("\tand\t{}, {}, {}\n", a.name, a.name, b.name),
+            A::Or(a, b) => format!("\torr\t{}, {}, {}\n", a.name, a.name, b.name),
+            A::Xor(a, b) => format!("\teor\t{}, {}, {}\n", a.name, a.name, b.name),
+            A::Shl(a, b) => format!("\tlsl\t{}, {}, {}\n", a.name, a.name, b.name),
+            A::Shr(a, b) => format!("\tlsr\t{}, {}, {}\n", a.name, a.name, b.name),
+            A::Sar(a, b) => format!("\tasr\t{}, {}, {}\n", a.name, a.name, b.name),
             A::StackLoad(a, b) => format!("\tldr\t{}, [sp, #{}]\n", a.name, b),
             A::StackStore(a, b) => format!("\tstr\t{}, [sp, #{}]\n", a.name, b),
-            A::Exit(a) => format!("\tmov\tx16, #1\n\tmov\tx0, {}\n\tsvc\t#0x80\n", a.name),
+            A::StackAddress(a, b) => format!("\tadd\t{}, sp, #{}\n", a.name, b),
+            A::SystemCall(immediate) => format!("\tsvc\t#{:#x}\n", immediate),
             A::Call(a) => format!("\tbl\t{}\n", a),
+            A::Return => "\tret\n".to_string(),
             A::Label(a) => format!("\n{}:\n", a),
             A::Compare(a, b) => format!("\tcmp\t{}, {}\n", a.name, b.name),
             A::Jump(a) => format!("\tb\t{}\n", a),
-            A::JumpEqual(a) => format!("\tbe\t{}\n", a),
+            A::JumpEqual(a) => format!("\tb.eq\t{}\n", a),
             A::JumpNotEqual(a) => format!("\tbne\t{}\n", a),
+            A::JumpGreater(a) => format!("\tb.gt\t{}\n", a),
+            A::JumpGreaterOrEqual(a) => format!("\tb.ge\t{}\n", a),
+            A::JumpCarry(a) => format!("\tb.cs\t{}\n", a),
+            A::JumpNotCarry(a) => format!("\tb.cc\t{}\n", a),
+            A::JumpNotOverflow(a) => format!("\tb.vc\t{}\n", a),
+            A::JumpUnsignedLess(a) => format!("\tb.lo\t{}\n", a),
+            // The table holds the distances from its start to the targets. x16 and x17 are
+            // never allocated, so they can be used without saving them.
+            A::JumpTable(index, table, targets) => {
+                let entries: String = targets.iter().map(|x| format!("\t.word\t{} - {}\n", x, table)).collect();
+
+                format!("\tadr\tx16, {}\n\tldrsw\tx17, [x16, {}, lsl #2]\n\tadd\tx16, x16, x17\n\tbr\tx16\n{}:\n{}", table, index.name, table, entries)
+            }
+            A::Trap(code) => format!("\tbrk\t#{}\n", code),
+            A::LoadAddress(a, symbol, ObjectFormat::MachO) => format!("\tadrp\t{}, {}@PAGE\n\tadd\t{}, {}, {}@PAGEOFF\n", a.name, symbol, a.name, a.name, symbol),
+            A::LoadAddress(a, symbol, ObjectFormat::Elf) => format!("\tadrp\t{}, {}\n\tadd\t{}, {}, :lo12:{}\n", a.name, symbol, a.name, a.name, symbol),
+            // The code continues right after the data, so the text section is selected again.
+            A::StaticData(symbol, bytes, alignment) => {
+                let alignment = alignment.trailing_zeros();
+
+                if bytes.iter().all(|x| *x == 0) {
+                    format!("\t.bss\n\t.p2align\t{}\n{}:\n\t.space\t{}\n\t.text\n", alignment, symbol, bytes.len())
+                } else {
+                    let data: Vec<String> = bytes.iter().map(|x| x.to_string()).collect();
+
+                    format!("\t.data\n\t.p2align\t{}\n{}:\n\t.byte\t{}\n\t.text\n", alignment, symbol, data.join(", "))
+                }
+            }
+            A::ReadOnlyData(symbol, bytes, alignment, format) => {
+                let data: Vec<String> = bytes.iter().map(|x| x.to_string()).collect();
+                let section = match format {
+                    ObjectFormat::MachO => "__TEXT,__const",
+                    ObjectFormat::Elf => ".rodata",
+                };
+
+                format!("\t.section\t{}\n\t.p2align\t{}\n{}:\n\t.byte\t{}\n\t.text\n", section, alignment.trailing_zeros(), symbol, data.join(", "))
+            }
+            A::InlineAssembly(lines) => lines.iter().map(|x| format!("\t{}\n", x)).collect(),
         }
     }
 }
@@ -61,23 +198,80 @@ impl From<AssemblyInstruction> for Aarch64Asm {
         match asm {
             AI::MoveReg(a, b) => AA::MoveReg(a, b),
             AI::MoveImm(a, i) => AA::MoveImm(a, i),
-            AI::Load(dest, adr, len) => AA::Load(dest, adr, len),
+            AI::Load(dest, adr, len, signed) => AA::Load(dest, adr, len, signed),
             AI::Store(data, adr, len) => AA::Store(data, adr, len),
+            AI::Extend(reg, len, signed) => AA::Extend(reg, len, signed),
             AI::AddReg(a, b) => AA::AddReg(a, b),
             AI::AddImm(a, i) => AA::AddImm(a, i),
             AI::SubReg(a, b) => AA::SubReg(a, b),
             AI::SubImm(a, i) => AA::SubImm(a, i),
             AI::MulReg(a, b) => AA::MulReg(a, b),
-            AI::DivReg(a, b) => AA::DivReg(a, b),
+            AI::MulHighReg(a, b, signed) => AA::MulHighReg(a, b, signed),
+            AI::DivReg(a, b, signed) => AA::DivReg(a, b, signed),
+            AI::RemReg(a, b, quotient, signed) => AA::RemReg(a, b, quotient, signed),
+            AI::And(a, b) => AA::And(a, b),
+            AI::Or(a, b) => AA::Or(a, b),
+            AI::Xor(a, b) => AA::Xor(a, b),
+            AI::Shl(a, b) => AA::Shl(a, b),
+            AI::Shr(a, b) => AA::Shr(a, b),
+            AI::Sar(a, b) => AA::Sar(a, b),
             AI::StackLoad(a, b) => AA::StackLoad(a, b),
             AI::StackStore(a, b) => AA::StackStore(a, b),
-            AI::Exit(a) => AA::Exit(a),
+            AI::StackAddress(a, b) => AA::StackAddress(a, b),
+            AI::SystemCall(immediate) => AA::SystemCall(immediate),
             AI::Call(label) => AA::Call(Rc::new(label)),
+            AI::Return => AA::Return,
             AI::Label(name) => AA::Label(name),
             AI::Compare(a, b) => AA::Compare(a, b),
             AI::Jump(a) => AA::Jump(a),
             AI::JumpEqual(a) => AA::JumpEqual(a),
             AI::JumpNotEqual(a) => AA::JumpNotEqual(a),
+            AI::JumpGreater(a) => AA::JumpGreater(a),
+            AI::JumpGreaterOrEqual(a) => AA::JumpGreaterOrEqual(a),
+            AI::JumpCarry(a) => AA::JumpCarry(a),
+            AI::JumpNotCarry(a) => AA::JumpNotCarry(a),
+            AI::JumpNotOverflow(a) => AA::JumpNotOverflow(a),
+            AI::JumpUnsignedLess(a) => AA::JumpUnsignedLess(a),
+            AI::JumpTable(a, b, c) => AA::JumpTable(a, b, c),
+            AI::Trap(code) => AA::Trap(code),
+            AI::LoadAddress(a, symbol, format) => AA::LoadAddress(a, symbol, format),
+            AI::StaticData(symbol, bytes, alignment) => AA::StaticData(symbol, bytes, alignment),
+            AI::ReadOnlyData(symbol, bytes, alignment, format) => AA::ReadOnlyData(symbol, bytes, alignment, format),
+            AI::InlineAssembly(lines) => AA::InlineAssembly(lines),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compiler::backend::arch::{Isa, Register, RegisterDataType, RegisterKind, RegisterSavingBehaviour};
+    use crate::compiler::backend::arch::aarch64::Aarch64Asm;
+
+    fn register(name: &str) -> Register {
+        Register::new(name.to_string(), RegisterKind::GeneralPurpose, 8, RegisterSavingBehaviour::CallerSaved, vec![RegisterDataType::Integer])
+    }
+
+    #[test]
+    fn test_sized_memory_access() {
+        let (x0, x1) = (register("x0"), register("x1"));
+
+        assert_eq!(Aarch64Asm::Load(x0.clone(), x1.clone(), 1, false).to_string(), "\tldrb\tw0, [x1]\n");
+        assert_eq!(Aarch64Asm::Load(x0.clone(), x1.clone(), 2, true).to_string(), "\tldrsh\tx0, [x1]\n");
+        assert_eq!(Aarch64Asm::Load(x0.clone(), x1.clone(), 8, true).to_string(), "\tldr\tx0, [x1]\n");
+        assert_eq!(Aarch64Asm::Store(x0.clone(), x1.clone(), 4).to_string(), "\tstr\tw0, [x1]\n");
+        assert_eq!(Aarch64Asm::Extend(x0.clone(), 1, true).to_string(), "\tsxtb\tx0, w0\n");
+        assert_eq!(Aarch64Asm::Extend(x0, 4, false).to_string(), "\tmov\tw0, w0\n");
+    }
+
+    #[test]
+    fn test_wide_immediates() {
+        let x0 = register("x0");
+
+        assert_eq!(Aarch64Asm::MoveImm(x0.clone(), 65535).to_string(), "\tmov\tx0, #65535\n");
+        assert_eq!(Aarch64Asm::MoveImm(x0.clone(), -65536).to_string(), "\tmov\tx0, #-65536\n");
+        assert_eq!(Aarch64Asm::MoveImm(x0.clone(), 100000).to_string(), "\tmovz\tx0, #34464, lsl #0\n\tmovk\tx0, #1, lsl #16\n");
+        assert_eq!(Aarch64Asm::MoveImm(x0.clone(), 0x1_2345_6789).to_string(), "\tmovz\tx0, #26505, lsl #0\n\tmovk\tx0, #9029, lsl #16\n\tmovk\tx0, #1, lsl #32\n");
+        assert_eq!(Aarch64Asm::MoveImm(x0.clone(), 1 << 48).to_string(), "\tmovz\tx0, #1, lsl #48\n");
+        assert_eq!(Aarch64Asm::MoveImm(x0, -100000).to_string(), "\tmovn\tx0, #34463, lsl #0\n\tmovk\tx0, #65534, lsl #16\n");
+    }
+}
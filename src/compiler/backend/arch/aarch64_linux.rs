@@ -0,0 +1,22 @@
+use crate::compiler::backend::arch::{aarch64_mac_os, Architecture, ObjectFormat, RegisterDataType, RegisterKind, RegisterSavingBehaviour, SyscallConvention, VariadicArguments};
+use crate::compiler::backend::arch::register::Register;
+
+
+/// Linux follows the standard arm64 ABI, which Apple's only deviates from in
+/// how variadic arguments are passed. System calls and the object format differ.
+pub fn generate() -> Architecture {
+    Architecture {
+        name: "aarch64_Linux".to_string(),
+        variadic_arguments: VariadicArguments::Registers,
+        syscall_convention: SyscallConvention::new(
+            // x8 is the scratch register, so it's reserved before the arguments are moved.
+            Register::new("x8".to_string(), RegisterKind::GeneralPurpose, 8, RegisterSavingBehaviour::Scratch, vec![RegisterDataType::Address, RegisterDataType::Integer]),
+            vec![0,1,2,3,4,5],
+            0,
+            93,
+            64
+        ),
+        object_format: ObjectFormat::Elf,
+        ..aarch64_mac_os::generate()
+    }
+}
@@ -1,6 +1,5 @@
-use std::collections::HashMap;
 use uuid::Uuid;
-use crate::compiler::backend::arch::{Architecture, RegisterDataType, RegisterKind, RegisterSavingBehaviour};
+use crate::compiler::backend::arch::{Architecture, RegisterDataType, RegisterKind, RegisterSavingBehaviour, SyscallConvention, VariadicArguments};
 use crate::compiler::backend::arch::register::{Register, RegisterMap};
 
 
@@ -42,15 +41,28 @@ pub fn generate() -> Architecture {
 
                 (Register::new("sp".to_string(), RegisterKind::StackPointer, 8, RegisterSavingBehaviour::CalleeSaved, vec![RegisterDataType::Address]), Some(Uuid::new_v4())),
             ],
-            vec![],
             8,
-            0,
-            HashMap::new(),
             26,
-            vec![0,1,2,3,4,5,6,7]
+            Register::new("x30".to_string(), RegisterKind::GeneralPurpose, 8, RegisterSavingBehaviour::CallerSaved, vec![RegisterDataType::Address]),
+            vec![0,1,2,3,4,5,6,7],
+            // x0 to x7, then x9 to x15 (x8 is the scratch register)
+            vec![0,1,2,3,4,5,6,7,9,10,11,12,13,14,15],
+            // x19 to x23. Every callee-saved register gets restored wherever a function returns,
+            // so smisc functions get five registers without that, while values can still be kept
+            // in x24 to x28 during calls.
+            vec![16,17,18,19,20]
          ),
         include_str!("aarch64_macOS_header_bp.s"),
         "",
-        16
+        16,
+        VariadicArguments::Stack,
+        SyscallConvention::new(
+            // x16 is never allocated, so it can be used without saving it.
+            Register::new("x16".to_string(), RegisterKind::GeneralPurpose, 8, RegisterSavingBehaviour::Scratch, vec![RegisterDataType::Integer]),
+            vec![0,1,2,3,4,5],
+            0x80,
+            1,
+            4
+        )
     )
 }
\ No newline at end of file
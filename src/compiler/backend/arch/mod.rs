@@ -1,4 +1,5 @@
 pub mod aarch64_mac_os;
+pub mod aarch64_linux;
 mod register;
 pub mod aarch64;
 
@@ -13,10 +14,112 @@ use crate::compiler::backend::assembly::AssemblyInstruction;
 use crate::compiler::backend::flattener::InstructionMeta;
 use crate::compiler::parser::function_meta::FunctionStyle;
 
+/// The size of the slot every argument passed on the stack takes up.
+const STACK_ARGUMENT_SIZE: usize = 8;
+
 pub trait Isa: Clone + Eq + Hash + From<AssemblyInstruction> {
     fn to_string(&self) -> String;
 }
 
+/// ### How a Target Passes Variadic Arguments
+///
+/// The arguments of a C function that are covered by its `...` (e.g. everything after the
+/// format of `printf`) aren't always passed like the named ones.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VariadicArguments {
+    /// All variadic arguments are passed on the stack, even if argument registers are left
+    /// (Apple's arm64 ABI).
+    Stack,
+
+    /// Variadic arguments are passed just like the named ones (the standard arm64 ABI,
+    /// used on Linux).
+    Registers,
+}
+
+/// Where an argument of a call is passed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgumentLocation {
+    Register(Register),
+
+    /// The offset of the argument's slot to the stack pointer at the time of the call.
+    Stack(u64),
+}
+
+/// ### How a Target Performs System Calls
+///
+/// The number of the call is placed in its own register and the arguments in the argument
+/// registers, the result is returned in the first of them. For example, Darwin uses `x16`,
+/// `x0` to `x5` and `svc #0x80`, aarch64 Linux uses `x8`, `x0` to `x5` and `svc #0`.
+#[derive(new, Debug, Clone, PartialEq)]
+pub struct SyscallConvention {
+    /// The register holding the number of the call. It doesn't need to be in the register map.
+    pub number_register: Register,
+
+    /// The indices of the argument registers in the register map.
+    pub argument_registers: Vec<usize>,
+
+    /// The immediate of the [instruction performing the call](AssemblyInstruction::SystemCall).
+    pub immediate: u16,
+
+    /// The number of the call that ends the process.
+    pub exit_number: i64,
+
+    /// The number of the call that writes to a file descriptor.
+    pub write_number: i64,
+}
+
+/// ### The Object File Format of a Target
+///
+/// Decides how the assembly refers to the addresses of symbols and names its sections.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ObjectFormat {
+    /// Used by Apple's platforms.
+    #[default]
+    MachO,
+
+    /// Used by Linux.
+    Elf,
+}
+
+/// ### The Platform the Code Is Compiled For
+///
+/// Selected with `--target`.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum Target {
+    #[default]
+    #[value(name = "aarch64-macos")]
+    Aarch64MacOs,
+
+    #[value(name = "aarch64-linux")]
+    Aarch64Linux,
+}
+
+impl Target {
+    /// Generates the [architecture](Architecture) the code gets compiled for.
+    pub fn generate_architecture(&self) -> Architecture {
+        match self {
+            Target::Aarch64MacOs => aarch64_mac_os::generate(),
+            Target::Aarch64Linux => aarch64_linux::generate(),
+        }
+    }
+
+    /// The symbol of the C function `name`, which gets a leading underscore on macOS (e.g. `_printf`).
+    pub fn get_c_symbol(&self, name: &str) -> String {
+        match self {
+            Target::Aarch64MacOs => format!("_{}", name),
+            Target::Aarch64Linux => name.to_string(),
+        }
+    }
+
+    /// The target triple the assembly is assembled for.
+    pub fn get_triple(&self) -> &'static str {
+        match self {
+            Target::Aarch64MacOs => "aarch64-apple-darwin",
+            Target::Aarch64Linux => "aarch64-unknown-linux-gnu",
+        }
+    }
+}
+
 #[derive(new, Debug, Clone, PartialEq)]
 pub struct Architecture {
     pub name: String,
@@ -24,6 +127,12 @@ pub struct Architecture {
     pub leading_boilerplate: &'static str,
     pub trailing_boilerplate: &'static str,
     pub address_alignment: usize,
+    pub variadic_arguments: VariadicArguments,
+    pub syscall_convention: SyscallConvention,
+
+    /// Mach-O unless the target changes it.
+    #[new(default)]
+    pub object_format: ObjectFormat,
 }
 
 impl Architecture {
@@ -106,7 +215,7 @@ impl Architecture {
             if let Some(reg_contents) = reg_contents.1 {
                 let mut movement = self.provide_empty_register(preserving, &|_| true);
                 instructions.append(movement.1.as_mut());
-                instructions.push(AssemblyInstruction::MoveReg(register.clone(), movement.0.clone()));
+                instructions.push(AssemblyInstruction::MoveReg(movement.0.clone(), register.clone()));
 
                 if let Some(index) = self.register_map.registers.iter().position(|x| x.0 == movement.0) {
                     self.register_map.registers[index].1 = Some(reg_contents);
@@ -144,12 +253,90 @@ impl Architecture {
 
     /// Get the argument register for an architecture and calling convention
     /// A return value of None means that it should be stored on the stack.
+    ///
+    /// The smisc convention has more argument registers and leaves [some of the callee-saved
+    /// registers](RegisterMap::smisc_caller_saved) to the caller.
     pub fn get_register_for_argument(&self, argument_index: usize, calling_convention: FunctionStyle) -> Option<Register> {
+        let argument_map = match calling_convention {
+            FunctionStyle::C => &self.register_map.c_style_arg_map,
+            FunctionStyle::Smisc => &self.register_map.smisc_arg_map,
+        };
+
+        Some(self.register_map.registers.get(*argument_map.get(argument_index)?)?.0.clone())
+    }
+
+    /// Gets the register the (argument_index)th argument of a system call is passed in. The
+    /// result of the call is returned in the register of the first argument.
+    pub fn get_register_for_syscall_argument(&self, argument_index: usize) -> Option<Register> {
+        let index = *self.syscall_convention.argument_registers.get(argument_index)?;
+
+        Some(self.register_map.registers[index].0.clone())
+    }
+
+    /// ### Gets Where an Argument Is Passed on the Stack
+    ///
+    /// Arguments that don't fit into [the registers](Self::get_register_for_argument) are
+    /// passed on the stack, each one in its own 8 byte slot, starting at the stack pointer
+    /// of the caller (like the C ABI does, except that Apple packs arguments smaller than 8
+    /// bytes). Returns the offset of the argument's slot.
+    pub fn get_stack_offset_for_argument(&self, argument_index: usize, calling_convention: FunctionStyle) -> u64 {
+        let register_count = match calling_convention {
+            FunctionStyle::C => self.register_map.c_style_arg_map.len(),
+            FunctionStyle::Smisc => self.register_map.smisc_arg_map.len(),
+        };
+
+        ((argument_index - register_count) * STACK_ARGUMENT_SIZE) as u64
+    }
+
+    /// ### Gets Where the Arguments of a Call Are Passed
+    ///
+    /// For variadic functions, the variadic start is the index of the first argument covered
+    /// by `...`, those are passed as the [target](VariadicArguments) requires. All other
+    /// arguments are passed like [the ones a function receives](Self::get_register_for_argument),
+    /// those that don't fit into registers taking up the next slot on the stack.
+    pub fn get_argument_locations(&self, argument_count: usize, calling_convention: FunctionStyle, variadic_start: Option<usize>) -> Vec<ArgumentLocation> {
+        let mut stack_offset = 0;
+
+        (0..argument_count).map(|i| {
+            let is_variadic = variadic_start.is_some_and(|start| i >= start);
+
+            let register = match self.variadic_arguments {
+                VariadicArguments::Stack if is_variadic => None,
+                _ => self.get_register_for_argument(i, calling_convention),
+            };
+
+            register.map(ArgumentLocation::Register).unwrap_or_else(|| {
+                stack_offset += STACK_ARGUMENT_SIZE as u64;
+                ArgumentLocation::Stack(stack_offset - STACK_ARGUMENT_SIZE as u64)
+            })
+        }).collect()
+    }
+
+    /// The stack space the caller reserves for its arguments passed on the stack, which keeps
+    /// the stack pointer aligned.
+    pub fn get_stack_argument_area_size(&self, stack_argument_count: usize) -> u64 {
+        (stack_argument_count * STACK_ARGUMENT_SIZE).next_multiple_of(self.address_alignment) as u64
+    }
+
+    /// Get the register a function returns its (return_index)th value in.
+    /// With both calling conventions on aarch64, those are the argument registers.
+    pub fn get_register_for_return_value(&self, return_index: usize, calling_convention: FunctionStyle) -> Option<Register> {
+        self.get_register_for_argument(return_index, calling_convention)
+    }
+
+    /// Get the register the address of the memory for a returned struct is passed in, if the
+    /// struct is too large for the [registers of return values](Self::get_register_for_return_value).
+    /// On aarch64, that's the scratch register (x8).
+    pub fn get_register_for_indirect_result(&self) -> Register {
+        self.get_scratch_register()
+    }
+
+    /// The amount of values a function can return, each one in its own register.
+    pub fn get_max_return_values(&self, calling_convention: FunctionStyle) -> usize {
         match calling_convention {
-            FunctionStyle::C => {
-                Some(self.register_map.registers.get(self.register_map.c_style_arg_map[argument_index])?.0.clone())
-            }
-            FunctionStyle::Smisc => todo!(),
+            // The C ABI only uses several registers for a single value (e.g. a small struct).
+            FunctionStyle::C => 1,
+            FunctionStyle::Smisc => self.register_map.smisc_arg_map.len(),
         }
     }
 
@@ -167,29 +354,67 @@ impl Architecture {
     /// code.
     /// This means that:
     /// 1. The stack pointer gets set (header)
-    /// 2. The stack pointer gets restored (trailer)
-    /// 3. The callee-saved registers get restored
+    /// 2. The link register gets saved at the given offset if there is one (header)
+    /// 3. The link register and the stack pointer get restored before returning (trailer)
     /// 4. [A new function gets prepared](Self::prepare_new_function)
     ///
+    /// The callee-saved registers have to be [restored](Self::restore_backup_map) before
+    /// the trailer, which is placed wherever the function returns.
+    ///
     /// The first result of this function is the header, the second the
     /// trailer code.
-    pub fn end_function(&mut self) -> (Vec<AssemblyInstruction>, Vec<AssemblyInstruction>) {
-        let stack_offset = (self.register_map.stack_offset + (self.register_map.stack_offset % 16)) as i64;
-        let header: Vec<AssemblyInstruction> = vec![
+    pub fn end_function(&mut self, link_register_offset: Option<u64>) -> (Vec<AssemblyInstruction>, Vec<AssemblyInstruction>) {
+        let stack_offset = self.get_frame_size() as i64;
+        let mut header: Vec<AssemblyInstruction> = vec![
             AssemblyInstruction::SubImm(self.get_stack_pointer(), stack_offset),
         ];
 
-        let mut trailer: Vec<AssemblyInstruction> = vec![
-            AssemblyInstruction::AddImm(self.get_stack_pointer(), stack_offset),
-        ];
+        let mut trailer: Vec<AssemblyInstruction> = vec![];
 
+        if let Some(offset) = link_register_offset {
+            header.push(AssemblyInstruction::StackStore(self.register_map.link_register.clone(), offset));
+            trailer.push(AssemblyInstruction::StackLoad(self.register_map.link_register.clone(), offset));
+        }
+
+        trailer.push(AssemblyInstruction::AddImm(self.get_stack_pointer(), stack_offset));
+        trailer.push(AssemblyInstruction::Return);
 
-        trailer.append(&mut self.restore_backup_map());
         self.prepare_new_function();
 
         (header, trailer)
     }
 
+    /// The amount of bytes the current function reserves on the stack. The arguments it
+    /// received on the stack are right above that.
+    pub fn get_frame_size(&self) -> u64 {
+        (self.register_map.stack_offset + (self.register_map.stack_offset % 16)) as u64
+    }
+
+    /// Starts the body of a function using the calling convention. Smisc functions don't
+    /// [restore](Self::restore_backup_map) the registers [they may change](RegisterMap::smisc_caller_saved),
+    /// so those are free to use right away.
+    pub fn start_function(&mut self, calling_convention: FunctionStyle) {
+        if calling_convention != FunctionStyle::Smisc { return; }
+
+        for index in self.register_map.smisc_caller_saved.clone() {
+            let register = &mut self.register_map.registers[index];
+
+            self.register_map.backup_reg_map.retain(|x| x.0 != register.0);
+            register.1 = None;
+        }
+    }
+
+    /// Whether calling a function of the calling convention can change the register (at the
+    /// index in the registers) without restoring it, so the caller has to save it.
+    pub fn is_caller_saved(&self, index: usize, calling_convention: FunctionStyle) -> bool {
+        match self.register_map.registers[index].0.saving_behaviour {
+            RegisterSavingBehaviour::CallerSaved => true,
+            RegisterSavingBehaviour::CalleeSaved => calling_convention == FunctionStyle::Smisc
+                && self.register_map.smisc_caller_saved.contains(&index),
+            RegisterSavingBehaviour::Scratch => false,
+        }
+    }
+
     /// Creates UUIDs for the callee-saved registers and writes to
     /// the backup register map for restoring the state later.
     pub fn backup_callee_saved_regs(&mut self) {
@@ -206,21 +431,22 @@ impl Architecture {
     }
 
     /// Restores the register map that was backed up if possible and
-    /// deletes all the other UUIDs.
-    pub fn restore_backup_map(&mut self) -> Vec<AssemblyInstruction> {
+    /// deletes all the other UUIDs, except for the kept ones (e.g. the
+    /// values a function returns), which stay in their registers.
+    pub fn restore_backup_map(&mut self, keeping: Vec<Uuid>) -> Vec<AssemblyInstruction> {
         let mut instructions: Vec<AssemblyInstruction> = Vec::new();
         let backup_uuids: Vec<Uuid> = self.register_map.backup_reg_map.iter().map(|x|x.1.unwrap()).collect();
 
         // Delete all unlisted objects in the registers
         for object in self.register_map.clone().registers.iter().filter_map(|x|x.1) {
-            if !backup_uuids.contains(&object) {
+            if !backup_uuids.contains(&object) && !keeping.contains(&object) {
                 self.delete_object(object);
             }
         }
 
         // Delete all unlisted objects in the stack
         for object in self.register_map.clone().stack.keys() {
-            if !backup_uuids.contains(object) {
+            if !backup_uuids.contains(object) && !keeping.contains(object) {
                 self.delete_object(*object);
             }
         }
@@ -228,7 +454,7 @@ impl Architecture {
         let mut correctly_placed_uuids: Vec<Uuid> = Vec::new();
         while let Some(item) = self.register_map.backup_reg_map.pop() {
             let object = item.1.unwrap();
-            instructions.append(&mut self.move_into_reg(object, item.0, correctly_placed_uuids.clone()));
+            instructions.append(&mut self.move_into_reg(object, item.0, [correctly_placed_uuids.clone(), keeping.clone()].concat()));
             correctly_placed_uuids.push(object);
         }
 
@@ -247,18 +473,18 @@ impl Architecture {
 
 
 
-    /// Stores all the caller saved regs to other empty registers or the stack
-    pub fn backup_caller_saved_regs(&mut self) -> Vec<AssemblyInstruction> {
+    /// Stores all the regs a call using the calling convention saves to other empty registers or the stack
+    pub fn backup_caller_saved_regs(&mut self, calling_convention: FunctionStyle) -> Vec<AssemblyInstruction> {
         let mut instructions: Vec<AssemblyInstruction> = vec![];
         for reg_info in self.register_map.registers.clone().iter().enumerate() {
-            let _i = reg_info.0;
+            let i = reg_info.0;
             let register = reg_info.1.clone();
 
-            if register.0.saving_behaviour != RegisterSavingBehaviour::CallerSaved { continue; }
+            if !self.is_caller_saved(i, calling_convention) { continue; }
 
             if let Some(object) = register.1 {
                 // Look for an empty reg to put stuff in or put it on the stack
-                if let Some(mut empty_register) = self.conditionally_provide_empty_register(vec![]) {
+                if let Some(mut empty_register) = self.conditionally_provide_empty_register(vec![], calling_convention) {
                     // Put the register in the register
                     instructions.append(empty_register.1.as_mut());
                     instructions.push(AssemblyInstruction::MoveReg(empty_register.0.clone(), register.0.clone()));
@@ -308,6 +534,47 @@ impl Architecture {
     }
 
 
+    /// Reserves memory on the stack (e.g. for arrays) and returns its offset
+    /// to the stack pointer. The memory stays reserved until the function ends.
+    pub fn allocate_stack(&mut self, size: usize) -> usize {
+        let offset = self.register_map.stack_offset;
+
+        // Keep the stack aligned for the registers that get pushed later on.
+        self.register_map.stack_offset = (offset + size).next_multiple_of(8);
+
+        offset
+    }
+
+    /// Gets the register with the given name (e.g. `x9`) if the compiler places objects in it.
+    pub fn get_register_by_name(&self, name: &str) -> Option<Register> {
+        self.register_map.registers.iter().map(|x| x.0.clone()).find(|x| x.name == name)
+    }
+
+    /// ### Reserves a Register
+    ///
+    /// Moves the object in the register into another one and marks the register as holding
+    /// a new object, which is returned. The register isn't chosen for anything else until
+    /// that object gets [deleted](Self::delete_object). Preserved objects are kept in their
+    /// registers.
+    pub fn reserve_register(&mut self, register: Register, preserving: Vec<Uuid>) -> (Uuid, Vec<AssemblyInstruction>) {
+        let placeholder = Uuid::new_v4();
+        let index = self.register_map.registers.iter().position(|x| x.0 == register).unwrap();
+        let contents = self.register_map.registers[index].1;
+        let mut instructions: Vec<AssemblyInstruction> = vec![];
+
+        self.register_map.registers[index].1 = Some(placeholder);
+
+        if let Some(contents) = contents {
+            let mut movement = self.provide_empty_register([preserving, vec![placeholder]].concat(), &|_| true);
+
+            instructions.append(movement.1.as_mut());
+            instructions.push(AssemblyInstruction::MoveReg(movement.0.clone(), register));
+            self.move_into_reg_no_code(contents, movement.0);
+        }
+
+        (placeholder, instructions)
+    }
+
     /// Gets the default stack pointer in use for this architecture
     pub fn get_stack_pointer(&self) -> Register {
         let sp = self.register_map.registers[self.register_map.stack_pointer_register].0.clone();
@@ -343,11 +610,17 @@ impl Architecture {
     }
 
     /// Provides an empty register using [this function](Self::provide_empty_register) IF
-    /// it can be achieved with an empty register. Returns None if there is no register
-    /// free and the data should potentially be stored on the stack instead.
-    pub fn conditionally_provide_empty_register(&mut self, ignoring: Vec<Uuid>) -> Option<(Register, Vec<AssemblyInstruction>)> {
+    /// it can be achieved with an empty register that a call using the calling convention
+    /// keeps. Returns None if there is no register free and the data should potentially
+    /// be stored on the stack instead.
+    pub fn conditionally_provide_empty_register(&mut self, ignoring: Vec<Uuid>, calling_convention: FunctionStyle) -> Option<(Register, Vec<AssemblyInstruction>)> {
         let mut other_self = self.clone();
-        let result = other_self.provide_empty_register(ignoring, &|x| x.saving_behaviour==RegisterSavingBehaviour::CalleeSaved);
+        let kept: Vec<Register> = (0..self.register_map.registers.len())
+            .filter(|&i| !self.is_caller_saved(i, calling_convention))
+            .map(|i| self.register_map.registers[i].0.clone())
+            .collect();
+
+        let result = other_self.provide_empty_register(ignoring, &|x| x.saving_behaviour==RegisterSavingBehaviour::CalleeSaved && kept.contains(&x));
 
         // Look if the register is empty
         let register = self.register_map.registers.iter().find(|&x| x.0==result.0).unwrap();
@@ -420,3 +693,25 @@ impl Architecture {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::compiler::backend::arch::{aarch64_linux, aarch64_mac_os, ArgumentLocation};
+    use crate::compiler::parser::function_meta::FunctionStyle;
+
+    #[test]
+    fn test_variadic_argument_locations() {
+        let architecture = aarch64_mac_os::generate();
+        let register = |i: usize| ArgumentLocation::Register(architecture.get_register_for_argument(i, FunctionStyle::C).unwrap());
+        let (x0, x1, x2) = (register(0), register(1), register(2));
+
+        // printf(format, a, b)
+        assert_eq!(architecture.get_argument_locations(3, FunctionStyle::C, Some(1)), vec![x0.clone(), ArgumentLocation::Stack(0), ArgumentLocation::Stack(8)]);
+        assert_eq!(architecture.get_argument_locations(3, FunctionStyle::C, None), vec![x0.clone(), x1.clone(), x2.clone()]);
+
+        let architecture = aarch64_linux::generate();
+        assert_eq!(architecture.get_argument_locations(3, FunctionStyle::C, Some(1)), vec![x0, x1, x2]);
+
+        let locations = architecture.get_argument_locations(10, FunctionStyle::C, Some(1));
+        assert_eq!(locations[8..], [ArgumentLocation::Stack(0), ArgumentLocation::Stack(8)]);
+    }
+}
@@ -15,23 +15,41 @@ pub struct Register {
 pub struct RegisterMap {
     pub(crate) registers: Vec<(Register, Option<Uuid>)>,
 
+    #[new(default)]
     pub(crate) backup_reg_map: Vec<(Register, Option<Uuid>)>,
 
     /// The index of the scratch register in the [registers map](Self::registers)
     pub(crate) scratch_register: usize,
 
     /// The stack offset. Must be reset at function start.
+    #[new(default)]
     pub(crate) stack_offset: usize,
 
     /// The contents of the stack and the offset in comparison to the stack pointer.
+    #[new(default)]
     pub(crate) stack: HashMap</* object- */Uuid, /*stack offset: */usize>,
 
     /// The index of the stack pointer register in the [registers map](Self::registers)
     pub(crate) stack_pointer_register: usize,
 
+    /// The register calls put the return address into (`x30` on aarch64). It's never
+    /// allocated, so it isn't in the [registers map](Self::registers) and functions
+    /// calling others save it in their frame.
+    pub(crate) link_register: Register,
+
     /// A map of where all the arguments go in a C-Style call
     /// The registers are given by their indexes in the registers.
-    pub(crate) c_style_arg_map: Vec<usize>
+    pub(crate) c_style_arg_map: Vec<usize>,
+
+    /// Like the [C-Style map](Self::c_style_arg_map), but for calls using the smisc
+    /// convention. It starts with the same registers, so calls between both kinds of
+    /// functions don't have to move the first arguments.
+    pub(crate) smisc_arg_map: Vec<usize>,
+
+    /// The callee-saved registers smisc functions may change without restoring them, given
+    /// by their indexes in the [registers](Self::registers). Calls to smisc functions save
+    /// them like the caller-saved registers.
+    pub(crate) smisc_caller_saved: Vec<usize>,
 }
 
 #[derive(new, Debug, Clone, Eq, Hash, PartialEq)]
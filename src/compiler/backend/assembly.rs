@@ -3,13 +3,17 @@ use std::hash::Hash;
 use std::io::{Error, ErrorKind, Write};
 use std::ops::Deref;
 use std::rc::Rc;
+use uuid::Uuid;
 use std::slice::Iter;
-use crate::compiler::backend::arch::{Architecture, Isa, Register};
+use crate::compiler::backend::arch::{Architecture, ArgumentLocation, Isa, ObjectFormat, Register, RegisterKind, RegisterSavingBehaviour};
 use crate::compiler::backend::arch::aarch64::Aarch64Asm;
 use crate::compiler::backend::flattener::{Instruction, InstructionMeta, JumpComparisonType};
 use crate::compiler::backend::flattener::InstructionMeta::Jump;
 use crate::compiler::parser::function_meta::FunctionStyle;
 
+/// The file descriptor [traps](Instruction::Trap) write their message to.
+const STANDARD_ERROR: i64 = 2;
+
 #[derive(Debug, Clone)]
 pub enum AssemblyInstruction {
     /// Copy the contents of one register into the other one.  
@@ -18,10 +22,13 @@ pub enum AssemblyInstruction {
     /// Copy the data into the given register.
     MoveImm(Register, i64),
     
-    /// Load (2) bytes of data at address at register (1) into register (0)
-    Load(Register, Register, u8),
+    /// Load (2) bytes of data at address at register (1) into register (0),
+    /// sign extending it if (3) is true and zero extending it otherwise
+    Load(Register, Register, u8, bool),
     /// Store (2) bytes of data from register (0) into the address at register (1)
     Store(Register, Register, u8),
+    /// Truncate register (0) to (1) bytes and extend it back, keeping the sign if (2) is true
+    Extend(Register, u8, bool),
     
     /// Adds the contents of the second register to the first register's contents
     AddReg(Register, Register),
@@ -33,8 +40,28 @@ pub enum AssemblyInstruction {
     SubImm(Register, i64),
     /// Multiplies the contents of the second register with the first register's contents.
     MulReg(Register, Register),
-    /// Divides the first register with the second one.
-    DivReg(Register, Register),
+    /// Put the upper half of the product of both registers into the first one, treating them as signed if true.
+    MulHighReg(Register, Register, bool),
+    /// Divides the first register with the second one, signed if (2) is true.
+    DivReg(Register, Register, bool),
+    /// Puts the remainder of dividing the first register by the second one into the
+    /// first one. The quotient is calculated in the third register on the way.
+    RemReg(Register, Register, Register, bool),
+
+    /// Bitwise and of both registers, stored in the first one
+    And(Register, Register),
+    /// Bitwise or of both registers, stored in the first one
+    Or(Register, Register),
+    /// Bitwise exclusive or of both registers, stored in the first one
+    Xor(Register, Register),
+    /// Shifts the first register to the left by the amount in the second one
+    Shl(Register, Register),
+    /// Shifts the first register to the right by the amount in the second one,
+    /// filling up with zeros
+    Shr(Register, Register),
+    /// Shifts the first register to the right by the amount in the second one,
+    /// keeping the sign
+    Sar(Register, Register),
 
     /// Get data from the stack at a specific offset and store it into a register
     /// without changing the stack address
@@ -43,11 +70,19 @@ pub enum AssemblyInstruction {
     /// Store data to the stack a given offset without adjusting the stack address pointer.
     StackStore(Register, u64),
 
-    /// Exit (returning Register)
-    Exit(Register),
+    /// Put the address of the stack at the given offset into the register.
+    StackAddress(Register, u64),
+
+    /// Perform a system call, whose number and arguments have been placed according to the
+    /// [target's convention](crate::compiler::backend::arch::SyscallConvention). The immediate
+    /// is encoded into the instruction if the target needs one (e.g. `svc #0x80`).
+    SystemCall(u16),
 
     /// Call a function (don't just jump to it)
     Call(String),
+
+    /// Return to the address in the link register, which the call put there.
+    Return,
     
     Label(Rc<String>),
 
@@ -56,6 +91,33 @@ pub enum AssemblyInstruction {
     Jump(Rc<String>),
     JumpEqual(Rc<String>),
     JumpNotEqual(Rc<String>),
+    JumpGreater(Rc<String>),
+    JumpGreaterOrEqual(Rc<String>),
+    /// Jump if the last addition carried (or the last subtraction didn't borrow)
+    JumpCarry(Rc<String>),
+    JumpNotCarry(Rc<String>),
+    /// Jump if the last signed operation didn't over- or underflow
+    JumpNotOverflow(Rc<String>),
+    /// Jump if the first compared value was lower, both treated as unsigned
+    JumpUnsignedLess(Rc<String>),
+    /// Jump to the label at the index in the register, looked up in the table
+    /// of targets that gets placed right after the jump under the given label.
+    JumpTable(Register, Rc<String>, Vec<Rc<String>>),
+
+    /// Stops the program abnormally with the given code.
+    Trap(u16),
+
+    /// Put the address of the symbol into the register, referring to it as the object format requires.
+    LoadAddress(Register, Rc<String>, ObjectFormat),
+    /// Place the bytes into the data section (or the bss section if they're all
+    /// zero) under the symbol, aligned to the given number of bytes.
+    StaticData(Rc<String>, Vec<u8>, u8),
+    /// Place the bytes into the read-only data section under the symbol,
+    /// aligned to the given number of bytes.
+    ReadOnlyData(Rc<String>, Vec<u8>, u8, ObjectFormat),
+
+    /// Lines of assembly written by the user, emitted as they are.
+    InlineAssembly(Vec<String>),
 }
 
 impl AssemblyInstruction {
@@ -63,22 +125,45 @@ impl AssemblyInstruction {
         match self {
             AssemblyInstruction::MoveReg(_, _) => InstructionMeta::MoveReg,
             AssemblyInstruction::MoveImm(_, _) => InstructionMeta::MoveImm,
-            AssemblyInstruction::Load(_, _, _) => InstructionMeta::Load,
+            AssemblyInstruction::Load(_, _, _, _) => InstructionMeta::Load,
             AssemblyInstruction::Store(_, _, _) => InstructionMeta::Store,
+            AssemblyInstruction::Extend(_, _, _) => InstructionMeta::Extend,
             AssemblyInstruction::StackStore(_, _) => InstructionMeta::StackStore,
+            AssemblyInstruction::StackAddress(_, _) => InstructionMeta::StackAddress,
             AssemblyInstruction::AddReg(_, _) => InstructionMeta::AddReg,
             AssemblyInstruction::SubReg(_, _) => InstructionMeta::SubReg,
             AssemblyInstruction::StackLoad(_, _) => InstructionMeta::StackLoad,
             AssemblyInstruction::AddImm(_, _) => InstructionMeta::AddImm,
             AssemblyInstruction::SubImm(_, _) => InstructionMeta::SubImm,
             AssemblyInstruction::MulReg(_, _) => InstructionMeta::MulReg,
-            AssemblyInstruction::DivReg(_, _) => InstructionMeta::DivReg,
-            AssemblyInstruction::Exit(_) => InstructionMeta::Exit,
+            AssemblyInstruction::MulHighReg(_, _, _) => InstructionMeta::MulHighReg,
+            AssemblyInstruction::DivReg(_, _, _) => InstructionMeta::DivReg,
+            AssemblyInstruction::RemReg(_, _, _, _) => InstructionMeta::RemReg,
+            AssemblyInstruction::And(_, _) => InstructionMeta::And,
+            AssemblyInstruction::Or(_, _) => InstructionMeta::Or,
+            AssemblyInstruction::Xor(_, _) => InstructionMeta::Xor,
+            AssemblyInstruction::Shl(_, _) => InstructionMeta::Shl,
+            AssemblyInstruction::Shr(_, _) => InstructionMeta::Shr,
+            AssemblyInstruction::Sar(_, _) => InstructionMeta::Sar,
+            AssemblyInstruction::SystemCall(_) => InstructionMeta::SystemCall,
             AssemblyInstruction::Call(_) => InstructionMeta::Call,
+            AssemblyInstruction::Return => InstructionMeta::Return,
             AssemblyInstruction::Label(_) => InstructionMeta::Label,
             AssemblyInstruction::JumpEqual(_) => InstructionMeta::JumpEqual,
             AssemblyInstruction::JumpNotEqual(_) => InstructionMeta::JumpNotEqual,
+            AssemblyInstruction::JumpGreater(_) => InstructionMeta::JumpGreater,
+            AssemblyInstruction::JumpGreaterOrEqual(_) => InstructionMeta::JumpGreaterOrEqual,
+            AssemblyInstruction::JumpCarry(_) => InstructionMeta::JumpCarry,
+            AssemblyInstruction::JumpNotCarry(_) => InstructionMeta::JumpNotCarry,
+            AssemblyInstruction::JumpNotOverflow(_) => InstructionMeta::JumpNotOverflow,
+            AssemblyInstruction::JumpUnsignedLess(_) => InstructionMeta::JumpUnsignedLess,
+            AssemblyInstruction::JumpTable(_, _, _) => InstructionMeta::JumpTable,
+            AssemblyInstruction::Trap(_) => InstructionMeta::Trap,
+            AssemblyInstruction::LoadAddress(_, _, _) => InstructionMeta::LoadAddress,
+            AssemblyInstruction::StaticData(_, _, _) => InstructionMeta::StaticData,
+            AssemblyInstruction::ReadOnlyData(_, _, _, _) => InstructionMeta::ReadOnlyData,
             AssemblyInstruction::Compare(_, _) => InstructionMeta::Compare,
+            AssemblyInstruction::InlineAssembly(_) => InstructionMeta::InlineAssembly,
             AssemblyInstruction::Jump(_) => Jump
         }
     }
@@ -98,7 +183,7 @@ impl AssemblyInstruction {
                 ]
             }
 
-            AssemblyInstruction::StackLoad(a, b) => {
+            AssemblyInstruction::StackLoad(a, b) | AssemblyInstruction::StackAddress(a, b) => {
                 vec![
                     (
                         String::from("$a"),
@@ -162,7 +247,7 @@ impl AssemblyInstruction {
                     )
                 ]
             },
-            AssemblyInstruction::Load(a, b, c) => {
+            AssemblyInstruction::Load(a, b, c, d) => {
                 vec![
                     (
                         String::from("$a"),
@@ -175,10 +260,30 @@ impl AssemblyInstruction {
                     (
                         String::from("$c"),
                         c.to_string()
+                    ),
+                    (
+                        String::from("$d"),
+                        d.to_string()
                     )
 
                 ]
             }
+            AssemblyInstruction::Extend(a, b, c) => {
+                vec![
+                    (
+                        String::from("$a"),
+                        a.name.clone()
+                    ),
+                    (
+                        String::from("$b"),
+                        b.to_string()
+                    ),
+                    (
+                        String::from("$c"),
+                        c.to_string()
+                    )
+                ]
+            }
             AssemblyInstruction::Store(a, b, c) => {
                 vec![
                     (
@@ -234,7 +339,9 @@ impl AssemblyInstruction {
             }
 
 
-            AssemblyInstruction::DivReg(a, b) | AssemblyInstruction::Compare(a, b ) => {
+            AssemblyInstruction::MulHighReg(a, b, _) | AssemblyInstruction::DivReg(a, b, _) | AssemblyInstruction::RemReg(a, b, _, _) | AssemblyInstruction::Compare(a, b ) |
+            AssemblyInstruction::And(a, b) | AssemblyInstruction::Or(a, b) | AssemblyInstruction::Xor(a, b) |
+            AssemblyInstruction::Shl(a, b) | AssemblyInstruction::Shr(a, b) | AssemblyInstruction::Sar(a, b) => {
                 vec![
                     (
                         String::from("$a"),
@@ -247,11 +354,11 @@ impl AssemblyInstruction {
                 ]
             }
 
-            AssemblyInstruction::Exit(a) => {
+            AssemblyInstruction::SystemCall(immediate) => {
                 vec![
                     (
                         String::from("$a"),
-                        a.name.clone()
+                        immediate.to_string()
                     )
                 ]
             }
@@ -263,7 +370,14 @@ impl AssemblyInstruction {
                     )
                 ]
             }
-            AssemblyInstruction::Label(label) | AssemblyInstruction::JumpEqual(label) | AssemblyInstruction::JumpNotEqual(label) | AssemblyInstruction::Jump(label) => {
+            AssemblyInstruction::Return => {
+                vec![]
+            }
+            AssemblyInstruction::Label(label) | AssemblyInstruction::JumpEqual(label) | AssemblyInstruction::JumpNotEqual(label) | AssemblyInstruction::Jump(label) |
+            AssemblyInstruction::JumpGreater(label) | AssemblyInstruction::JumpGreaterOrEqual(label) |
+            AssemblyInstruction::JumpCarry(label) | AssemblyInstruction::JumpNotCarry(label) |
+            AssemblyInstruction::JumpNotOverflow(label) |
+            AssemblyInstruction::JumpUnsignedLess(label) => {
                 vec![
                     (
                         String::from("$a"),
@@ -271,6 +385,66 @@ impl AssemblyInstruction {
                     )
                 ]
             }
+            AssemblyInstruction::JumpTable(index, table, targets) => {
+                vec![
+                    (
+                        String::from("$a"),
+                        index.name.clone()
+                    ),
+                    (
+                        String::from("$b"),
+                        table.deref().clone()
+                    ),
+                    (
+                        String::from("$c"),
+                        targets.iter().map(|x| x.deref().clone()).collect::<Vec<String>>().join(", ")
+                    )
+                ]
+            }
+            AssemblyInstruction::Trap(code) => {
+                vec![
+                    (
+                        String::from("$a"),
+                        code.to_string()
+                    )
+                ]
+            }
+            AssemblyInstruction::LoadAddress(a, symbol, _) => {
+                vec![
+                    (
+                        String::from("$a"),
+                        a.name.clone()
+                    ),
+                    (
+                        String::from("$b"),
+                        symbol.deref().clone()
+                    )
+                ]
+            }
+            AssemblyInstruction::StaticData(symbol, bytes, alignment) | AssemblyInstruction::ReadOnlyData(symbol, bytes, alignment, _) => {
+                vec![
+                    (
+                        String::from("$a"),
+                        symbol.deref().clone()
+                    ),
+                    (
+                        String::from("$b"),
+                        bytes.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(", ")
+                    ),
+                    (
+                        String::from("$c"),
+                        alignment.to_string()
+                    )
+                ]
+            }
+            AssemblyInstruction::InlineAssembly(lines) => {
+                vec![
+                    (
+                        String::from("$a"),
+                        lines.join("\n")
+                    )
+                ]
+            }
         }
     }
 
@@ -333,12 +507,47 @@ pub fn generate_assembly<T: Isa>(code: Vec<AssemblyInstruction>, arch: Architect
     file.flush().expect("");
 }
 
+/// Replaces the placeholders in a line of [inline assembly](Instruction::InlineAssembly)
+/// (e.g. `{0}`) by the registers of their operands, `{{` and `}}` by a single brace.
+fn insert_registers(line: &str, registers: &[Register]) -> String {
+    let mut result = String::new();
+    let mut characters = line.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        match character {
+            '{' | '}' if characters.peek() == Some(&character) => {
+                characters.next();
+                result.push(character);
+            }
+
+            '{' => {
+                let index: String = characters.by_ref().take_while(|x| *x != '}').collect();
+                result.push_str(&registers[index.parse::<usize>().unwrap()].name);
+            }
+
+            _ => result.push(character),
+        }
+    }
+
+    result
+}
+
 pub fn generate_assembly_instructions(code: Vec<Instruction>, architecture: Architecture) -> Vec<AssemblyInstruction> {
     let mut architecture = architecture;
     let mut instructions: Vec<AssemblyInstruction> = Vec::new();
 
     let mut function_start_idx: usize = 0;
 
+    // The arguments of the current function passed on the stack (with their offset above
+    // the frame) and the instructions loading them.
+    let mut stack_arguments: Vec<(Uuid, u64)> = Vec::new();
+    let mut stack_argument_loads: Vec<(usize, u64)> = Vec::new();
+
+    // Where the current function returns, the frame gets torn down there once its size is
+    // known. Calls overwrite the link register, which has to be saved then.
+    let mut return_sites: Vec<usize> = Vec::new();
+    let mut makes_calls = false;
+
     architecture.prepare_new_function();
 
 
@@ -391,62 +600,264 @@ pub fn generate_assembly_instructions(code: Vec<Instruction>, architecture: Arch
 
                 instructions.push(AssemblyInstruction::MulReg(reg_a.0, reg_b.0));
             }
-            Instruction::Div(obj_a, obj_b) => {
+            Instruction::MulHigh(obj_a, obj_b, signed) => {
                 let mut reg_a = architecture.get_object(obj_a, vec![obj_b]);
                 let mut reg_b = architecture.get_object(obj_b, vec![obj_a]);
 
                 instructions.append(reg_a.1.as_mut());
                 instructions.append(reg_b.1.as_mut());
 
-                instructions.push(AssemblyInstruction::DivReg(reg_a.0, reg_b.0));
+                instructions.push(AssemblyInstruction::MulHighReg(reg_a.0, reg_b.0, signed));
             }
+            Instruction::Div(obj_a, obj_b, signed) => {
+                let mut reg_a = architecture.get_object(obj_a, vec![obj_b]);
+                let mut reg_b = architecture.get_object(obj_b, vec![obj_a]);
 
-            Instruction::Mod(_, _) => {}
-            Instruction::Load(_, _, _) => {}
-            Instruction::Store(_, _, _) => {}
+                instructions.append(reg_a.1.as_mut());
+                instructions.append(reg_b.1.as_mut());
+
+                instructions.push(AssemblyInstruction::DivReg(reg_a.0, reg_b.0, signed));
+            }
+            Instruction::Mod(obj_a, obj_b, signed) => {
+                let mut reg_a = architecture.get_object(obj_a, vec![obj_b]);
+                let mut reg_b = architecture.get_object(obj_b, vec![obj_a]);
+
+                instructions.append(reg_a.1.as_mut());
+                instructions.append(reg_b.1.as_mut());
+
+                // The quotient is only needed within the instruction, so the register stays empty.
+                let mut quotient = architecture.provide_empty_register(vec![obj_a, obj_b], &|_| true);
+                instructions.append(quotient.1.as_mut());
+
+                instructions.push(AssemblyInstruction::RemReg(reg_a.0, reg_b.0, quotient.0, signed));
+            }
+
+            Instruction::And(obj_a, obj_b) | Instruction::Or(obj_a, obj_b) | Instruction::Xor(obj_a, obj_b) |
+            Instruction::Shl(obj_a, obj_b) | Instruction::Shr(obj_a, obj_b) | Instruction::Sar(obj_a, obj_b) => {
+                let mut reg_a = architecture.get_object(obj_a, vec![obj_b]);
+                let mut reg_b = architecture.get_object(obj_b, vec![obj_a]);
+
+                instructions.append(reg_a.1.as_mut());
+                instructions.append(reg_b.1.as_mut());
+
+                instructions.push(match instruction {
+                    Instruction::And(_, _) => AssemblyInstruction::And(reg_a.0, reg_b.0),
+                    Instruction::Or(_, _) => AssemblyInstruction::Or(reg_a.0, reg_b.0),
+                    Instruction::Xor(_, _) => AssemblyInstruction::Xor(reg_a.0, reg_b.0),
+                    Instruction::Shl(_, _) => AssemblyInstruction::Shl(reg_a.0, reg_b.0),
+                    Instruction::Shr(_, _) => AssemblyInstruction::Shr(reg_a.0, reg_b.0),
+                    _ => AssemblyInstruction::Sar(reg_a.0, reg_b.0),
+                });
+            }
+
+            Instruction::Load(obj_a, obj_b, size, signed) => {
+                let mut reg_a = architecture.get_object(obj_a, vec![obj_b]);
+                let mut reg_b = architecture.get_object(obj_b, vec![obj_a]);
+
+                instructions.append(reg_a.1.as_mut());
+                instructions.append(reg_b.1.as_mut());
+
+                instructions.push(AssemblyInstruction::Load(reg_a.0, reg_b.0, size, signed));
+            }
+            Instruction::Store(obj_a, obj_b, size) => {
+                let mut reg_a = architecture.get_object(obj_a, vec![obj_b]);
+                let mut reg_b = architecture.get_object(obj_b, vec![obj_a]);
+
+                instructions.append(reg_a.1.as_mut());
+                instructions.append(reg_b.1.as_mut());
+
+                instructions.push(AssemblyInstruction::Store(reg_a.0, reg_b.0, size));
+            }
+            Instruction::StackAllocate(obj, size) => {
+                let offset = architecture.allocate_stack(size as usize);
+                let mut reg_a = architecture.get_object(obj, vec![]);
+
+                instructions.append(reg_a.1.as_mut());
+
+                instructions.push(AssemblyInstruction::StackAddress(reg_a.0, offset as u64));
+            }
+            Instruction::Extend(obj, size, signed) => {
+                let mut reg_a = architecture.get_object(obj, vec![]);
+
+                instructions.append(reg_a.1.as_mut());
+
+                instructions.push(AssemblyInstruction::Extend(reg_a.0, size, signed));
+            }
             Instruction::Drop(obj) => {
                 architecture.delete_object(obj);
             }
             Instruction::Exit(obj) => {
+                let convention = architecture.syscall_convention.clone();
                 let mut reg_a = architecture.get_object(obj, vec![]);
 
                 instructions.append(reg_a.1.as_mut());
 
-                instructions.push(AssemblyInstruction::Exit(reg_a.0));
+                // The process ends, so the registers don't need to be kept track of.
+                instructions.push(AssemblyInstruction::MoveImm(convention.number_register, convention.exit_number));
+                instructions.push(AssemblyInstruction::MoveReg(architecture.get_register_for_syscall_argument(0).unwrap(), reg_a.0));
+                instructions.push(AssemblyInstruction::SystemCall(convention.immediate));
             }
-            Instruction::Call(asm_name, args, _out) => {
-                instructions.append(&mut architecture.backup_caller_saved_regs());
+            Instruction::Syscall(result, number, args) => {
+                let convention = architecture.syscall_convention.clone();
+                let objects = [args.clone(), vec![number]].concat();
+
+                // The kernel keeps the same registers as C functions do.
+                instructions.append(&mut architecture.backup_caller_saved_regs(FunctionStyle::C));
+
+                // The number register has to stay untouched while the arguments are moved. If
+                // objects can be placed in it (x8 on Linux), it's reserved for that.
+                let mut reservation = None;
+                if architecture.register_map.registers.iter().any(|x| x.0 == convention.number_register) {
+                    let (placeholder, mut movement) = architecture.reserve_register(convention.number_register.clone(), objects.clone());
+                    instructions.append(&mut movement);
+                    reservation = Some(placeholder);
+
+                    let mut reg_number = architecture.get_object(number, objects.clone());
+                    instructions.append(reg_number.1.as_mut());
+                    instructions.push(AssemblyInstruction::MoveReg(convention.number_register, reg_number.0));
+                } else {
+                    instructions.append(&mut architecture.move_into_reg(number, convention.number_register, objects.clone()));
+                }
+
+                for (i, arg) in args.iter().enumerate() {
+                    let reg = architecture.get_register_for_syscall_argument(i)
+                        .expect("The amount of arguments is checked against the convention while checking the types");
+
+                    instructions.append(&mut architecture.move_into_reg(*arg, reg, objects.clone()));
+                }
+
+                instructions.push(AssemblyInstruction::SystemCall(convention.immediate));
 
-                for arg in args.clone().iter().enumerate() {
-                    let i = arg.0;
-                    let arg = arg.1;
+                for object in objects.into_iter().chain(reservation) {
+                    architecture.delete_object(object);
+                }
 
-                    let reg = architecture.get_register_for_argument(i, FunctionStyle::C);
-                    let mut move_instructions = architecture.move_into_reg(*arg, reg.unwrap(), args.clone());
+                architecture.move_into_reg_no_code(result, architecture.get_register_for_syscall_argument(0).unwrap());
+            }
+            Instruction::Call(asm_name, args, outputs, style, variadic_start, indirect_result) => {
+                makes_calls = true;
+                instructions.append(&mut architecture.backup_caller_saved_regs(style));
+
+                let locations = architecture.get_argument_locations(args.len(), style, variadic_start);
+
+                // Arguments that are passed on the stack are collected in the frame first, as
+                // objects on the stack can't be found anymore once the stack pointer moved.
+                let stack_args: Vec<(Uuid, u64)> = args.iter().zip(locations.iter())
+                    .filter_map(|(arg, location)| match location {
+                        ArgumentLocation::Stack(offset) => Some((*arg, *offset)),
+                        ArgumentLocation::Register(_) => None,
+                    })
+                    .collect();
+                let stack_area_size = architecture.get_stack_argument_area_size(stack_args.len());
+                let collection_offset = if stack_args.is_empty() { 0 } else { architecture.allocate_stack(stack_area_size as usize) as u64 };
+
+                for (arg, offset) in stack_args.iter() {
+                    let mut reg = architecture.get_object(*arg, args.clone());
+
+                    instructions.append(reg.1.as_mut());
+                    instructions.push(AssemblyInstruction::StackStore(reg.0, collection_offset + offset));
+                }
+
+                for (arg, location) in args.iter().zip(locations) {
+                    let ArgumentLocation::Register(reg) = location else { continue };
+                    let mut move_instructions = architecture.move_into_reg(*arg, reg.clone(), args.clone());
 
                     instructions.append(move_instructions.as_mut());
+                    architecture.move_into_reg_no_code(*arg, reg);
+                }
+
+                // The address is passed in the scratch register on aarch64, so it's only put
+                // there right before the call. It can't be loaded once the stack pointer moved.
+                let indirect_result_register = indirect_result.map(|address| {
+                    let mut reg = architecture.get_object(address, [args.clone(), vec![address]].concat());
+
+                    instructions.append(reg.1.as_mut());
+                    reg.0
+                });
+
+                // The scratch register doesn't survive the call anyway.
+                if !stack_args.is_empty() {
+                    let scratch = architecture.get_scratch_register();
+                    instructions.push(AssemblyInstruction::SubImm(architecture.get_stack_pointer(), stack_area_size as i64));
+
+                    for (_, offset) in stack_args.iter() {
+                        instructions.push(AssemblyInstruction::StackLoad(scratch.clone(), stack_area_size + collection_offset + offset));
+                        instructions.push(AssemblyInstruction::StackStore(scratch.clone(), *offset));
+                    }
+                }
+
+                if let Some(register) = indirect_result_register {
+                    instructions.push(AssemblyInstruction::MoveReg(architecture.get_register_for_indirect_result(), register));
                 }
 
                 instructions.push(AssemblyInstruction::Call(asm_name));
+
+                if !stack_args.is_empty() {
+                    instructions.push(AssemblyInstruction::AddImm(architecture.get_stack_pointer(), stack_area_size as i64));
+                }
                 
-                for arg in args.clone().iter() {
+                for arg in args.iter().chain(indirect_result.iter()) {
                     architecture.delete_object(*arg);
                 }
 
+                for (i, output) in outputs.iter().enumerate() {
+                    let reg = architecture.get_register_for_return_value(i, style);
+                    architecture.move_into_reg_no_code(*output, reg.unwrap());
+                }
+
             },
             Instruction::Label(asm_name, _global) => {
                 instructions.push(AssemblyInstruction::Label(asm_name));
             }
+            Instruction::Return(values, style) => {
+                // The code following the return (e.g. after an if-block) continues with the registers as they are.
+                let mut returning = architecture.clone();
+
+                for (i, value) in values.iter().enumerate() {
+                    let register = returning.get_register_for_return_value(i, style)
+                        .expect("The amount of return values is checked while checking the types");
+
+                    instructions.append(&mut returning.move_into_reg(*value, register.clone(), values.clone()));
+                    returning.move_into_reg_no_code(*value, register);
+                }
+
+                instructions.append(&mut returning.restore_backup_map(values));
+                return_sites.push(instructions.len());
+
+                // The frame has to fit whatever got stored on the way.
+                architecture.register_map.stack_offset = architecture.register_map.stack_offset.max(returning.register_map.stack_offset);
+            }
             Instruction::FunctionEnd => {
-                let (header, mut trailer) = architecture.end_function();
-                instructions.append(&mut trailer);
+                // Reaching the end of the function returns without values.
+                instructions.append(&mut architecture.restore_backup_map(vec![]));
+                return_sites.push(instructions.len());
+
+                let link_register_offset = makes_calls.then(|| architecture.allocate_stack(8) as u64);
+
+                // The frame's size is known now, so the arguments above it can be located.
+                let frame_size = architecture.get_frame_size();
+
+                for (index, offset) in stack_argument_loads.drain(..) {
+                    if let AssemblyInstruction::StackLoad(register, _) = instructions[index].clone() {
+                        instructions[index] = AssemblyInstruction::StackLoad(register, frame_size + offset);
+                    }
+                }
+
+                let (header, trailer) = architecture.end_function(link_register_offset);
+
+                // Inserting from the back keeps the indices of the earlier sites valid.
+                for site in return_sites.drain(..).rev() {
+                    instructions.splice(site..site, trailer.clone());
+                }
+
                 instructions.splice(function_start_idx..function_start_idx, header);
+                makes_calls = false;
             }
-            Instruction::ReceiveArgument(arg_name, arg_index) => {
-                if let Some(position) = architecture.get_register_for_argument(arg_index as usize, FunctionStyle::C) {
+            Instruction::ReceiveArgument(arg_name, arg_index, style) => {
+                if let Some(position) = architecture.get_register_for_argument(arg_index as usize, style) {
                     architecture.move_into_reg_no_code(arg_name, position);
                 } else {
-                    todo!("No register for argument found")
+                    stack_arguments.push((arg_name, architecture.get_stack_offset_for_argument(arg_index as usize, style)));
                 }
             }
             Instruction::JumpConditional(condition, label) => {
@@ -469,27 +880,660 @@ pub fn generate_assembly_instructions(code: Vec<Instruction>, architecture: Arch
                     reg_b = Some(register_b.0);
                 }
 
-                match condition.comparison {
-                    JumpComparisonType::Equal => {
-                        instructions.push(AssemblyInstruction::JumpEqual(label));
-                    }
+                instructions.push(match condition.comparison {
+                    JumpComparisonType::Equal => AssemblyInstruction::JumpEqual(label),
+                    JumpComparisonType::NotEqual => AssemblyInstruction::JumpNotEqual(label),
+                    JumpComparisonType::Greater => AssemblyInstruction::JumpGreater(label),
+                    JumpComparisonType::GreaterOrEqual => AssemblyInstruction::JumpGreaterOrEqual(label),
+                    JumpComparisonType::Carry => AssemblyInstruction::JumpCarry(label),
+                    JumpComparisonType::NotCarry => AssemblyInstruction::JumpNotCarry(label),
+                    JumpComparisonType::NotOverflow => AssemblyInstruction::JumpNotOverflow(label),
+                    JumpComparisonType::UnsignedLess => AssemblyInstruction::JumpUnsignedLess(label),
+                });
+            }
+            Instruction::Jump(label) => {
+                instructions.push(AssemblyInstruction::Jump(label))
+            }
+            Instruction::JumpTable(obj, table, targets) => {
+                let mut reg_a = architecture.get_object(obj, vec![]);
 
-                    JumpComparisonType::NotEqual => {
-                        instructions.push(AssemblyInstruction::JumpNotEqual(label));
-                    }
+                instructions.append(reg_a.1.as_mut());
 
-                    _ => { todo!() }
+                instructions.push(AssemblyInstruction::JumpTable(reg_a.0, table, targets));
+            }
+            Instruction::Trap(code, symbol, length) => {
+                let convention = architecture.syscall_convention.clone();
+
+                // The program stops here, so the registers don't need to be kept track of.
+                instructions.append(&mut vec![
+                    AssemblyInstruction::MoveImm(architecture.get_register_for_syscall_argument(0).unwrap(), STANDARD_ERROR),
+                    AssemblyInstruction::LoadAddress(architecture.get_register_for_syscall_argument(1).unwrap(), symbol, architecture.object_format),
+                    AssemblyInstruction::MoveImm(architecture.get_register_for_syscall_argument(2).unwrap(), length as i64),
+                    AssemblyInstruction::MoveImm(convention.number_register, convention.write_number),
+                    AssemblyInstruction::SystemCall(convention.immediate),
+                    AssemblyInstruction::Trap(code),
+                ]);
+            }
+            Instruction::LoadAddress(obj, symbol) => {
+                let mut reg_a = architecture.get_object(obj, vec![]);
+
+                instructions.append(reg_a.1.as_mut());
+
+                instructions.push(AssemblyInstruction::LoadAddress(reg_a.0, symbol, architecture.object_format));
+            }
+            Instruction::StaticData(symbol, bytes, alignment) => {
+                instructions.push(AssemblyInstruction::StaticData(symbol, bytes, alignment))
+            }
+            Instruction::InlineAssembly(assembly) => {
+                let inputs: Vec<Uuid> = assembly.inputs.iter().map(|x| x.1).collect();
+                let mut registers: Vec<Option<Register>> = vec![None; assembly.inputs.len() + assembly.outputs.len()];
+
+                // The clobbered registers get emptied and stay reserved, so no operand is placed in them.
+                let mut reserved: Vec<Uuid> = vec![];
+
+                // Unknown and reserved registers are reported while checking the types.
+                for clobber in assembly.clobbers.iter() {
+                    let register = architecture.get_register_by_name(clobber).unwrap();
+                    let (placeholder, mut movement) = architecture.reserve_register(register, [inputs.clone(), reserved.clone()].concat());
+
+                    instructions.append(&mut movement);
+                    reserved.push(placeholder);
+                }
+
+                for (i, input) in assembly.inputs.iter() {
+                    let mut reg = architecture.get_object(*input, [inputs.clone(), reserved.clone()].concat());
+
+                    instructions.append(reg.1.as_mut());
+                    registers[*i] = Some(reg.0);
+                }
+
+                // Outputs get registers of their own, so the inputs stay intact.
+                let mut outputs: Vec<Uuid> = vec![];
+
+                for (i, output) in assembly.outputs.iter() {
+                    let ignoring = [inputs.clone(), reserved.clone(), outputs.clone()].concat();
+                    let mut reg = architecture.provide_empty_register(ignoring, &|x| x.kind == RegisterKind::GeneralPurpose);
+
+                    instructions.append(reg.1.as_mut());
+                    architecture.move_into_reg_no_code(*output, reg.0.clone());
+                    registers[*i] = Some(reg.0);
+                    outputs.push(*output);
+                }
+
+                let registers: Vec<Register> = registers.into_iter().map(Option::unwrap).collect();
+                instructions.push(AssemblyInstruction::InlineAssembly(assembly.lines.iter().map(|x| insert_registers(x, &registers)).collect()));
+
+                for placeholder in reserved {
+                    architecture.delete_object(placeholder);
                 }
             }
-            Instruction::Jump(label) => {
-                instructions.push(AssemblyInstruction::Jump(label))
+            Instruction::ReadOnlyData(symbol, bytes, alignment) => {
+                instructions.push(AssemblyInstruction::ReadOnlyData(symbol, bytes, alignment, architecture.object_format))
             }
-            Instruction::FunctionStart => {
+            Instruction::FunctionStart(style) => {
                 println!("FunctionStart at {}", i);
                 function_start_idx = instructions.len();
+                architecture.start_function(style);
+
+                // Arguments passed on the stack are loaded right away, their offset gets
+                // corrected once the size of the frame below them is known.
+                for (argument, offset) in stack_arguments.drain(..) {
+                    let mut reg = architecture.provide_empty_register(vec![], &|x| x.saving_behaviour != RegisterSavingBehaviour::Scratch);
+
+                    instructions.append(reg.1.as_mut());
+                    stack_argument_loads.push((instructions.len(), offset));
+                    instructions.push(AssemblyInstruction::StackLoad(reg.0.clone(), offset));
+                    architecture.move_into_reg_no_code(argument, reg.0);
+                }
+            }
+            Instruction::ReceiveIndirectResult(object) => {
+                // The address is passed in the scratch register on aarch64, which gets overwritten soon.
+                let mut reg = architecture.provide_empty_register(vec![], &|x| x.saving_behaviour != RegisterSavingBehaviour::Scratch);
+
+                instructions.append(reg.1.as_mut());
+                instructions.push(AssemblyInstruction::MoveReg(reg.0.clone(), architecture.get_register_for_indirect_result()));
+                architecture.move_into_reg_no_code(object, reg.0);
             }
         }
     }
 
     instructions
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::ops::Deref;
+    use std::process::Command;
+    use std::rc::Rc;
+    use crate::compiler::backend::arch::aarch64::Aarch64Asm;
+    use crate::compiler::backend::arch::{Isa, Target};
+    use crate::compiler::backend::assembly::generate_assembly_instructions;
+    use crate::compiler::backend::context::{Context, DEFAULT_ALLOCATOR};
+    use crate::compiler::backend::flattener::flatten;
+    use crate::compiler::data_types::integer::build_integer_types;
+    use crate::compiler::data_types::object::ObjectType;
+    use crate::compiler::line_map::LineMap;
+    use crate::compiler::parser::parse::parse;
+    use crate::compiler::parser::tree::node::{CodeBlockArray, Node};
+    use crate::compiler::tokenization::tokenizer::tokenize_file;
+    use crate::compiler::type_checker::check_types;
+
+    /// Compiles the code for aarch64 macOS and returns the lines of the
+    /// generated assembly, without the boilerplate around it.
+    fn compile_to_assembly(code: &str) -> Vec<String> {
+        compile_to_assembly_for(code, Target::Aarch64MacOs)
+    }
+
+    /// Like [compile_to_assembly], but for the given target.
+    fn compile_to_assembly_for(code: &str, target: Target) -> Vec<String> {
+        let mut line_map = LineMap::new();
+        let tokens = tokenize_file(code.to_string(), "n/a", 0, Rc::new(build_integer_types()), &mut line_map);
+        let mut object_types = Rc::new(ObjectType::generate_built_ins());
+
+        let parsed = parse(vec![tokens], &mut line_map, &mut object_types).unwrap();
+
+        let mut context = Context::clear(line_map);
+        context.options.target = target;
+        context.options.allocator = Rc::new(target.get_c_symbol(DEFAULT_ALLOCATOR));
+        object_types.iter().for_each(|object_type| {
+            context.datatypes.insert(object_type.type_uuid, object_type.clone());
+        });
+
+        let mut parsed = parsed.downcast_rc::<CodeBlockArray>().unwrap().deref().clone();
+        parsed.perform_early_context_changes(&mut context);
+
+        let parsed: Rc<dyn Node> = Rc::new(parsed);
+        check_types(parsed.clone(), &mut context);
+        assert_eq!(context.line_map.error_count, 0);
+
+        let flattened = flatten(parsed, &mut context);
+
+        generate_assembly_instructions(flattened, target.generate_architecture())
+            .into_iter()
+            .map(|x| Aarch64Asm::from(x).to_string())
+            .collect::<String>()
+            .lines()
+            .map(|x| x.trim().to_string())
+            .collect()
+    }
+
+    /// Splits a line of assembly into its mnemonic and its operands.
+    fn split_instruction(line: &str) -> (&str, Vec<&str>) {
+        let (mnemonic, operands) = line.split_once('\t').unwrap_or((line, ""));
+
+        (mnemonic, operands.split(", ").filter(|x| !x.is_empty()).collect())
+    }
+
+    /// Follows the immediates moved into registers through the registers and the
+    /// stack, returning what each register holds after the lines.
+    fn trace_immediates(lines: &[String]) -> HashMap<&str, &str> {
+        let mut registers: HashMap<&str, &str> = HashMap::new();
+        let mut stack: HashMap<&str, &str> = HashMap::new();
+
+        for line in lines {
+            match split_instruction(line) {
+                ("mov", operands) => {
+                    let value = registers.get(operands[1]).copied().unwrap_or(operands[1]);
+                    registers.insert(operands[0], value);
+                }
+                // Registers nothing was moved into, like the link register, are stored as they are.
+                ("str", operands) => { stack.insert(operands[2], registers.get(operands[0]).copied().unwrap_or(operands[0])); }
+                ("ldr", operands) => { registers.insert(operands[0], stack[operands[2]]); }
+                _ => {}
+            }
+        }
+
+        registers
+    }
+
+    /// Runs the code from `_start` until it exits and returns the exit code. Branches, calls
+    /// and the stack are followed, but only the instructions needed to calculate values are supported.
+    fn execute_until_exit(lines: &[String]) -> i64 {
+        let mut registers: HashMap<String, u64> = HashMap::from([("sp".to_string(), 0x10000)]);
+        let mut memory: HashMap<u64, u8> = HashMap::new();
+
+        // The flags of the last comparison, addition or subtraction: negative, zero, carry and overflow.
+        let mut flags = (false, false, false, false);
+
+        // `w` registers are the lower halves of the `x` registers, writing them clears the upper one.
+        fn read(registers: &HashMap<String, u64>, operand: &str) -> u64 {
+            if let Some(immediate) = operand.strip_prefix('#') {
+                return immediate.parse::<i64>().unwrap() as u64;
+            }
+
+            let value = registers.get(&operand.replacen('w', "x", 1)).copied().unwrap_or_default();
+            if operand.starts_with('w') { value as u32 as u64 } else { value }
+        }
+
+        fn write(registers: &mut HashMap<String, u64>, operand: &str, value: u64) {
+            let value = if operand.starts_with('w') { value as u32 as u64 } else { value };
+            registers.insert(operand.replacen('w', "x", 1), value);
+        }
+
+        let labels: HashMap<&str, usize> = lines.iter().enumerate().filter_map(|(i, x)| Some((x.strip_suffix(':')?, i))).collect();
+        let mut pc = labels["_start"];
+
+        loop {
+            pc += 1;
+            let line = &lines[pc];
+
+            if line.is_empty() || line.ends_with(':') || line.starts_with('.') { continue; }
+
+            let (mnemonic, operands) = split_instruction(line);
+            let value = |i: usize| read(&registers, operands[i]);
+
+            // Wide immediates are put together from 16-bit parts (e.g. `movk x0, #1, lsl #16`).
+            let shift = || operands[2].strip_prefix("lsl #").unwrap().parse::<u64>().unwrap();
+
+            // Memory is addressed by a register and an optional offset (e.g. `[sp, #16]`).
+            let address = || {
+                let operand = operands[1..].join(", ");
+                let operand = operand.trim_start_matches('[').trim_end_matches(']');
+                let (base, offset) = operand.split_once(", ").unwrap_or((operand, "#0"));
+
+                read(&registers, base).wrapping_add(read(&registers, offset))
+            };
+
+            let size = || match mnemonic.trim_start_matches("ldr").trim_start_matches("str") {
+                "b" | "sb" => 1,
+                "h" | "sh" => 2,
+                "sw" => 4,
+                _ if operands[0].starts_with('w') => 4,
+                _ => 8,
+            };
+
+            let condition = match mnemonic {
+                "b" => Some(true),
+                "b.eq" => Some(flags.1),
+                "bne" => Some(!flags.1),
+                "b.gt" => Some(!flags.1 && flags.0 == flags.3),
+                "b.ge" => Some(flags.0 == flags.3),
+                "b.cs" => Some(flags.2),
+                "b.cc" | "b.lo" => Some(!flags.2),
+                "b.vc" => Some(!flags.3),
+                _ => None,
+            };
+
+            if let Some(condition) = condition {
+                if condition { pc = labels[operands[0]]; }
+                continue;
+            }
+
+            let result = match mnemonic {
+                "svc" if read(&registers, "x16") == 1 => return read(&registers, "x0") as i64,
+                // The message of a trap is written before it stops the program.
+                "svc" => continue,
+                "brk" => panic!("The program trapped with `{}`", line),
+                "bl" => {
+                    registers.insert("x30".to_string(), pc as u64);
+                    pc = labels[operands[0]];
+                    continue;
+                }
+                "ret" => {
+                    pc = read(&registers, "x30") as usize;
+                    continue;
+                }
+                "cmp" => {
+                    let (a, b) = (value(0), value(1));
+                    flags = ((a.wrapping_sub(b) as i64) < 0, a == b, a >= b, (a as i64).overflowing_sub(b as i64).1);
+                    continue;
+                }
+                "str" | "strb" | "strh" => {
+                    let (address, bytes) = (address(), value(0).to_le_bytes());
+
+                    for i in 0..size() {
+                        memory.insert(address + i, bytes[i as usize]);
+                    }
+
+                    continue;
+                }
+                "ldr" | "ldrb" | "ldrh" | "ldrsb" | "ldrsh" | "ldrsw" => {
+                    let (address, size) = (address(), size());
+                    let loaded = (0..size).fold(0, |result, i| result | (memory.get(&(address + i)).copied().unwrap_or_default() as u64) << (i * 8));
+
+                    // Signed loads extend the sign of the loaded value.
+                    match mnemonic.starts_with("ldrs") {
+                        true => ((loaded << (64 - size * 8)) as i64 >> (64 - size * 8)) as u64,
+                        false => loaded,
+                    }
+                }
+                "mov" => value(1),
+                "movz" => value(1) << shift(),
+                "movn" => !(value(1) << shift()),
+                "movk" => value(0) & !(0xffff << shift()) | value(1) << shift(),
+                "add" => value(1).wrapping_add(value(2)),
+                "sub" => value(1).wrapping_sub(value(2)),
+                "adds" => {
+                    let (a, b) = (value(1), value(2));
+                    flags = ((a.wrapping_add(b) as i64) < 0, a.wrapping_add(b) == 0, a.overflowing_add(b).1, (a as i64).overflowing_add(b as i64).1);
+                    a.wrapping_add(b)
+                }
+                "subs" => {
+                    let (a, b) = (value(1), value(2));
+                    flags = ((a.wrapping_sub(b) as i64) < 0, a == b, a >= b, (a as i64).overflowing_sub(b as i64).1);
+                    a.wrapping_sub(b)
+                }
+                "mul" => value(1).wrapping_mul(value(2)),
+                "umulh" => ((value(1) as u128 * value(2) as u128) >> 64) as u64,
+                "smulh" => ((value(1) as i64 as i128 * value(2) as i64 as i128) >> 64) as u64,
+                "udiv" => value(1).checked_div(value(2)).unwrap_or_default(),
+                "sdiv" => (value(1) as i64).checked_div(value(2) as i64).unwrap_or_default() as u64,
+                "msub" => value(3).wrapping_sub(value(1).wrapping_mul(value(2))),
+                "and" => value(1) & value(2),
+                "orr" => value(1) | value(2),
+                "eor" => value(1) ^ value(2),
+                "lsl" => value(1) << (value(2) & 63),
+                "lsr" => value(1) >> (value(2) & 63),
+                "asr" => ((value(1) as i64) >> (value(2) & 63)) as u64,
+                "uxtb" => value(1) as u8 as u64,
+                "uxth" => value(1) as u16 as u64,
+                "sxtb" => value(1) as i8 as u64,
+                "sxth" => value(1) as i16 as u64,
+                "sxtw" => value(1) as i32 as u64,
+                _ => panic!("`{}` isn't supported while executing", line),
+            };
+
+            write(&mut registers, operands[0], result);
+        }
+    }
+
+    #[test]
+    fn test_remainder_lowering() {
+        let assembly = compile_to_assembly("extern \"C\" \"_start\" func start() {\nvar a: i32 = 10;\na %= 3;\nvar b: u32 = 10;\nb %= 4;\nexit a + b as i32;\n}");
+
+        for division in ["sdiv", "udiv"] {
+            let index = assembly.iter().position(|x| split_instruction(x).0 == division).unwrap();
+            let (_, operands) = split_instruction(&assembly[index]);
+            let (quotient, dividend, divisor) = (operands[0], operands[1], operands[2]);
+
+            // remainder = dividend - quotient * divisor
+            assert_eq!(split_instruction(&assembly[index + 1]), ("msub", vec![dividend, quotient, divisor, dividend]));
+            assert_ne!(quotient, dividend);
+            assert_ne!(quotient, divisor);
+        }
+    }
+
+    #[test]
+    fn test_sized_store_and_load_round_trip() {
+        for (datatype, store, load) in [("u8", "strb", "ldrb"), ("u16", "strh", "ldrh")] {
+            let assembly = compile_to_assembly(&format!("extern \"C\" \"_start\" func start() {{\nvar a: [{}; 2] = [1, 2];\na[1] = 7;\nexit a[1] as i32;\n}}", datatype));
+
+            let value_index = assembly.iter().position(|x| {
+                let (mnemonic, operands) = split_instruction(x);
+                mnemonic == "mov" && operands.get(1) == Some(&"#7")
+            }).unwrap();
+            let value = split_instruction(&assembly[value_index]).1[0].replacen('x', "w", 1);
+
+            // The value gets stored with the width of the element ...
+            let store_index = value_index + assembly[value_index..].iter().position(|x| split_instruction(x).0 == store).unwrap();
+            assert_eq!(split_instruction(&assembly[store_index]).1[0], value);
+
+            // ... and loaded back the same way, into a 32-bit register that gets zero extended.
+            let load_index = store_index + assembly[store_index..].iter().position(|x| split_instruction(x).0 == load).unwrap();
+            assert!(split_instruction(&assembly[load_index]).1[0].starts_with('w'));
+        }
+    }
+
+    #[test]
+    fn test_smisc_arguments_beyond_c_registers() {
+        let assembly = compile_to_assembly("func sum(a: u64, b: u64, c: u64, d: u64, e: u64, f: u64, g: u64, h: u64, i: u64, j: u64) {\nexit (a + b + c + d + e + f + g + h + i + j) as i32;\n}\nextern \"C\" \"_start\" func start() {\nsum(1, 2, 3, 4, 5, 6, 7, 8, 9, 10);\n}");
+
+        let start = assembly.iter().position(|x| x == "_start:").unwrap();
+        let call = assembly.iter().position(|x| split_instruction(x).0 == "bl").unwrap();
+        let registers = trace_immediates(&assembly[start..call]);
+
+        // The first eight arguments are passed like in C, the others continue after the scratch register x8.
+        let argument_registers = ["x0", "x1", "x2", "x3", "x4", "x5", "x6", "x7", "x9", "x10"];
+        for (i, register) in argument_registers.iter().enumerate() {
+            assert_eq!(registers[register], format!("#{}", i + 1), "argument {} in {}", i + 1, register);
+        }
+
+        // The callee receives them from the same registers.
+        let body = &assembly[..start];
+        for register in ["x9", "x10"] {
+            assert!(body.iter().any(|x| split_instruction(x).0 == "adds" && split_instruction(x).1[2] == register));
+        }
+    }
+
+    #[test]
+    fn test_full_width_multiplication_overflow_check() {
+        for (datatype, multiplication) in [("i64", "smulh"), ("u64", "umulh")] {
+            let assembly = compile_to_assembly(&format!("extern \"C\" \"_start\" func start() {{\nvar a: {0} = 3;\nvar b: {0} = a * a;\nexit b as i32;\n}}", datatype));
+
+            let high_index = assembly.iter().position(|x| split_instruction(x).0 == multiplication).unwrap();
+            let low_index = assembly.iter().position(|x| split_instruction(x).0 == "mul").unwrap();
+            let upper_half = split_instruction(&assembly[high_index]).1[0];
+
+            // The upper half is calculated from the operands, so before the multiplication overwrites one of them.
+            assert!(high_index < low_index);
+
+            let comparison = assembly[low_index..].iter().find(|x| split_instruction(x).0 == "cmp").unwrap();
+            assert_eq!(split_instruction(comparison).1[0], upper_half);
+
+            if datatype == "i64" {
+                assert!(assembly[low_index..].iter().any(|x| split_instruction(x).0 == "asr"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_negation_overflow_check() {
+        let assembly = compile_to_assembly("extern \"C\" \"_start\" func start() {\nvar a: i64 = 5;\nvar b: i64 = -a;\nexit b as i32;\n}");
+
+        let index = assembly.iter().position(|x| split_instruction(x).0 == "subs").unwrap();
+        assert_eq!(split_instruction(&assembly[index + 1]).0, "b.vc");
+    }
+
+    #[test]
+    fn test_trap_reports_source_location() {
+        let assembly = compile_to_assembly("extern \"C\" \"_start\" func start() {\nvar a: i32 = 5;\na += 1;\nexit a;\n}");
+
+        let trap_index = assembly.iter().position(|x| x == "brk\t#1").unwrap();
+        assert_eq!(assembly[trap_index - 1], "svc\t#0x80");
+        assert_eq!(assembly[trap_index - 2], "mov\tx16, #4");
+
+        let message: Vec<u8> = assembly.iter()
+            .find_map(|x| x.strip_prefix(".byte\t"))
+            .unwrap()
+            .split(", ")
+            .map(|x| x.parse().unwrap())
+            .collect();
+
+        assert_eq!(String::from_utf8(message).unwrap(), "integer overflow at n/a:3:1\n");
+    }
+
+    #[test]
+    fn test_shifts_stay_within_their_type() {
+        let cases = [
+            ("var a: u64 = 1 << 63;\na = a >> 62;\nexit a as i32;", 2),
+            ("var a: i64 = -8;\na = a >> 1;\nexit a as i32;", -4),
+            ("var a: u8 = 128;\na = a << 1;\nexit a as i32;", 0),
+            ("var a: i8 = 64;\na = a << 1;\nexit a as i32;", -128),
+            ("var a: u16 = 0xff;\na = a << 4;\nexit a as i32;", 0xff0),
+            ("var a: i8 = -1;\na = a >>> 1;\nexit a as i32;", 127),
+            ("var a: i32 = -16;\na = a >>> 28;\nexit a;", 15),
+        ];
+
+        for (code, expected) in cases {
+            let assembly = compile_to_assembly(&format!("extern \"C\" \"_start\" func start() {{\n{}\n}}", code));
+
+            assert_eq!(execute_until_exit(&assembly), expected, "in:\n{code}");
+        }
+    }
+
+    #[test]
+    fn test_bitwise_not_stays_within_its_type() {
+        let cases = [
+            ("var a: u8 = 12;\nvar b: u8 = ~a;\nexit b as i32;", 243),
+            ("var a: u8 = ~12;\nexit a as i32;", 243),
+            ("var a: u16 = 0;\na = ~a;\nexit a as i32;", 0xffff),
+            ("var a: u32 = 0;\nvar b: u64 = ((~a) as u64) >> 32;\nexit b as i32;", 0),
+            ("var a: i8 = 5;\na = ~a;\nexit a as i32;", -6),
+        ];
+
+        for (code, expected) in cases {
+            let assembly = compile_to_assembly(&format!("extern \"C\" \"_start\" func start() {{\n{}\n}}", code));
+
+            assert_eq!(execute_until_exit(&assembly), expected, "in:\n{code}");
+        }
+    }
+
+    #[test]
+    fn test_wide_immediates() {
+        let cases = [
+            ("let a: u64 = 100000;\nexit a as i32;", 100000),
+            ("let a: i64 = -100000;\nexit a as i32;", -100000),
+            ("let a: u64 = 4886718345;\nexit (a >> 4) as i32;", 305419896),
+            ("let a: u64 = 0x7fff000000000000;\nexit (a >> 48) as i32;", 0x7fff),
+        ];
+
+        for (code, expected) in cases {
+            let assembly = compile_to_assembly(&format!("extern \"C\" \"_start\" func start() {{\n{}\n}}", code));
+
+            assert_eq!(execute_until_exit(&assembly), expected, "in:\n{code}");
+        }
+    }
+
+    #[test]
+    fn test_generated_assembly_assembles() {
+        let code = "static COUNTER: u64 = 100000;\nextern \"C\" \"_start\" func start() {\nlet a: u64 = 4886718345;\nlet b: i64 = -100000;\nlet smiley = '\\u{1F600}';\nvar c: i32 = 5;\nc += 1;\nCOUNTER = a;\nexit c;\n}";
+
+        for target in [Target::Aarch64MacOs, Target::Aarch64Linux] {
+            let path = std::env::temp_dir().join(format!("smiscc-{}.s", uuid::Uuid::new_v4()));
+            fs::write(&path, compile_to_assembly_for(code, target).join("\n") + "\n").unwrap();
+
+            let output = Command::new("llvm-mc")
+                .args([&format!("-triple={}", target.get_triple()), "-filetype=obj", "-o", "/dev/null"])
+                .arg(&path)
+                .output();
+            fs::remove_file(&path).unwrap();
+
+            // Only hosts with an assembler for aarch64 can check this.
+            let Ok(output) = output else {
+                eprintln!("llvm-mc isn't installed, the generated assembly wasn't assembled");
+                return;
+            };
+
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        }
+    }
+
+    #[test]
+    fn test_c_symbols_of_each_target() {
+        let code = "extern \"C\" func abs(value: i32) -> i32;\nextern \"C\" func helper() {\n}\nextern \"C\" \"_start\" func start() {\nhelper();\nlet a = new 5;\nexit abs(-5);\n}";
+
+        for (target, prefix) in [(Target::Aarch64MacOs, "_"), (Target::Aarch64Linux, "")] {
+            let assembly = compile_to_assembly_for(code, target);
+            let calls: Vec<&str> = assembly.iter().filter_map(|x| x.strip_prefix("bl\t")).collect();
+
+            assert_eq!(calls, [format!("{prefix}helper"), format!("{prefix}malloc"), format!("{prefix}abs")]);
+            assert!(assembly.contains(&format!("{prefix}helper:")));
+        }
+    }
+
+    #[test]
+    fn test_linux_system_calls() {
+        let assembly = compile_to_assembly_for("extern \"C\" \"_start\" func start() {\nvar a: i32 = 5;\na += 1;\nlet result = syscall(172, 7, 8);\nexit a;\n}", Target::Aarch64Linux);
+
+        let calls: Vec<usize> = assembly.iter().enumerate().filter(|(_, x)| *x == "svc\t#0x0").map(|(i, _)| i).collect();
+
+        // The overflow trap writes its message, then come the system call and the exit.
+        assert_eq!(calls.len(), 3);
+
+        let registers = trace_immediates(&assembly[..calls[1]]);
+        assert_eq!((registers["x8"], registers["x0"], registers["x1"]), ("#172", "#7", "#8"));
+
+        let registers = trace_immediates(&assembly[..calls[2]]);
+        assert_eq!(registers["x8"], "#93");
+
+        // The message of the trap is referred to as ELF requires.
+        assert!(assembly.iter().any(|x| x == ".section\t.rodata"));
+        assert!(assembly.iter().any(|x| x.starts_with("add\tx1, x1, :lo12:")));
+    }
+
+    #[test]
+    fn test_return_values() {
+        let cases = [
+            // Several values are returned in registers and received by several names.
+            ("let (q, r) = divide(17, 5);\nexit (q * 10 + r) as i32;", 32),
+            ("let (q, r) = divide(17, 5);\nlet (s, t) = divide(r, q);\nexit (s * 10 + t) as i32;", 2),
+            // Both branches return, the link register survives the nested call.
+            ("let s = add_unless_zero(0, 7);\nexit s as i32;", 7),
+            ("let s = add_unless_zero(3, 7);\nexit s as i32;", 10),
+            ("let (q, r) = divide(17, 5);\nlet s = add_unless_zero(q, r);\nexit s as i32;", 5),
+        ];
+
+        let functions = "func divide(a: u64, b: u64) -> (u64, u64) {\nreturn a / b, a % b;\n}\n\
+            func add(a: u64, b: u64) -> u64 {\nreturn a + b;\n}\n\
+            func add_unless_zero(a: u64, b: u64) -> u64 {\nif a == 0 {\nreturn b;\n}\nreturn add(a, b);\n}";
+
+        for (code, expected) in cases {
+            let assembly = compile_to_assembly(&format!("{}\nextern \"C\" \"_start\" func start() {{\n{}\n}}", functions, code));
+
+            assert_eq!(execute_until_exit(&assembly), expected, "in:\n{code}");
+        }
+    }
+
+    #[test]
+    fn test_smisc_saved_registers() {
+        // More values than there are caller-saved registers, so x19 and following get used.
+        let names = ["b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m", "n", "o", "p", "q", "r", "s"];
+        let values: String = names.iter().enumerate().map(|(i, name)| format!("let {} = a + {};\n", name, i + 1)).collect();
+        let total = names.join(" + ");
+
+        let start = "extern \"C\" \"_start\" func start() {\nlet r = spread(1);\nexit r as i32;\n}";
+
+        // C functions would have to restore x19 to x23, so they use the stack instead.
+        for (modifier, uses_x19) in [("", true), ("extern \"C\" \"_spread\" ", false)] {
+            let assembly = compile_to_assembly(&format!("{modifier}func spread(a: u64) -> u64 {{\n{values}return {total};\n}}\n{start}"));
+
+            let call = assembly.iter().find(|x| split_instruction(x).0 == "bl").unwrap();
+            let label = format!("{}:", split_instruction(call).1[0]);
+            let body = &assembly[assembly.iter().position(|x| *x == label).unwrap()..assembly.iter().position(|x| x == "_start:").unwrap()];
+
+            assert_eq!(body.iter().any(|x| split_instruction(x).1.contains(&"x19")), uses_x19, "in:\n{modifier}");
+            assert!(!body.iter().any(|x| x.starts_with("str\tx19")));
+            assert_eq!(execute_until_exit(&assembly), 189);
+        }
+
+        // Callers of smisc functions save the values they keep in those registers.
+        let assembly = compile_to_assembly(&format!(
+            "func spread(a: u64) -> u64 {{\n{values}return {total};\n}}\n\
+            func outer(a: u64) -> u64 {{\n{values}let t = spread(a);\nreturn {total} + t;\n}}\n\
+            extern \"C\" \"_start\" func start() {{\nlet r = outer(1);\nexit r as i32;\n}}"
+        ));
+
+        assert_eq!(execute_until_exit(&assembly), 378);
+    }
+
+    #[test]
+    fn test_struct_return_values() {
+        let structs = "struct Point { x: i32, y: i32 }\nstruct Box3 { a: u64, b: u64, c: u64 }\n\
+            func make_point(x: i32, y: i32) -> Point {\nreturn Point { x: x, y: y + 1 };\n}\n\
+            func make_box(a: u64) -> Box3 {\nlet result = Box3 { a: a, b: a * 2, c: a * 3 };\nreturn result;\n}\n";
+
+        let cases = [
+            // Small structs are returned in registers.
+            ("let p = make_point(3, 4);\nexit p.x + p.y;", 8),
+            // Larger ones are written to the memory whose address was passed in x8.
+            ("let b = make_box(5);\nexit (b.a + b.b + b.c) as i32;", 30),
+            ("let p = make_point(3, 4);\nlet b = make_box(5);\nexit p.x + p.y + (b.c as i32);", 23),
+        ];
+
+        for (code, expected) in cases {
+            let assembly = compile_to_assembly(&format!("{}extern \"C\" \"_start\" func start() {{\n{}\n}}", structs, code));
+
+            assert_eq!(execute_until_exit(&assembly), expected, "in:\n{code}");
+        }
+
+        // The caller passes the address right before the call, the callee takes it before anything else.
+        let assembly = compile_to_assembly(&format!("{}extern \"C\" \"_start\" func start() {{\nlet b = make_box(5);\n}}", structs));
+        let call = assembly.iter().position(|x| split_instruction(x).0 == "bl").unwrap();
+        let label = format!("{}:", split_instruction(&assembly[call]).1[0]);
+        let callee = assembly.iter().position(|x| *x == label).unwrap();
+
+        assert_eq!(split_instruction(&assembly[call - 1]).1[0], "x8");
+        assert_eq!(split_instruction(&assembly[callee + 2]).1[1], "x8");
+    }
+}
@@ -3,8 +3,10 @@ use clap::builder::Str;
 use derive_new::new;
 use uuid::Uuid;
 use std::rc::Rc;
+use crate::compiler::backend::arch::Target;
 use crate::compiler::data_types::object::ObjectType;
-use crate::compiler::line_map::LineMap;
+use crate::compiler::line_map::{LineMap, TokenPosition};
+use crate::compiler::modules::Module;
 use crate::compiler::parser::function_meta::FunctionMeta;
 
 /// The current compiler state. This includes what variables are available,
@@ -20,6 +22,35 @@ pub struct Context {
     /// [objects hash map](Self::objects).
     pub mutable_objects: Vec<Uuid>,
 
+    /// Where the objects were declared, given by the same Uuid with
+    /// which they are identified in the [objects hash map](Self::objects).
+    /// This is used to point at the declaration in notifications.
+    #[new(default)]
+    pub declaration_positions: HashMap<Uuid, (usize, TokenPosition)>,
+
+    /// Objects that are stored in memory instead of a register. Their
+    /// Uuid holds the address of the value rather than the value itself.
+    #[new(default)]
+    pub memory_objects: Vec<Uuid>,
+
+    /// The declarations of all variables whose address is taken (`&a`),
+    /// which is determined by the type checker. Those variables have to
+    /// be stored in [memory](Self::memory_objects).
+    #[new(default)]
+    pub addressed_variables: Vec<(usize, TokenPosition)>,
+
+    /// The values of all constants (`const`), which are known at compile time,
+    /// given by the same Uuid with which they are identified in the
+    /// [objects hash map](Self::objects).
+    #[new(default)]
+    pub constant_values: HashMap<Uuid, i128>,
+
+    /// The symbols of all objects placed in the data section (`static`). Like
+    /// [memory objects](Self::memory_objects), their value is in memory, but
+    /// their address has to be loaded from the symbol whenever it's needed.
+    #[new(default)]
+    pub global_symbols: HashMap<Uuid, Rc<String>>,
+
     pub line_map: LineMap,
     
     /// The objects, mapped by their full name. The Uuid refers
@@ -37,15 +68,171 @@ pub struct Context {
 
     /// The amount of labels generated, useful for creating new label names
     /// when none are forced by the user.
-    pub label_count: usize
+    pub label_count: usize,
+
+    /// The module of every source file, given by the file number of the nodes in it.
+    #[new(default)]
+    pub modules: Vec<Module>,
+
+    /// The blocks that are currently checked or generated, the innermost one being the last.
+    #[new(default)]
+    pub scopes: Vec<Scope>,
+
+    /// The function whose body is currently checked or generated, which is the one `return` leaves.
+    #[new(default)]
+    pub current_function: Option<FunctionMeta>,
+
+    /// The address the current function copies the struct it returns to, if the struct is
+    /// too large to be returned in registers.
+    #[new(default)]
+    pub indirect_result: Option<Uuid>,
+
+    /// The options given on the command line.
+    #[new(default)]
+    pub options: CompileOptions,
+}
+
+/// ### Options Changing the Generated Code
+///
+/// These are given on the command line, the defaults apply otherwise.
+#[derive(Clone, Debug)]
+pub struct CompileOptions {
+    /// What happens when integer arithmetic exceeds the bounds of its type.
+    pub overflow_behaviour: OverflowBehaviour,
+
+    /// Whether array indices get checked at runtime (`--check-bounds`).
+    pub check_bounds: bool,
+
+    /// The assembly name of the function `new` calls to allocate memory (`--allocator`),
+    /// by default the target's symbol of [the C allocator](DEFAULT_ALLOCATOR).
+    pub allocator: Rc<String>,
+
+    /// Whether declaring a variable with the name of another one emits a warning (`--warn-shadowing`).
+    pub warn_shadowing: bool,
+
+    /// The platform the code is compiled for (`--target`).
+    pub target: Target,
+}
+
+impl Default for CompileOptions {
+    fn default() -> Self {
+        CompileOptions {
+            overflow_behaviour: OverflowBehaviour::default(),
+            check_bounds: false,
+            allocator: Rc::new(Target::default().get_c_symbol(DEFAULT_ALLOCATOR)),
+            warn_shadowing: false,
+            target: Target::default(),
+        }
+    }
+}
+
+/// ### The Local Variables of a Block
+///
+/// Local variables live in [the name map](Context::name_map) until the end of the
+/// block they're declared in, which restores whatever the names referred to before.
+#[derive(Clone, Debug, Default, new)]
+pub struct Scope {
+    /// The names declared in the block and what they referred to before it (if anything).
+    #[new(default)]
+    shadowed: Vec<(String, Option<Uuid>)>,
+
+    /// Variables declared further down in the block, which can't be used yet.
+    pending: Vec<(String, (usize, TokenPosition))>,
+}
+
+/// The C function `new` calls unless another allocator is given. It receives
+/// the size in bytes and returns the address of the allocated memory.
+pub const DEFAULT_ALLOCATOR: &str = "malloc";
+
+/// ### Behaviour On Integer Overflows
+///
+/// Selected with `--overflow=trap|wrap`.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum OverflowBehaviour {
+    /// Checks the result of every addition, subtraction and multiplication
+    /// and stops the program when it doesn't fit into its type.
+    #[default]
+    Trap,
+
+    /// Results silently wrap around (two's complement).
+    Wrap,
 }
 
 
 impl Context {
     pub fn clear(line_map: LineMap) -> Context {
-        Context { objects: HashMap::new(), mutable_objects: Vec::new(), line_map, name_map: HashMap::new(), datatypes: HashMap::new(), function_metas: Vec::new(), label_count: 0 }
+        Context::new(HashMap::new(), Vec::new(), line_map, HashMap::new(), HashMap::new(), Vec::new(), 0)
     }
     
+    /// Starts a block, given the variables declared in it (see [Scope::pending]).
+    pub fn enter_scope(&mut self, declarations: Vec<(String, (usize, TokenPosition))>) {
+        self.scopes.push(Scope::new(declarations));
+    }
+
+    /// Ends the innermost block, which drops the variables declared in it.
+    pub fn exit_scope(&mut self) {
+        let Some(scope) = self.scopes.pop() else { return };
+
+        for (name, previous) in scope.shadowed.into_iter().rev() {
+            match previous {
+                Some(object) => self.name_map.insert(name, object),
+                None => self.name_map.remove(&name),
+            };
+        }
+    }
+
+    /// ### Declares a Local Variable
+    ///
+    /// It's known until the end of the innermost block. Returns the object the name
+    /// referred to before, which is shadowed now.
+    pub fn declare(&mut self, name: String, object: Uuid) -> Option<Uuid> {
+        let previous = self.name_map.insert(name.clone(), object);
+
+        if let Some(scope) = self.scopes.last_mut() {
+            if let Some(index) = scope.pending.iter().position(|x| x.0 == name) {
+                scope.pending.remove(index);
+            }
+
+            if !scope.shadowed.iter().any(|x| x.0 == name) {
+                scope.shadowed.push((name, previous));
+            }
+        }
+
+        previous
+    }
+
+    /// Where a variable that can't be used yet is declared (further down in one of the current blocks).
+    pub fn get_pending_declaration(&self, name: &str) -> Option<(usize, TokenPosition)> {
+        self.scopes.iter().rev().find_map(|scope| scope.pending.iter().find(|x| x.0 == name).map(|x| x.1))
+    }
+
+    /// The module of a source file (the file that is compiled if there are no modules).
+    pub fn get_module(&self, file_number: usize) -> Module {
+        self.modules.get(file_number).cloned().unwrap_or_default()
+    }
+
+    /// The full name of something declared in a source file (e.g. `shapes::area`).
+    pub fn qualify(&self, name: &str, file_number: usize) -> String {
+        self.get_module(file_number).qualify(name)
+    }
+
+    /// ### Finds the Object a Name Refers To
+    ///
+    /// Local variables aren't namespaced, so they shadow constants and statics with the same name.
+    pub fn resolve_object(&self, name: &str, file_number: usize) -> Option<Uuid> {
+        if let Some(object) = self.name_map.get(name) {
+            return Some(*object);
+        }
+
+        self.get_module(file_number).get_candidates(name).iter().find_map(|x| self.name_map.get(x).copied())
+    }
+
+    /// Finds the function a name (e.g. `shapes::area`) refers to.
+    pub fn resolve_function(&self, name: &str, file_number: usize) -> Option<&FunctionMeta> {
+        self.get_module(file_number).get_candidates(name).iter()
+            .find_map(|x| self.function_metas.iter().find(|meta| meta.code_name == *x))
+    }
+
     pub fn generate_label(&mut self) -> Rc<String>{
         self.label_count += 1;
         Rc::new(String::from("LB") + (self.label_count - 1).to_string().as_str())   
@@ -3,6 +3,7 @@ use derive_new::new;
 use uuid::Uuid;
 use crate::compiler::backend::context::Context;
 use crate::compiler::data_types::object::Object;
+use crate::compiler::parser::function_meta::FunctionStyle;
 use crate::compiler::parser::tree::node::Node;
 
 pub fn flatten(line: Rc<dyn Node>, context: &mut Context) -> Vec<Instruction> {
@@ -58,13 +59,46 @@ pub enum Instruction {
     Add(Uuid, Uuid),
     Sub(Uuid, Uuid),
     Mul(Uuid, Uuid),
-    Div(Uuid, Uuid),
-    Mod(Uuid, Uuid),
+    /// Put the upper 64 bits of the 128-bit product of (0) and (1) into (0).
+    /// Both are treated as signed if (2) is true.
+    MulHigh(Uuid, Uuid, bool),
+    /// Divide (0) by (1), rounding towards zero. Both are treated as signed if (2) is true.
+    Div(Uuid, Uuid, bool),
+    /// Put the remainder of dividing (0) by (1) into (0). The remainder has the sign
+    /// of (0) if (2) is true, otherwise both are treated as unsigned.
+    Mod(Uuid, Uuid, bool),
+
+    And(Uuid, Uuid),
+    Or(Uuid, Uuid),
+    Xor(Uuid, Uuid),
+
+    /// Shift (0) to the left by (1) bits
+    Shl(Uuid, Uuid),
+    /// Shift (0) to the right by (1) bits, filling up with zeros
+    Shr(Uuid, Uuid),
+    /// Shift (0) to the right by (1) bits, keeping the sign
+    Sar(Uuid, Uuid),
     
-    /// Load (size of (datatype (2))) bytes of object at (1) into (0)
-    Load(Uuid, Uuid, u8),
+    /// Load (2) bytes at the address in (1) into (0). The value gets
+    /// sign extended to the register width if (3) is true, otherwise
+    /// zero extended.
+    Load(Uuid, Uuid, u8, bool),
+    /// Store the lowest (2) bytes of (0) at the address in (1)
     Store(Uuid, Uuid, u8),
 
+    /// Reserve (1) bytes on the stack of the current function and put
+    /// their address into (0). The memory stays reserved until the
+    /// function returns.
+    StackAllocate(Uuid, u64),
+
+    /// Truncate (0) to its lowest (1) bytes and extend it back to the
+    /// register width, keeping the sign if (2) is true.
+    ///
+    /// Integers always occupy the entire register, extended according to
+    /// their type. Widening them is free, narrowing them (or changing their
+    /// signedness) requires this.
+    Extend(Uuid, u8, bool),
+
     /// Removes an object from the list of objects that need to be
     /// maintained. This will not clean the heap if this is a pointer.
     Drop(Uuid),
@@ -72,24 +106,87 @@ pub enum Instruction {
     /// Exit the current program while returning the given object
     Exit(Uuid),
 
+    /// Performs the system call with the number in the second object, passing the
+    /// other objects as its arguments. The result is placed in the first object.
+    Syscall(/* result: */Uuid, /* number: */Uuid, /* arguments: */Vec<Uuid>),
+
     /// Calls a function given its assembly name.
     /// This doesn't only jump, it performs a subroutine, it branches,
     /// calls a function, however you might want to call it.
-    Call(/* assembly name: */String, /* inputs: */Vec<Uuid>, /* outputs: */Vec<Uuid>),
+    /// For variadic functions, the index of the first input covered by `...` is given.
+    /// Functions returning structs too large for registers get the address of the memory
+    /// for it as the indirect result.
+    Call(/* assembly name: */String, /* inputs: */Vec<Uuid>, /* outputs: */Vec<Uuid>, FunctionStyle, /* variadic start: */Option<usize>, /* indirect result: */Option<Uuid>),
+
+    /// Returns from the current function, handing the objects to the caller in the
+    /// registers the calling convention returns values in.
+    Return(/* values: */Vec<Uuid>, FunctionStyle),
 
     /// Defines a label at the current code position
     /// This label is globalized if the bool is true
     Label(Rc<String>, bool),
     
     
-    ReceiveArgument(Uuid, u8),
+    /// Makes the (u8)th argument of the current function, passed according to its calling
+    /// convention, known as the object.
+    ReceiveArgument(Uuid, u8, FunctionStyle),
+
+    /// Makes the address of the memory the current function returns its struct in known as
+    /// the object, for structs too large for registers. It has to follow the
+    /// [start of the function](Self::FunctionStart) right away.
+    ReceiveIndirectResult(Uuid),
 
-    FunctionStart,
+    /// Starts the body of a function using the calling convention, after its arguments were received.
+    FunctionStart(FunctionStyle),
     FunctionEnd,
     
     JumpConditional(JumpCondition, Rc<String>),
     
     Jump(Rc<String>),
+
+    /// Jumps to the label at the index (0) of the targets (2). The targets are
+    /// stored right after the jump under the label (1). The index has to be in
+    /// bounds.
+    JumpTable(Uuid, Rc<String>, Vec<Rc<String>>),
+
+    /// Stops the program abnormally with the code (0) after writing the message at the
+    /// symbol (1), which is (2) bytes long, to the standard error. The message describes
+    /// the reason (e.g. the source location of an overflow).
+    Trap(u16, Rc<String>, usize),
+
+    /// Puts the address of the symbol (1) into the object (0).
+    LoadAddress(Uuid, Rc<String>),
+
+    /// Places the bytes (1) into the data section under the symbol (0),
+    /// aligned to (2) bytes. Doesn't affect the surrounding code.
+    StaticData(Rc<String>, Vec<u8>, u8),
+
+    /// Like [static data](Instruction::StaticData), but the bytes are placed into a
+    /// section that can't be modified.
+    ReadOnlyData(Rc<String>, Vec<u8>, u8),
+
+    /// Places assembly written by the user into the code, see [InlineAssembly].
+    InlineAssembly(Rc<InlineAssembly>),
+}
+
+/// ### Assembly Written by the User
+///
+/// The lines get emitted as they are, except that `{n}` is replaced by the register
+/// of the (n)th operand. The registers are chosen when the assembly gets generated.
+#[derive(new, Clone, Debug)]
+pub struct InlineAssembly {
+    pub lines: Vec<String>,
+
+    /// The objects that have to be in their operand's register before the lines run, by
+    /// the index of the operand.
+    pub inputs: Vec<(usize, Uuid)>,
+
+    /// The objects that are in their operand's register after the lines ran, by the
+    /// index of the operand.
+    pub outputs: Vec<(usize, Uuid)>,
+
+    /// The names of the registers the lines change besides the outputs.
+    pub clobbers: Vec<String>,
 }
 
 #[derive(new, Clone, Debug)]
@@ -116,15 +213,17 @@ pub enum JumpComparisonType {
     NotEqual,
     Greater,
     GreaterOrEqual,
-    Less,
-    LessOrEqual,
     Carry,
     NotCarry,
+    /// The last signed operation didn't over- or underflow
+    NotOverflow,
+    /// Less when comparing both values as unsigned integers
+    UnsignedLess,
 }
 
 impl JumpComparisonType {
     pub fn requires_args(&self) -> bool {
-        !matches!(self, JumpComparisonType::Carry | JumpComparisonType::NotCarry)
+        !matches!(self, JumpComparisonType::Carry | JumpComparisonType::NotCarry | JumpComparisonType::NotOverflow)
     }
 }
 
@@ -136,18 +235,28 @@ impl Instruction {
             Instruction::Add(a, b) => vec![*a, *b],
             Instruction::Sub(a, b) => vec![*a, *b],
             Instruction::Mul(a, b) => vec![*a, *b],
-            Instruction::Div(a, b) => vec![*a, *b],
-            Instruction::Mod(a, b) => vec![*a, *b],
-            Instruction::Load(a, b, _) => vec![*a, *b],
+            Instruction::MulHigh(a, b, _) => vec![*a, *b],
+            Instruction::Div(a, b, _) => vec![*a, *b],
+            Instruction::Mod(a, b, _) => vec![*a, *b],
+            Instruction::And(a, b) | Instruction::Or(a, b) | Instruction::Xor(a, b) => vec![*a, *b],
+            Instruction::Shl(a, b) | Instruction::Shr(a, b) | Instruction::Sar(a, b) => vec![*a, *b],
+            Instruction::Load(a, b, _, _) => vec![*a, *b],
             Instruction::Store(a, b, _) => vec![*a, *b],
+            Instruction::Extend(a, _, _) => vec![*a],
+            Instruction::StackAllocate(a, _) => vec![*a],
             Instruction::Drop(a) => vec![*a],
             Instruction::MoveData(a, _) => vec![*a],
             Instruction::Exit(a) => vec![*a],
-            Instruction::Call(_, args, outs) => [args.clone(), outs.clone()].concat(),
-            Instruction::Label(_, _) | Instruction::FunctionEnd | Instruction::FunctionStart => vec![],
-            Instruction::ReceiveArgument(_, _) => { vec![] }
+            Instruction::Syscall(result, number, args) => [vec![*result, *number], args.clone()].concat(),
+            Instruction::Call(_, args, outs, _, _, indirect_result) => [args.clone(), outs.clone(), indirect_result.iter().copied().collect()].concat(),
+            Instruction::Return(values, _) => values.clone(),
+            Instruction::Label(_, _) | Instruction::FunctionEnd | Instruction::FunctionStart(_) => vec![],
+            Instruction::ReceiveArgument(_, _, _) | Instruction::ReceiveIndirectResult(_) => { vec![] }
             Instruction::JumpConditional(condition, _) => condition.get_objects(),
-            Instruction::Jump(_) => vec![],
+            Instruction::Jump(_) | Instruction::Trap(_, _, _) | Instruction::StaticData(_, _, _) | Instruction::ReadOnlyData(_, _, _) => vec![],
+            Instruction::LoadAddress(a, _) => vec![*a],
+            Instruction::JumpTable(a, _, _) => vec![*a],
+            Instruction::InlineAssembly(assembly) => assembly.inputs.iter().chain(assembly.outputs.iter()).map(|x| x.1).collect(),
         }
     }
 
@@ -176,24 +285,51 @@ pub enum InstructionMeta {
     SubReg,
     SubImm,
     MulReg,
+    MulHighReg,
     DivReg,
+    RemReg,
     ModReg,
 
+    And,
+    Or,
+    Xor,
+    Shl,
+    Shr,
+    Sar,
+
     Load,
     Store,
+    Extend,
 
     StackLoad,
     StackStore,
+    StackAddress,
 
-    Exit,
+    SystemCall,
 
     Call,
+    Return,
 
     Label,
     
     Jump,
     JumpEqual,
     JumpNotEqual,
+    JumpGreater,
+    JumpGreaterOrEqual,
+    JumpCarry,
+    JumpNotCarry,
+    JumpNotOverflow,
+    JumpUnsignedLess,
+    JumpTable,
     
     Compare,
+
+    Trap,
+
+    LoadAddress,
+    StaticData,
+    ReadOnlyData,
+
+    InlineAssembly,
 }
\ No newline at end of file
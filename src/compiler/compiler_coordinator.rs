@@ -2,11 +2,12 @@ use std::ops::Deref;
 use std::process::Command;
 use crate::ArgumentList;
 use crate::compiler::backend::assembly;
-use crate::compiler::backend::context::Context;
+use crate::compiler::backend::context::{CompileOptions, Context, DEFAULT_ALLOCATOR};
 use crate::compiler::backend::flattener::flatten;
-use crate::compiler::tokenization::tokenizer::tokenize_file;
+use crate::compiler::modules::load_modules;
 use crate::compiler::parser::parse::parse;
-use crate::compiler::backend::arch::aarch64_mac_os;
+use crate::compiler::type_checker;
+use crate::compiler::backend::arch::Target;
 use crate::compiler::data_types::object::ObjectType;
 use crate::compiler::line_map::LineMap;
 use std::rc::Rc;
@@ -17,25 +18,35 @@ use crate::compiler::parser::tree::node::{CodeBlockArray, Node};
 pub fn compile(code: String, args: ArgumentList) {
     let mut line_map: LineMap = LineMap::new();
 
-    let tokens = tokenize_file(
+    let files = load_modules(
+        args.file.clone().unwrap_or_default().as_str(),
         code,
-        0usize,
         Rc::new(build_integer_types()),
         &mut line_map,
     );
 
     if args.show_tokens {
         println!("----- Tokens -----");
-        for line in tokens.iter().enumerate() {
-            println!("{}:\t{:?}", line.0 + 1, line.1)
+        for file in files.iter() {
+            for line in file.tokens.iter().enumerate() {
+                println!("{}:\t{:?}", line.0 + 1, line.1)
+            }
         }
         println!("------------------");
     }
 
     let mut object_types = Rc::new(ObjectType::generate_built_ins());
 
-    let mut parsed = parse(vec![tokens.clone()], &mut line_map, &mut object_types).unwrap();
+    let mut parsed = parse(files.iter().map(|x| x.tokens.clone()).collect(), &mut line_map, &mut object_types).unwrap();
     let mut context = Context::clear(line_map);
+    context.options = CompileOptions {
+        overflow_behaviour: args.overflow,
+        check_bounds: args.check_bounds,
+        allocator: Rc::new(args.allocator.clone().unwrap_or_else(|| args.target.get_c_symbol(DEFAULT_ALLOCATOR))),
+        warn_shadowing: args.warn_shadowing,
+        target: args.target,
+    };
+    context.modules = files.into_iter().map(|x| x.module).collect();
     object_types.iter().for_each(|object_type| {
        context.datatypes.insert(object_type.type_uuid, object_type.clone());
     });
@@ -44,30 +55,34 @@ pub fn compile(code: String, args: ArgumentList) {
     parsed_clone.perform_early_context_changes(&mut context);
     parsed = Rc::new(parsed_clone);
 
+    type_checker::check_types(parsed.clone(), &mut context);
+
+    // Flattening relies on the types being correct (e.g. called functions existing).
+    if context.line_map.error_count != 0 {
+        context.line_map.display_finish();
+        return;
+    }
+
     let flattened = flatten(parsed, &mut context);
-    let arch = aarch64_mac_os::generate();
+    let arch = args.target.generate_architecture();
     let assembly = assembly::generate_assembly_instructions(flattened, arch.clone());
 
-    if context.line_map.error_count == 0 {
-        assembly::generate_assembly::<Aarch64Asm>(assembly, arch, "test.s".to_string())
+    // Some errors are only found while flattening, there's nothing to assemble then.
+    if context.line_map.error_count != 0 {
+        context.line_map.display_finish();
+        return;
     }
 
+    assembly::generate_assembly::<Aarch64Asm>(assembly, arch, "test.s".to_string());
+
     cc::Build::new()
         .file("test.s")
         .out_dir("./build/")
-        .target("aarch64-apple-darwin")
+        .target(args.target.get_triple())
         .opt_level(0)
-        .host("aarch64-apple-darwin")
+        .host(args.target.get_triple())
         .compile("test");
 
-    let xc_command = Command::new("xcrun")
-        .args(["-sdk", "macosx", "--show-sdk-path"])
-        .output()
-        .unwrap();
-
-    let syslibroot = String::from_utf8(xc_command.stdout).unwrap();
-
-
     let mut command = Command::new("ld");
 
     for entry in glob::glob("./build/*-test.o").unwrap() {
@@ -77,13 +92,43 @@ pub fn compile(code: String, args: ArgumentList) {
     command
         .arg("-o")
         .arg("test")
-        .arg("-lSystem")
-        .arg("-syslibroot")
-        .arg(syslibroot.trim())
         .arg("-e")
-        .arg("_start")
-        .arg("-arch")
-        .arg("arm64");
+        .arg("_start");
+
+    match args.target {
+        Target::Aarch64MacOs => {
+            let xc_command = Command::new("xcrun")
+                .args(["-sdk", "macosx", "--show-sdk-path"])
+                .output()
+                .unwrap();
+
+            let syslibroot = String::from_utf8(xc_command.stdout).unwrap();
+
+            command
+                .arg("-lSystem")
+                .arg("-syslibroot")
+                .arg(syslibroot.trim())
+                .arg("-arch")
+                .arg("arm64");
+        }
+        Target::Aarch64Linux => {
+            command
+                .arg("-dynamic-linker")
+                .arg("/lib/ld-linux-aarch64.so.1")
+                .arg("-lc");
+        }
+    }
+
+    // Libraries providing the functions declared with `extern`
+    for directory in args.library_path.iter() {
+        command.arg(format!("-L{}", directory));
+    }
+
+    for library in args.link.iter() {
+        command.arg(format!("-l{}", library));
+    }
+
+    command.status().unwrap();
 
 
 
@@ -0,0 +1,28 @@
+use uuid::Uuid;
+use crate::compiler::data_types::object::{ObjectType, Trait};
+
+/// ### Builds the Type of a Fixed-Size Array
+///
+/// The uuid is derived from the element type and the length, so building
+/// the same array type twice (e.g. once for an annotation and once for a
+/// literal) results in the same type.
+pub fn build_array_type(element: &ObjectType, length: usize) -> ObjectType {
+    let uuid = Uuid::new_v5(&element.type_uuid, length.to_string().as_bytes());
+
+    let mut type_ = ObjectType::new(format!("[{}; {}]", element.name, length), uuid);
+
+    type_.add_complex_trait(Trait::ARRAY, vec![element.type_uuid.to_string(), length.to_string()]);
+
+    if let Some(element_size) = element.get_size() {
+        type_.add_complex_trait(Trait::SIZED, vec![(element_size * length).to_string()]);
+    }
+
+    type_
+}
+
+/// Gets the uuid of the element type (.0) and the length (.1) if the type is an array.
+pub fn get_array_info(type_: &ObjectType) -> Option<(Uuid, usize)> {
+    let arguments = type_.get_trait(Trait::ARRAY)?;
+
+    Some((Uuid::parse_str(arguments.first()?).ok()?, arguments.get(1)?.parse().ok()?))
+}
@@ -0,0 +1,40 @@
+use crate::compiler::data_types::datatypes_general::{BuildResult, Buildable};
+use crate::compiler::data_types::object::{ObjectType, Trait};
+use crate::compiler::tokenization::token::Token;
+
+/// The size of a [character](Character), enough for every unicode code point.
+pub const CHARACTER_SIZE: usize = 4;
+
+/// A single unicode character (e.g. `'a'`). Its value is the code point.
+pub struct Character {}
+
+impl Character {
+    /// The name of the type referring to the character type in the user's code
+    pub const CHARACTER_KEYWORD: &str = "char";
+
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Buildable for Character {
+    fn build(&self, _tokens: Vec<Token>, _parent_type: ObjectType) -> BuildResult {
+        todo!()
+    }
+
+    fn build_type(&self) -> ObjectType {
+        let uuid = uuid::Uuid::new_v4();
+
+        let mut type_ = ObjectType::new(Self::CHARACTER_KEYWORD.to_string(), uuid);
+
+        type_.add_complex_trait(Trait::SIZED, vec![CHARACTER_SIZE.to_string()]);
+        type_.add_trait(Trait::VALUE_TYPE);
+        type_.add_trait(Trait::CHARACTER);
+
+        type_
+    }
+
+    fn get_name(&self) -> String {
+        Self::CHARACTER_KEYWORD.to_string()
+    }
+}
@@ -0,0 +1,47 @@
+use derive_new::new;
+use uuid::Uuid;
+use crate::compiler::data_types::object::{ObjectType, Trait};
+
+/// The size of an enum's value in bytes. Values are unsigned, so
+/// discriminants range from 0 to `u32::MAX`.
+pub const ENUM_SIZE: usize = 4;
+
+/// A variant of an [enum type](build_enum_type) and the value representing it.
+#[derive(Clone, Debug, new)]
+pub struct EnumVariant {
+    pub name: String,
+    pub value: u32,
+}
+
+/// ### Builds the Type of an Enum
+///
+/// Just like structs, every declaration results in a new type. Variants are
+/// represented by their value (like in C), so they can be compared and
+/// matched without touching memory.
+pub fn build_enum_type(name: String, variants: &[EnumVariant]) -> ObjectType {
+    let mut type_ = ObjectType::new(name, Uuid::new_v4());
+
+    let arguments = variants.iter()
+        .flat_map(|x| [x.name.clone(), x.value.to_string()])
+        .collect();
+
+    type_.add_complex_trait(Trait::ENUM, arguments);
+    type_.add_complex_trait(Trait::SIZED, vec![ENUM_SIZE.to_string()]);
+    type_.add_trait(Trait::VALUE_TYPE);
+
+    type_
+}
+
+/// Gets the variants in the order of their declaration if the type is an enum.
+pub fn get_enum_variants(type_: &ObjectType) -> Option<Vec<EnumVariant>> {
+    let arguments = type_.get_trait(Trait::ENUM)?;
+
+    // Enums without variants still have a single (empty) argument
+    if arguments.len() == 1 && arguments[0].is_empty() {
+        return Some(vec![]);
+    }
+
+    arguments.chunks_exact(2)
+        .map(|x| Some(EnumVariant::new(x[0].clone(), x[1].parse().ok()?)))
+        .collect()
+}
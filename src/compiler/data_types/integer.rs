@@ -4,24 +4,15 @@ use crate::compiler::data_types::datatypes_general::{BuildResult, Buildable, Obj
 use crate::compiler::data_types::object::{Object, ObjectType, Trait};
 use crate::compiler::line_map::{DisplayCodeInfo, DisplayCodeKind, LineMap, NotificationInfo};
 use crate::compiler::tokenization::token::Token;
-use crate::config::target::ADDRESS_INTEGER_TYPE;
+use crate::config::target::{ADDRESS_INTEGER_TYPE, SIGNED_ADDRESS_INTEGER_TYPE};
 use crate::util::math::convert_to_int;
 
 /// Builds all integer subtypes and returns them with their corresponding
 /// types
 pub fn build_integer_types() -> Vec<(IntegerType, ObjectType)> {
-    let types = vec![
-        IntegerType::Unsigned32BitInteger,
-        IntegerType::Signed32BitInteger,
-        IntegerType::Unsigned16BitInteger,
-        IntegerType::Signed16BitInteger,
-        IntegerType::Unsigned8BitInteger,
-        IntegerType::Signed8BitInteger,
-    ];
-
     let mut types_and_built_object_types : Vec<(IntegerType, ObjectType)> = Vec::new();
 
-    for kind in types {
+    for kind in IntegerType::ALL {
         let object_type = kind.build_type();
         types_and_built_object_types.push((kind, object_type));
     }
@@ -114,15 +105,72 @@ pub enum IntegerType {
     Signed16BitInteger,
     Unsigned32BitInteger,
     Signed32BitInteger,
-    Address
+    Unsigned64BitInteger,
+    Signed64BitInteger,
+
+    /// An unsigned integer as wide as an address on the target (`usize`).
+    Address,
+
+    /// A signed integer as wide as an address on the target (`isize`).
+    SignedAddress,
 }
 
 
 impl IntegerType {
+    /// All integer types, in the order in which they are tried when
+    /// building integers.
+    pub const ALL: [IntegerType; 10] = [
+        IntegerType::Unsigned32BitInteger,
+        IntegerType::Signed32BitInteger,
+        IntegerType::Unsigned16BitInteger,
+        IntegerType::Signed16BitInteger,
+        IntegerType::Unsigned8BitInteger,
+        IntegerType::Signed8BitInteger,
+        IntegerType::Unsigned64BitInteger,
+        IntegerType::Signed64BitInteger,
+        IntegerType::Address,
+        IntegerType::SignedAddress,
+    ];
+
+    /// Finds the integer type with the given [code name](IntegerType::get_code_name).
+    pub fn from_code_name(name: &str) -> Option<IntegerType> {
+        IntegerType::ALL.into_iter().find(|x| x.get_code_name() == name)
+    }
+
+    /// Whether the value lies within the [lower](IntegerType::get_lower_bound)
+    /// and [upper](IntegerType::get_upper_bound) bound of the type.
+    pub fn contains(&self, value: i128) -> bool {
+        value <= self.get_upper_bound() as i128 && value >= -(self.get_lower_bound() as i128)
+    }
+
+    /// Truncates the value to the width of the type and extends it back, just
+    /// like it would happen to the value in a register (e.g. `256` becomes `0`
+    /// for `u8` and `255` becomes `-1` for `i8`).
+    pub fn wrap(&self, value: i128) -> i128 {
+        let bits = self.get_memory_size() as u32 * 8;
+        let truncated = value & ((1i128 << bits) - 1);
+
+        if self.is_signed() && truncated >= 1i128 << (bits - 1) {
+            return truncated - (1i128 << bits);
+        }
+
+        truncated
+    }
+
+    /// Whether the type can hold negative values.
+    pub fn is_signed(&self) -> bool {
+        self.get_lower_bound() != 0
+    }
+
+    /// The range of values the type can hold, e.g. `-128 to 127` for `i8`.
+    pub fn describe_range(&self) -> String {
+        format!("{} to {}", -(self.get_lower_bound() as i128), self.get_upper_bound())
+    }
+
     /// ### The maximum value that the integer is allowed to contain.
     ///
-    /// Right now, no checks are performed. This might, however, be changed
-    /// in the future.
+    /// Literals exceeding it are rejected at compile time. Depending on the
+    /// overflow behaviour, arithmetic results are checked at runtime as well.
     ///
     /// For an u32, this would be 2^32 - 1.
     /// For an i32, this would be 2^31 - 1.
@@ -134,15 +182,18 @@ impl IntegerType {
             IntegerType::Signed16BitInteger =>      0x7f_ff,
             IntegerType::Unsigned32BitInteger =>    0xff_ff_ff_ff,
             IntegerType::Signed32BitInteger =>      0x7f_ff_ff_ff,
+            IntegerType::Unsigned64BitInteger =>    0xff_ff_ff_ff_ff_ff_ff_ff,
+            IntegerType::Signed64BitInteger =>      0x7f_ff_ff_ff_ff_ff_ff_ff,
 
             IntegerType::Address =>                 ADDRESS_INTEGER_TYPE.get_upper_bound(),
+            IntegerType::SignedAddress =>           SIGNED_ADDRESS_INTEGER_TYPE.get_upper_bound(),
         }
     }
 
     /// ### The minimum value that the integer is allowed to contain.
     ///
-    /// Right now, no checks regarding over- or underflows are implemented.
-    /// This might, however, be changed in the future.
+    /// Literals below it are rejected at compile time. Depending on the
+    /// overflow behaviour, arithmetic results are checked at runtime as well.
     ///
     /// For an u32, this would be 0.
     /// For an i32, this would be 2^31.
@@ -154,8 +205,10 @@ impl IntegerType {
             IntegerType::Signed8BitInteger =>   0x80,
             IntegerType::Signed16BitInteger =>  0x80_00,
             IntegerType::Signed32BitInteger =>  0x80_00_00_00,
+            IntegerType::Signed64BitInteger =>  0x80_00_00_00_00_00_00_00,
 
             IntegerType::Address =>             ADDRESS_INTEGER_TYPE.get_lower_bound(),
+            IntegerType::SignedAddress =>       SIGNED_ADDRESS_INTEGER_TYPE.get_lower_bound(),
 
             _ => /* Unsigned - no negatives */  0x0
         }
@@ -184,8 +237,11 @@ impl IntegerType {
             IntegerType::Signed16BitInteger =>      "i16".to_string(),
             IntegerType::Unsigned32BitInteger =>    "u32".to_string(),
             IntegerType::Signed32BitInteger =>      "i32".to_string(),
+            IntegerType::Unsigned64BitInteger =>    "u64".to_string(),
+            IntegerType::Signed64BitInteger =>      "i64".to_string(),
 
-            IntegerType::Address =>                 ADDRESS_INTEGER_TYPE.get_code_name(),
+            IntegerType::Address =>                 "usize".to_string(),
+            IntegerType::SignedAddress =>           "isize".to_string(),
         }
     }
 
@@ -206,9 +262,11 @@ impl IntegerType {
             IntegerType::Unsigned8BitInteger  | IntegerType::Signed8BitInteger =>   1,
             IntegerType::Unsigned16BitInteger | IntegerType::Signed16BitInteger =>  2,
             IntegerType::Unsigned32BitInteger | IntegerType::Signed32BitInteger =>  4,
+            IntegerType::Unsigned64BitInteger | IntegerType::Signed64BitInteger =>  8,
 
 
             IntegerType::Address => ADDRESS_INTEGER_TYPE.get_memory_size(),
+            IntegerType::SignedAddress => SIGNED_ADDRESS_INTEGER_TYPE.get_memory_size(),
         }
     }
 }
@@ -287,6 +345,9 @@ impl Buildable for IntegerType {
         type_.add_trait(Trait::INTEGER);
         type_.add_complex_trait(Trait::SIZED, vec![self.get_memory_size().to_string()]);
 
+        if self.get_lower_bound() != 0 {
+            type_.add_trait(Trait::SIGNED);
+        }
 
         type_
     }
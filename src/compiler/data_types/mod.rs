@@ -1,4 +1,10 @@
 pub mod datatypes_general;
 pub mod object;
 pub mod integer;
-pub mod boolean;
\ No newline at end of file
+pub mod boolean;
+pub mod array;
+pub mod reference;
+pub mod structure;
+pub mod enumeration;
+pub mod string;
+pub mod character;
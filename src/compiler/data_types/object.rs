@@ -1,8 +1,10 @@
 use derive_new::new;
 use uuid::Uuid;
 use crate::compiler::data_types::boolean::Boolean;
+use crate::compiler::data_types::character::Character;
 use crate::compiler::data_types::datatypes_general::Buildable;
-use crate::compiler::data_types::integer::IntegerType;
+use crate::compiler::data_types::integer::build_integer_types;
+use crate::compiler::data_types::string::StringType;
 use crate::compiler::line_map::{DisplayCodeInfo, DisplayCodeKind, LineMap, NotificationInfo, TokenPosition};
 use crate::compiler::tokenization::token::Token;
 
@@ -196,12 +198,13 @@ impl ObjectType {
     
     pub fn generate_built_ins() -> Vec<ObjectType> {
         // Generate the datatypes
-        let u32_ = IntegerType::Unsigned32BitInteger;
-        let u32_type = u32_.build_type();
+        let integer_types = build_integer_types().into_iter().map(|x| x.1);
         let bool_ = Boolean::new();
         let bool_type = bool_.build_type();
+        let string_type = StringType::new().build_type();
+        let character_type = Character::new().build_type();
         
-        vec![u32_type, bool_type]
+        integer_types.chain([bool_type, string_type, character_type]).collect()
     }
 
     pub fn add_trait(&mut self, trait_: &str) {
@@ -230,6 +233,11 @@ impl ObjectType {
     pub fn has_trait(&self, trait_: &str) -> bool {
         self.get_trait(trait_).is_some()
     }
+
+    /// The size of an instance in bytes if the type is [sized](Trait::SIZED).
+    pub fn get_size(&self) -> Option<usize> {
+        self.get_trait(Trait::SIZED)?.first()?.parse().ok()
+    }
 }
 
 
@@ -255,6 +263,7 @@ impl Trait{
     /// 
     /// This means that the address of the string points to its `length<b>` (... in bytes)
     /// and all further characters until `(address + length<b>)` are UTF-8 characters.
+    /// The length is an unsigned 64-bit integer, so the characters start at `address + 8`.
     pub const BASIC_STRING: &str = "basic_string";
 
     /// Means that the type can be interpreted as containing a boolean value.
@@ -275,4 +284,26 @@ impl Trait{
     /// Marks the type as being some kind of integer.
     /// **Note:** This alone won't make the type accept arithmetic operations.
     pub const INTEGER: &str = "integer";
+
+    /// The type can hold negative values and can therefore be negated.
+    pub const SIGNED: &str = "signed";
+
+    /// The object is a fixed-size array of (array:element_uuid:length) elements.
+    /// Its value is the address of the first element.
+    pub const ARRAY: &str = "array:";
+
+    /// The object is a struct with the fields (struct:name:type_uuid:offset:...),
+    /// offsets are in bytes. Just like with arrays, its value is its address.
+    pub const STRUCT: &str = "struct:";
+
+    /// The object has to be placed at an address that is a multiple of (aligned:alignment)
+    /// bytes. Types without this trait are aligned to their size.
+    pub const ALIGNED: &str = "aligned:";
+
+    /// The object is an enum with the variants (enum:name:value:...). Its value
+    /// is the value of the variant.
+    pub const ENUM: &str = "enum:";
+
+    /// The object is a single unicode character, its value is the code point.
+    pub const CHARACTER: &str = "character";
 }
\ No newline at end of file
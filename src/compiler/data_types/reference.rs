@@ -0,0 +1,25 @@
+use uuid::Uuid;
+use crate::compiler::data_types::object::{ObjectType, Trait};
+
+/// The size of a reference in bytes, which is the width of an address.
+pub const REFERENCE_SIZE: usize = 8;
+
+/// ### Builds the Type of a Reference
+///
+/// Just like with [arrays](super::array::build_array_type), the uuid is derived
+/// from the referenced type, so `&u32` is always the same type.
+pub fn build_reference_type(target: &ObjectType) -> ObjectType {
+    let uuid = Uuid::new_v5(&target.type_uuid, b"&");
+
+    let mut type_ = ObjectType::new(format!("&{}", target.name), uuid);
+
+    type_.add_complex_trait(Trait::REFERENCE_TYPE, vec![target.type_uuid.to_string()]);
+    type_.add_complex_trait(Trait::SIZED, vec![REFERENCE_SIZE.to_string()]);
+
+    type_
+}
+
+/// Gets the uuid of the referenced type if the type is a reference.
+pub fn get_reference_target(type_: &ObjectType) -> Option<Uuid> {
+    Uuid::parse_str(type_.get_trait(Trait::REFERENCE_TYPE)?.first()?).ok()
+}
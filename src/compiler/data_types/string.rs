@@ -0,0 +1,60 @@
+use crate::compiler::data_types::datatypes_general::{BuildResult, Buildable};
+use crate::compiler::data_types::object::{ObjectType, Trait};
+use crate::compiler::tokenization::token::Token;
+
+/// The size of the length in front of the characters of a [basic string](Trait::BASIC_STRING).
+pub const STRING_LENGTH_SIZE: usize = 8;
+
+/// A string literal's type. Its value is the address of the string, which has
+/// a [basic layout](Trait::BASIC_STRING) and can't be modified.
+pub struct StringType {}
+
+impl StringType {
+    /// The name of the type referring to the string type in the user's code
+    pub const STRING_KEYWORD: &str = "str";
+
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Buildable for StringType {
+    fn build(&self, _tokens: Vec<Token>, _parent_type: ObjectType) -> BuildResult {
+        todo!()
+    }
+
+    fn build_type(&self) -> ObjectType {
+        let uuid = uuid::Uuid::new_v4();
+
+        let mut type_ = ObjectType::new(Self::STRING_KEYWORD.to_string(), uuid);
+
+        type_.add_complex_trait(Trait::SIZED, vec!["8".to_string()]);
+        type_.add_trait(Trait::VALUE_TYPE);
+        type_.add_trait(Trait::BASIC_STRING);
+
+        type_
+    }
+
+    fn get_name(&self) -> String {
+        Self::STRING_KEYWORD.to_string()
+    }
+}
+
+/// Lays out the string the way [basic strings](Trait::BASIC_STRING) are stored in memory.
+pub fn encode_basic_string(string: &str) -> Vec<u8> {
+    let mut bytes = (string.len() as u64).to_le_bytes().to_vec();
+    bytes.extend_from_slice(string.as_bytes());
+
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compiler::data_types::string::encode_basic_string;
+
+    #[test]
+    fn test_encode_basic_string() {
+        assert_eq!(encode_basic_string("ab"), vec![2, 0, 0, 0, 0, 0, 0, 0, b'a', b'b']);
+        assert_eq!(encode_basic_string("ä"), vec![2, 0, 0, 0, 0, 0, 0, 0, 0xc3, 0xa4]);
+    }
+}
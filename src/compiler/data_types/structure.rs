@@ -0,0 +1,71 @@
+use derive_new::new;
+use uuid::Uuid;
+use crate::compiler::data_types::array::get_array_info;
+use crate::compiler::data_types::object::{ObjectType, Trait};
+
+/// A field of a [struct type](build_struct_type) and where it's located inside of it.
+#[derive(Clone, Debug, new)]
+pub struct StructField {
+    pub name: String,
+
+    /// The uuid of the field's type
+    pub datatype: Uuid,
+
+    /// The distance from the start of the struct in bytes
+    pub offset: usize,
+}
+
+/// ### Builds the Type of a Struct
+///
+/// Unlike arrays and references, every declaration results in a new type, even if
+/// the fields are the same. The fields are laid out the way C lays them out: each one
+/// starts at a multiple of its alignment and the size is padded to a multiple of the
+/// largest alignment.
+pub fn build_struct_type(name: String, fields: &[(String, ObjectType)], all_types: &[ObjectType]) -> ObjectType {
+    let mut type_ = ObjectType::new(name, Uuid::new_v4());
+
+    let mut arguments: Vec<String> = vec![];
+    let mut offset: usize = 0;
+    let mut alignment = 1;
+
+    for (field_name, field_type) in fields {
+        let field_alignment = get_alignment(field_type, all_types);
+
+        offset = offset.next_multiple_of(field_alignment);
+        alignment = alignment.max(field_alignment);
+
+        arguments.append(&mut vec![field_name.clone(), field_type.type_uuid.to_string(), offset.to_string()]);
+
+        offset += field_type.get_size().unwrap();
+    }
+
+    type_.add_complex_trait(Trait::STRUCT, arguments);
+    type_.add_complex_trait(Trait::ALIGNED, vec![alignment.to_string()]);
+    type_.add_complex_trait(Trait::SIZED, vec![offset.next_multiple_of(alignment).to_string()]);
+
+    type_
+}
+
+/// Gets the fields in the order of their declaration if the type is a struct.
+pub fn get_struct_fields(type_: &ObjectType) -> Option<Vec<StructField>> {
+    let arguments = type_.get_trait(Trait::STRUCT)?;
+
+    arguments.chunks_exact(3)
+        .map(|x| Some(StructField::new(x[0].clone(), Uuid::parse_str(&x[1]).ok()?, x[2].parse().ok()?)))
+        .collect()
+}
+
+/// Gets the alignment of a type in bytes. Values that aren't composed of others
+/// are aligned to their size, arrays to their elements.
+pub fn get_alignment(type_: &ObjectType, all_types: &[ObjectType]) -> usize {
+    if let Some(alignment) = type_.get_trait(Trait::ALIGNED).and_then(|x| x.first()?.parse().ok()) {
+        return alignment;
+    }
+
+    if let Some((element_uuid, _)) = get_array_info(type_)
+        && let Some(element) = all_types.iter().find(|x| x.type_uuid == element_uuid) {
+        return get_alignment(element, all_types);
+    }
+
+    type_.get_size().unwrap_or(1).clamp(1, 8)
+}
@@ -7,6 +7,11 @@ pub struct LineMap {
     pub files: Vec<LmFile>,
     pub warning_count: u32,
     pub error_count: u32,
+
+    /// The title and the underlined code of every error displayed so far,
+    /// so tests can check which errors were reported and where.
+    #[cfg(test)]
+    pub reported_errors: Vec<(String, String)>,
 }
 
 
@@ -51,6 +56,9 @@ impl LineMap{
 
         self.error_count += 1;
 
+        #[cfg(test)]
+        self.record_error(&info);
+
         println!("{} {}", "Error:".bold().red(), info.title.red());
 
         for mut notification_info in info.display_code{
@@ -81,7 +89,27 @@ impl LineMap{
     }
 
     pub fn new() -> Self {
-        LineMap{ files: Vec::new(), warning_count: 0, error_count: 0 }
+        LineMap{
+            files: Vec::new(),
+            warning_count: 0,
+            error_count: 0,
+            #[cfg(test)]
+            reported_errors: Vec::new(),
+        }
+    }
+
+    /// Remembers the title of the error and the code underlined by its
+    /// first display code info in the [reported errors](Self::reported_errors).
+    #[cfg(test)]
+    fn record_error(&mut self, info: &NotificationInfo) {
+        let underlined = info.display_code.first().map(|code| {
+            let position = code.characters.unwrap_or_else(|| self.get_position_of_tokens(code.line_number_in_map, code.start_token as u16, code.end_token as i16));
+            let line = &self.files[code.line_number_in_map as usize].trimmed_contents;
+
+            line.get(position.start..position.start + position.length).unwrap_or_default().to_string()
+        });
+
+        self.reported_errors.push((info.title.clone(), underlined.unwrap_or_default()));
     }
 
 
@@ -105,6 +133,28 @@ impl LineMap{
         TokenPosition::new(start_token_start, total_length)
     }
 
+    /// Generates the display code info that underlines all tokens inside
+    /// the given position.
+    pub fn display_code_for(&self, position: TokenPosition, annotations: Vec<String>, kind: DisplayCodeKind) -> DisplayCodeInfo {
+        let tokens_positions = &self.files[position.line].tokens_positions;
+        let end = position.start + position.length;
+
+        let start_token = tokens_positions.iter().position(|x| x.start >= position.start).unwrap_or(0);
+        let end_token = tokens_positions.iter().rposition(|x| x.start + x.length <= end && x.start >= position.start).unwrap_or(start_token);
+
+        DisplayCodeInfo::new(position.line as u32, start_token as u32, end_token as i32, annotations, kind)
+    }
+
+    /// Like [display_code_for](Self::display_code_for), but underlines exactly the characters
+    /// inside the position, even if they're only a part of a token (e.g. an escape sequence
+    /// inside a string).
+    pub fn display_characters_for(&self, position: TokenPosition, annotations: Vec<String>, kind: DisplayCodeKind) -> DisplayCodeInfo {
+        let mut info = self.display_code_for(position, annotations, kind);
+        info.characters = Some(position);
+
+        info
+    }
+
     #[cfg(test)]
     pub fn add_file(&mut self, line: LmFile) {
         self.files.push(line);
@@ -155,6 +205,7 @@ impl NotificationInfo {
 /// 3. The last token that should be underlined (or -1 for all tokens afterward)
 /// 4. Annotations that get printed under the first affected token.
 /// 5. The kind of info (warning, error, additional information)
+/// 6. Optionally, the exact characters to underline instead of the tokens
 ///
 /// Multiple display code infos may be stored in a notification info.
 ///
@@ -167,12 +218,13 @@ pub struct DisplayCodeInfo{
                                     // tokens. Look at the rust compiler
     pub kind: DisplayCodeKind,      // For example: InitialError (=> red), InitialWarning (=>
                                     // yellow) or AdditionalInfo (=> green)
+    pub characters: Option<TokenPosition>, // Overrides the underlined tokens if present
 }
 
 
 impl DisplayCodeInfo {
     pub fn new(line_number_in_map: u32, start_token: u32, end_token: i32, annotations: Vec<String>, kind: DisplayCodeKind) -> Self{
-        DisplayCodeInfo { line_number_in_map, start_token, end_token, annotations, kind }
+        DisplayCodeInfo { line_number_in_map, start_token, end_token, annotations, kind, characters: None }
     }
 
 
@@ -214,7 +266,7 @@ impl DisplayCodeInfo {
         let mut leading_text = separation_string.clone();
 
         // 1. Add the leading spaces
-        let start_token_start = line.tokens_positions[self.start_token as usize].start;
+        let start_token_start = self.characters.map_or(line.tokens_positions[self.start_token as usize].start, |x| x.start);
         for _ in 0..start_token_start {
             leading_text += " ";
         }
@@ -246,7 +298,7 @@ impl DisplayCodeInfo {
 
         let end_token_position = line.tokens_positions[end_token as usize].clone();
         let end_token_end = end_token_position.start + end_token_position.length;
-        let underlined_length = end_token_end - start_token_start;
+        let underlined_length = self.characters.map_or(end_token_end - start_token_start, |x| x.length);
 
         print!("{}", leading_text);
 
@@ -324,9 +376,24 @@ pub struct Position {
 pub struct TokenPosition{
     pub start: usize,
     pub length: usize,
+
+    /// The index of the line in the [line map](LineMap) the token is in.
+    #[new(default)]
+    pub line: usize,
 }
 
 impl TokenPosition {
+    /// Creates a position reaching from the start of the first position
+    /// to the end of the second one. The line is taken from the first one.
+    pub fn spanning(first: TokenPosition, last: TokenPosition) -> Self {
+        let end = (last.start + last.length).max(first.start + first.length);
+
+        let mut position = Self::new(first.start, end - first.start);
+        position.line = first.line;
+
+        position
+    }
+
     /// Creates a token for when it doesn't really matter in test cases
     #[cfg(test)]
     pub fn test_value() -> Self {
@@ -3,10 +3,15 @@ use crate::ArgumentList;
 mod compiler_coordinator;
 pub mod data_types;
 pub mod line_map;
+mod modules;
 pub mod trimmer;
 pub mod tokenization;
 pub mod parser;
 mod backend;
+mod type_checker;
+
+pub use backend::context::OverflowBehaviour;
+pub use backend::arch::Target;
 
 pub fn compile(code: String, args: ArgumentList) {
     compiler_coordinator::compile(code, args);
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use derive_new::new;
+use crate::compiler::data_types::integer::IntegerType;
+use crate::compiler::data_types::object::ObjectType;
+use crate::compiler::line_map::{DisplayCodeKind, LineMap, NotificationInfo, TokenPosition};
+use crate::compiler::parser::parse_path::parse_path;
+use crate::compiler::tokenization::token::Token;
+use crate::compiler::tokenization::tokenizer::tokenize_file;
+use crate::config::tokenization_options::Keyword;
+
+/// The extension of imported files if the importing one doesn't have any.
+const DEFAULT_EXTENSION: &str = "txt";
+
+/// ### A Source File's Namespace
+///
+/// Functions, constants and statics declared in a module are known by their full name
+/// (e.g. `geometry::shapes::area`) in [the context](crate::compiler::backend::context::Context).
+/// Local variables and types (structs and enums) aren't namespaced.
+#[derive(Clone, Debug, Default, new)]
+pub struct Module {
+    /// The full name of the module (e.g. `geometry::shapes`), empty for the file that is compiled.
+    pub name: String,
+
+    /// The imported modules, mapped from the name they're used with in this file (e.g.
+    /// `shapes`) to their full name.
+    #[new(default)]
+    pub imports: HashMap<String, String>,
+
+    /// The modules whose contents can be used without their name (`import std::*;`), given
+    /// by their full name.
+    #[new(default)]
+    pub glob_imports: Vec<String>,
+}
+
+impl Module {
+    /// The full name of something declared in this module.
+    pub fn qualify(&self, name: &str) -> String {
+        if self.name.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}::{}", self.name, name)
+        }
+    }
+
+    /// ### The Full Names a Name Could Refer To
+    ///
+    /// In the order they should be tried. A path (e.g. `shapes::area`) starts with an
+    /// imported module, everything else is declared in this module, the compiled file
+    /// or a module imported with `*`.
+    pub fn get_candidates(&self, name: &str) -> Vec<String> {
+        if let Some((first, rest)) = name.split_once("::") {
+            return match self.imports.get(first) {
+                Some(module) => vec![format!("{}::{}", module, rest), name.to_string()],
+                None => vec![name.to_string()],
+            }
+        }
+
+        let mut candidates = vec![self.qualify(name), name.to_string()];
+        candidates.extend(self.glob_imports.iter().map(|module| format!("{}::{}", module, name)));
+        candidates.dedup();
+
+        candidates
+    }
+}
+
+/// A tokenized source file and the namespace its declarations belong to.
+#[derive(Clone, Debug, new)]
+pub struct SourceFile {
+    pub tokens: Vec<Token>,
+    pub module: Module,
+}
+
+/// ### Loads a File and Everything It Imports
+///
+/// Imports are resolved relative to the importing file (`import geometry::shapes;` in
+/// `src/main.txt` refers to `src/geometry/shapes.txt`). Every file is only loaded once,
+/// an import cycle is reported instead. The files are returned such that every file
+/// comes after the ones it imports, the root file being the last one.
+pub fn load_modules(root_file: &str, contents: String, integer_types: Rc<Vec<(IntegerType, ObjectType)>>, line_map: &mut LineMap) -> Vec<SourceFile> {
+    let root_file = PathBuf::from(root_file);
+    let root_directory = root_file.parent().map(Path::to_path_buf).unwrap_or_default();
+
+    let mut loader = ModuleLoader::new(root_directory, integer_types);
+    loader.load(root_file, contents, String::new(), line_map);
+
+    loader.files
+}
+
+#[derive(new)]
+struct ModuleLoader {
+    root_directory: PathBuf,
+    integer_types: Rc<Vec<(IntegerType, ObjectType)>>,
+
+    /// The full names of all modules that have been loaded, by their file.
+    #[new(default)]
+    loaded: HashMap<PathBuf, String>,
+
+    /// The files that are currently being loaded, the last one being imported by the one before.
+    #[new(default)]
+    stack: Vec<(PathBuf, String)>,
+
+    #[new(default)]
+    files: Vec<SourceFile>,
+}
+
+impl ModuleLoader {
+    fn load(&mut self, file: PathBuf, contents: String, name: String, line_map: &mut LineMap) {
+        let tokens = tokenize_file(contents, &file.to_string_lossy(), 0, self.integer_types.clone(), line_map);
+        let mut module = Module::new(name.clone());
+
+        self.stack.push((file.clone(), name.clone()));
+
+        for (index, token) in tokens.iter().enumerate() {
+            if !matches!(token, Token::KeywordType(Keyword::Import, _)) { continue; }
+
+            // Malformed imports are reported while parsing.
+            let Some(path) = parse_path(&tokens, &mut (index + 1), true) else { continue; };
+
+            let imported_file = self.get_file_of(&file, &path.segments);
+            let imported_name = self.get_module_name(&imported_file, &path.segments);
+            let key = imported_file.canonicalize().unwrap_or(imported_file.clone());
+
+            if let Some(cycle_start) = self.stack.iter().position(|x| x.0.canonicalize().unwrap_or(x.0.clone()) == key) {
+                let chain = self.stack[cycle_start..].iter()
+                    .map(|x| x.0.to_string_lossy().to_string())
+                    .chain(std::iter::once(imported_file.to_string_lossy().to_string()))
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+
+                report_error(line_map, path.position, "Import Cycle", "A module can't (indirectly) import itself", format!("*note:* the imports are: {}", chain));
+                continue;
+            }
+
+            if !self.loaded.contains_key(&key) {
+                let Ok(imported_contents) = std::fs::read_to_string(&imported_file) else {
+                    report_error(line_map, path.position, "Module Not Found", &format!("The module `{}` doesn't exist", path.get_name()), format!("*note:* there is no file at `{}`", imported_file.to_string_lossy()));
+                    continue;
+                };
+
+                self.load(imported_file, imported_contents, imported_name.clone(), line_map);
+            }
+
+            if path.is_glob {
+                module.glob_imports.push(imported_name);
+            } else {
+                module.imports.insert(path.segments.last().unwrap().clone(), imported_name);
+            }
+        }
+
+        self.stack.pop();
+        self.loaded.insert(file.canonicalize().unwrap_or(file), name);
+        self.files.push(SourceFile::new(tokens, module));
+    }
+
+    /// The file a path in an import refers to, relative to the importing file.
+    fn get_file_of(&self, importing_file: &Path, segments: &[String]) -> PathBuf {
+        let mut file = importing_file.parent().map(Path::to_path_buf).unwrap_or_default();
+        segments.iter().for_each(|segment| file.push(segment));
+
+        let extension = importing_file.extension().and_then(|x| x.to_str()).unwrap_or(DEFAULT_EXTENSION);
+        file.set_extension(extension);
+
+        file
+    }
+
+    /// The full name of the module in a file, which is its path relative to the compiled
+    /// file. Files outside its directory are known by the path they're imported with.
+    fn get_module_name(&self, file: &Path, segments: &[String]) -> String {
+        let Ok(relative) = file.with_extension("").strip_prefix(&self.root_directory).map(Path::to_path_buf) else {
+            return segments.join("::");
+        };
+
+        relative.iter().map(|x| x.to_string_lossy().to_string()).collect::<Vec<_>>().join("::")
+    }
+}
+
+fn report_error(line_map: &mut LineMap, position: TokenPosition, title: &str, message: &str, note: String) {
+    let notification = NotificationInfo::new(
+        title.to_string(),
+        message.to_string(),
+        vec![
+            line_map.display_code_for(position, vec![note], DisplayCodeKind::InitialError),
+        ],
+    );
+
+    line_map.display_error(notification);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compiler::modules::Module;
+
+    #[test]
+    fn test_get_candidates() {
+        let mut module = Module::new("geometry::shapes".to_string());
+        module.imports.insert("math".to_string(), "geometry::math".to_string());
+        module.glob_imports.push("std".to_string());
+
+        assert_eq!(module.get_candidates("area"), vec!["geometry::shapes::area", "area", "std::area"]);
+        assert_eq!(module.get_candidates("math::sqrt"), vec!["geometry::math::sqrt", "math::sqrt"]);
+        assert_eq!(module.get_candidates("other::x"), vec!["other::x"]);
+        assert_eq!(Module::default().get_candidates("main"), vec!["main"]);
+    }
+}
@@ -6,8 +6,15 @@ pub struct FunctionMeta {
     pub code_name: String,
     pub assembly_name: String,
     pub assembly_style: FunctionStyle,
-    pub return_type_uuid: Option<Uuid>,
+
+    /// The types of the values the function returns, in the order of the
+    /// [registers they're returned in](crate::compiler::backend::arch::Architecture::get_register_for_return_value).
+    /// Only smisc functions can return more than one.
+    pub return_types: Vec<Uuid>,
     pub arguments: Vec<FunctionArgument>,
+
+    /// Whether further arguments can follow the listed ones (like for C's `printf`).
+    pub is_variadic: bool,
 }
 
 #[derive(Debug, Clone, new)]
@@ -17,9 +24,16 @@ pub struct FunctionArgument {
     pub type_uuid: Uuid,
 }
 
-#[derive(Debug, Clone)]
+/// ### The Calling Convention of a Function
+///
+/// Where arguments and return values are passed, see
+/// [get_register_for_argument](crate::compiler::backend::arch::Architecture::get_register_for_argument).
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum FunctionStyle {
+    /// The convention of the platform's C ABI, used for `extern "C"` functions.
     C,
-    #[allow(dead_code)]
+
+    /// The native convention used for all other functions. It passes more arguments and
+    /// return values in registers and restores fewer registers, but only smisc code can call them.
     Smisc
 }
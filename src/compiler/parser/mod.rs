@@ -9,6 +9,11 @@ pub mod function_meta;
 mod parse_code_block;
 mod parse_line;
 mod parse_datatype;
+mod parse_struct;
+mod parse_enum;
+mod parse_match;
+mod parse_asm;
+pub mod parse_path;
 mod parse_arg_array;
 mod modifier;
 mod parse_expression_kind;
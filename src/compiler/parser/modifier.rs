@@ -35,6 +35,7 @@ impl Modifier {
 
     pub fn get_format(&self) -> Vec<(ExpressionKind, bool)> {
         match self.base {
+            // The ABI and optionally the assembly name (e.g. `extern "C" "_start"`)
             Keyword::Extern => {
                 vec![
                     (
@@ -43,7 +44,7 @@ impl Modifier {
                     ),
                     (
                         ExpressionKind::StringLiteral,
-                        true,
+                        false,
                     )
                 ]
             },
@@ -7,23 +7,23 @@ use crate::config::tokenization_options::Keyword;
 use strum::IntoEnumIterator;
 use crate::compiler::data_types::object::ObjectType;
 use crate::compiler::parser::parse_line::parse_line;
-use crate::compiler::parser::parser_meta::ParserMetaState;
+use crate::compiler::parser::parser_meta::{BlockStack, ParserMetaState};
 
 pub fn parse(files: Vec<Vec<Token>>, line_map: &mut LineMap, object_types: &mut Rc<Vec<ObjectType>>) -> Option<Rc<dyn Node>> {
     let statements = Rc::new(Statements::iter().collect::<Vec<_>>());
     let lines_in_block: Vec<Rc<dyn Node>> = vec![];
 
     let code = CodeBlockNode::new((0, TokenPosition::new(0, 0)), Some(Rc::new("_stray".to_string())), lines_in_block);
-    let mut blocks: Vec<CodeBlockNode> = vec![code];
+    let mut block_stack = BlockStack::new(vec![code]);
 
 
     for x in files.iter().enumerate() {
         let contents = x.1;
         let mut file_number = x.0;
-        let mut code_block_depth = 0;
-        let mut current_block_index = 0;
         let mut cursor = 0;
-        let mut object_types = object_types.clone();
+
+        block_stack.code_block_depth = 0;
+        block_stack.current_block_idx = 0;
 
 
         if contents.is_empty() { continue; }
@@ -31,15 +31,13 @@ pub fn parse(files: Vec<Vec<Token>>, line_map: &mut LineMap, object_types: &mut
 
         while cursor < contents.len() {
             let mut parser_meta = ParserMetaState::new(
-                Rc::new(files[0].clone()),
+                Rc::new(contents.clone()),
                 &mut cursor,
                 line_map,
                 statements.clone(),
                 &mut file_number,
-                &mut blocks,
-                &mut current_block_index,
-                &mut code_block_depth,
-                &mut object_types
+                &mut block_stack,
+                object_types
             );
 
 
@@ -50,7 +48,7 @@ pub fn parse(files: Vec<Vec<Token>>, line_map: &mut LineMap, object_types: &mut
     }
 
 
-    Some(Rc::new(CodeBlockArray::new((0, TokenPosition::new(0, 0)), blocks.clone())))
+    Some(Rc::new(CodeBlockArray::new((0, TokenPosition::new(0, 0)), block_stack.blocks.clone())))
 }
 
 #[derive(Clone, Debug)]
@@ -58,6 +56,9 @@ pub enum ExpressionKind {
     /// Something that is supposed to be parsed as an arithmetic expression
     Value,
 
+    /// Any amount of comma separated [values](Self::Value), including none (e.g. `a, b + 1`)
+    Values,
+
     Assignment,
 
     /// **Note:** None means multiple keywords are allowed here
@@ -66,6 +67,10 @@ pub enum ExpressionKind {
     /// **Note:** Only Identifiers that won't get coerced into values
     Identifier(Option<String>),
 
+    /// The name of a declared variable (e.g. `a`) or several names in parentheses,
+    /// which receive the values a function returns (e.g. `(q, r)`)
+    DeclaredNames,
+
     CodeBlock,
 
     /// An expression kind repeated multiple times.
@@ -75,7 +80,29 @@ pub enum ExpressionKind {
     /// Something with a name and a type (e.g. `a: u32`)
     Parameter,
 
+    /// A colon followed by a type (e.g. `: u32`)
+    TypeAnnotation,
+
+    /// An arrow followed by the type a function returns (e.g. `-> isize`), or
+    /// several ones in parentheses (e.g. `-> (u32, u32)`)
+    ReturnType,
+
     StringLiteral,
+
+    /// The name and the fields of a struct (e.g. `Point { x: i32, y: i32 }`)
+    StructDeclaration,
+
+    /// The name and the variants of an enum (e.g. `Color { Red, Green = 5 }`)
+    EnumDeclaration,
+
+    /// The arms of a match statement (e.g. `{ 1, 2 => { ... }, _ => { ... } }`)
+    MatchArms,
+
+    /// The path of an imported module (e.g. `geometry::shapes` or `std::*`)
+    ImportPath,
+
+    /// The lines, operands and clobbers of inline assembly (e.g. `{ "nop", in(reg) a }`)
+    AsmBlock,
 }
 
 
@@ -89,7 +116,7 @@ mod tests {
     use crate::compiler::line_map::{LineMap, TokenPosition};
     use crate::compiler::parser::parse_arithmetic_expression::parse_arithmetic_expression;
     use crate::compiler::parser::parse_token::parse_token;
-    use crate::compiler::parser::parser_meta::ParserMetaState;
+    use crate::compiler::parser::parser_meta::{BlockStack, ParserMetaState};
     use crate::compiler::parser::statements::Statements;
     use crate::compiler::tokenization::token::Token;
     use crate::compiler::parser::tree::node::*;
@@ -99,7 +126,7 @@ mod tests {
     #[test]
     fn test_parse_token() {
         let node = parse_token(Token::IntegerLiteral(10, Some(IntegerType::Unsigned32BitInteger), TokenPosition::test_value()), 0, LineMap::new());
-        let expected = Some(ValueNode::Literal(LiteralValueNode::Integer(IntegerLiteralNode { content: 10, kind: Some(IntegerType::Unsigned32BitInteger), position: (0, TokenPosition { start: 0, length: 0 }) })));
+        let expected = Some(ValueNode::Literal(LiteralValueNode::Integer(IntegerLiteralNode { content: 10, kind: Some(IntegerType::Unsigned32BitInteger), position: (0, TokenPosition { start: 0, length: 0, line: 0 }) })));
 
         assert_eq!(format!("{node:?}"), format!("{expected:?}"));
     }
@@ -119,10 +146,8 @@ mod tests {
         let mut cursor = 0;
         let statements = Rc::new(Statements::iter().collect::<Vec<_>>());
         let mut object_types = Rc::new(ObjectType::generate_built_ins());
-        let mut code_block_depth = 0;
-        let mut current_block_index = 0;
         let mut file_number = 0;
-        let mut blocks = vec![];
+        let mut block_stack = BlockStack::new(vec![]);
         let mut line_map = LineMap::test_map();
 
         let mut meta = ParserMetaState::new(
@@ -131,9 +156,7 @@ mod tests {
             &mut line_map,
             statements,
             &mut file_number,
-            &mut blocks,
-            &mut current_block_index,
-            &mut code_block_depth,
+            &mut block_stack,
             &mut object_types
         );
 
@@ -149,8 +172,8 @@ mod tests {
     #[test]
     fn test_parse_multiplication() {
         let integer_types = Rc::new(build_integer_types());
-        let tokens1 = tokenize_file("6 + 7 * 67 - 420;".to_string(), 0, integer_types.clone(), &mut LineMap::test_map(),);
-        let tokens2 = tokenize_file("(6 + (7 * 67)) - 420;".to_string(), 0, integer_types, &mut LineMap::test_map(),);
+        let tokens1 = tokenize_file("6 + 7 * 67 - 420;".to_string(), "n/a", 0, integer_types.clone(), &mut LineMap::test_map(),);
+        let tokens2 = tokenize_file("(6 + (7 * 67)) - 420;".to_string(), "n/a", 0, integer_types, &mut LineMap::test_map(),);
 
         let mut parsed1;
         let mut parsed2;
@@ -159,10 +182,8 @@ mod tests {
             let mut cursor = 0;
             let statements = Rc::new(Statements::iter().collect::<Vec<_>>());
             let mut object_types = Rc::new(ObjectType::generate_built_ins());
-            let mut code_block_depth = 0;
-            let mut current_block_index = 0;
             let mut file_number = 0;
-            let mut blocks = vec![];
+            let mut block_stack = BlockStack::new(vec![]);
             let mut line_map = LineMap::test_map();
 
 
@@ -172,9 +193,7 @@ mod tests {
                 &mut line_map,
                 statements,
                 &mut file_number,
-                &mut blocks,
-                &mut current_block_index,
-                &mut code_block_depth,
+                &mut block_stack,
                 &mut object_types
             );
 
@@ -193,10 +212,8 @@ mod tests {
             let mut cursor = 0;
             let statements = Rc::new(Statements::iter().collect::<Vec<_>>());
             let mut object_types = Rc::new(ObjectType::generate_built_ins());
-            let mut code_block_depth = 0;
-            let mut current_block_index = 0;
             let mut file_number = 0;
-            let mut blocks = vec![];
+            let mut block_stack = BlockStack::new(vec![]);
             let mut line_map = LineMap::test_map();
 
 
@@ -206,9 +223,7 @@ mod tests {
                 &mut line_map,
                 statements,
                 &mut file_number,
-                &mut blocks,
-                &mut current_block_index,
-                &mut code_block_depth,
+                &mut block_stack,
                 &mut object_types
             );
 
@@ -227,4 +242,116 @@ mod tests {
 
         assert_eq!(format!("{:?}", parsed1), format!("{:?}", parsed2));
     }
+
+    /// Parses each of the expressions and returns their debug representations,
+    /// with the positions and the uuids of the types removed, so expressions
+    /// can be compared with their fully parenthesized versions.
+    fn parse_expressions(inputs: &[&str]) -> Vec<String> {
+        let integer_types = Rc::new(build_integer_types());
+        let mut parsed: Vec<String> = vec![];
+
+        for input in inputs {
+            let tokens = tokenize_file(input.to_string(), "n/a", 0, integer_types.clone(), &mut LineMap::test_map());
+
+            let mut cursor = 0;
+            let statements = Rc::new(Statements::iter().collect::<Vec<_>>());
+            let mut object_types = Rc::new(ObjectType::generate_built_ins());
+            let mut file_number = 0;
+            let mut block_stack = BlockStack::new(vec![]);
+            let mut line_map = LineMap::test_map();
+
+            let mut meta = ParserMetaState::new(
+                Rc::new(tokens),
+                &mut cursor,
+                &mut line_map,
+                statements,
+                &mut file_number,
+                &mut block_stack,
+                &mut object_types
+            );
+
+            let node = parse_arithmetic_expression(&mut meta, 0, true).unwrap();
+            let mut node = node.downcast_rc::<ValueNode>().unwrap().deref().clone();
+
+            node.repeatedly_reset_position();
+
+            // The uuids of the types differ between both runs.
+            let debug_string = format!("{:?}", node);
+            let mut parts = debug_string.split("datatype: ");
+            let first = parts.next().unwrap_or_default().to_string();
+
+            parsed.push(first + &parts.map(|x| x.split_once(',').map_or(x, |x| x.1)).collect::<String>());
+        }
+
+        parsed
+    }
+
+    #[test]
+    fn test_parse_logical_precedence() {
+        let parsed = parse_expressions(&[
+            "a || b && c + 1 == d ^^ e;",
+            "(a || (b && ((c + 1) == d))) ^^ e;",
+        ]);
+
+        assert_eq!(parsed[0], parsed[1]);
+        assert!(parsed[0].starts_with("Arithmetic(ArithmeticNode { operation: Xor"));
+    }
+
+    #[test]
+    fn test_parse_unary_operators() {
+        let parsed = parse_expressions(&[
+            "-a * ~b + !c == -5;",
+            "((((-a) * (~b)) + (!c)) == (-5));",
+        ]);
+
+        assert_eq!(parsed[0], parsed[1]);
+        assert!(parsed[0].contains("Unary(UnaryNode { operation: Subtraction"));
+        assert!(parsed[0].contains("Integer(IntegerLiteralNode { content: -5"));
+    }
+
+    #[test]
+    fn test_parse_casts() {
+        let parsed = parse_expressions(&[
+            "a + b as u8 * c;",
+            "a + ((b as u8) * c);",
+        ]);
+
+        assert_eq!(parsed[0], parsed[1]);
+        assert!(parsed[0].contains("Multiplication, argument_a: Cast(CastNode { value: Identifier(IdentifierNode { identifier: \"b\""));
+    }
+
+    #[test]
+    fn test_parse_arrays() {
+        let parsed = parse_expressions(&[
+            "[a, b + 1][i * 2] + c;",
+            "([a, (b + 1)][(i * 2)]) + c;",
+        ]);
+
+        assert_eq!(parsed[0], parsed[1]);
+        assert!(parsed[0].contains("Addition, argument_a: Index(IndexNode { array: ArrayLiteral(ArrayLiteralNode { elements: [Identifier"));
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        let parsed = parse_expressions(&[
+            "-a.b.c * d[0].e;",
+            "(-((a.b).c)) * ((d[0]).e);",
+        ]);
+
+        assert_eq!(parsed[0], parsed[1]);
+        assert!(parsed[0].contains("Multiplication, argument_a: Unary(UnaryNode { operation: Subtraction, argument: FieldAccess(FieldAccessNode { value: FieldAccess"));
+        assert!(parsed[0].contains("argument_b: FieldAccess(FieldAccessNode { value: Index(IndexNode"));
+    }
+
+    #[test]
+    fn test_parse_references() {
+        let parsed = parse_expressions(&[
+            "*a * new b + &c[1] as u8;",
+            "((*a) * (new b)) + ((&(c[1])) as u8);",
+        ]);
+
+        assert_eq!(parsed[0], parsed[1]);
+        assert!(parsed[0].contains("Multiplication, argument_a: Dereference(DereferenceNode { value: Identifier"));
+        assert!(parsed[0].contains("Cast(CastNode { value: AddressOf(AddressOfNode { value: Index(IndexNode"));
+    }
 }
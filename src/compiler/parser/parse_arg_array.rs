@@ -4,7 +4,7 @@ use crate::compiler::line_map::LineMap;
 use crate::compiler::parser::parser_meta::ParserMetaState;
 use crate::compiler::tokenization::token::Token;
 
-type ArrayParsingSubfunction<T> = dyn Fn(Rc<Vec<Token>>, &mut usize, &mut LineMap, Rc<Vec<ObjectType>>) -> T;
+type ArrayParsingSubfunction<T> = dyn Fn(Rc<Vec<Token>>, &mut usize, &mut LineMap, &mut Rc<Vec<ObjectType>>) -> T;
 
 /// Parses an argument array using another parse function.
 /// The array should be comma separated and be constrained by
@@ -56,7 +56,7 @@ pub fn parse_arg_array<T>(state: &mut ParserMetaState, parse_fn: &ArrayParsingSu
 
                 // Find whatever is expected
                 let mut map = state.line_map.clone();
-                data.push(parse_fn(state.tokens.clone(), state.cursor, &mut map, state.datatypes.clone()));
+                data.push(parse_fn(state.tokens.clone(), state.cursor, &mut map, state.datatypes));
                 *state.line_map = map;
                 expected_item = false;
             }
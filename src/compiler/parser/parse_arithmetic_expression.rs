@@ -1,11 +1,19 @@
 use std::ops::Deref;
 use std::rc::Rc;
 use clap::Parser;
+use crate::compiler::data_types::enumeration::get_enum_variants;
+use crate::compiler::data_types::structure::get_struct_fields;
 use crate::compiler::line_map::TokenPosition;
 use crate::compiler::parser::parse_token::parse_token;
 use crate::compiler::parser::parser_meta::ParserMetaState;
-use crate::compiler::parser::tree::node::{ArithmeticNode, FunctionCallNode, Node, ValueNode};
+use crate::compiler::parser::parse_datatype::parse_datatype;
+use crate::compiler::parser::parse_enum::parse_enum_variant;
+use crate::compiler::parser::parse_struct::parse_struct_literal;
+use crate::compiler::parser::parse_path::parse_path;
+use crate::compiler::parser::tree::node::{AddressOfNode, ArithmeticNode, ArrayLiteralNode, CastNode, DereferenceNode, FieldAccessNode, FunctionCallNode, IdentifierNode, IndexNode, IntegerLiteralNode, LiteralValueNode, NewNode, Node, SyscallNode, UnaryNode, ValueNode};
 use crate::compiler::tokenization::token::Token;
+use crate::config::tokenization_options::Keyword;
+use crate::config::misc::SYSCALL_INTRINSIC;
 use crate::util::operator::Operation;
 
 pub fn parse_arithmetic_expression(meta_state: &mut ParserMetaState, min_op_importance: u8, stop_at_unexpected_token: bool) -> Option<Rc<dyn Node>> {
@@ -54,7 +62,9 @@ pub fn parse_arithmetic_expression(meta_state: &mut ParserMetaState, min_op_impo
             break;
         }
 
-        if token.is_line_delimiting() {
+        // Expressions end before assignments (e.g. `*a = 5`) and the arrows of match arms (e.g. `-1 =>`),
+        // even when they're the operand of a unary operator.
+        if token.is_line_delimiting() || matches!(token, Token::Assignment(_) | Token::CompoundAssignment(_, _) | Token::FatArrow(_)) {
             *meta_state.cursor -= 1;
             break;
         }
@@ -74,6 +84,7 @@ pub fn parse_arithmetic_expression(meta_state: &mut ParserMetaState, min_op_impo
 
                                 // Gather all the arguments
                                 let mut args: Vec<Rc<dyn Node>> = Vec::new();
+                                let end_pos: TokenPosition;
 
                                 loop {
                                     println!("current token (pae/f): {:?}", meta_state.tokens[*meta_state.cursor]);
@@ -95,7 +106,7 @@ pub fn parse_arithmetic_expression(meta_state: &mut ParserMetaState, min_op_impo
                                     if let Some(token) = meta_state.tokens.get(*meta_state.cursor ) {
                                         *meta_state.cursor += 1;
                                         match token {
-                                            Token::ArithmeticParenthesisClose(_) => {break;},
+                                            Token::ArithmeticParenthesisClose(pos) => {end_pos = *pos; break;},
                                             Token::ArgumentSeparator(_) => {println!("argument separated, args: {args:?}"); continue},
                                             _ => {todo!("Unexpected token in function call: {:?}", token)}
                                         }
@@ -107,14 +118,21 @@ pub fn parse_arithmetic_expression(meta_state: &mut ParserMetaState, min_op_impo
                     println!("ended, args: {args:?}");
 
 
-                                let function_node = FunctionCallNode::new(
-                                    Rc::new(function_name),
-                                    args,
-                                    (*meta_state.file_number, TokenPosition::new(start_pos.1.start, 0))
-                                );
+                                // The call reaches from the name to the closing parenthesis.
+                                let position = (*meta_state.file_number, TokenPosition::spanning(start_pos.1, end_pos));
+
+                                let function_node: Rc<dyn Node> = if function_name == SYSCALL_INTRINSIC {
+                                    Rc::new(SyscallNode::new(args, position))
+                                } else {
+                                    Rc::new(FunctionCallNode::new(
+                                        Rc::new(function_name),
+                                        args,
+                                        position
+                                    ))
+                                };
 
                                 calculated_nodes.remove(calculated_nodes.len() - 1);
-                                calculated_nodes.push(Rc::new(function_node));
+                                calculated_nodes.push(function_node);
 
                                 break;
                             }
@@ -131,11 +149,138 @@ pub fn parse_arithmetic_expression(meta_state: &mut ParserMetaState, min_op_impo
 
             }
 
-            Token::ArithmeticParenthesisClose(_) => {
+            Token::ArrayParenthesisOpen(open_position) => {
+                let expects_operand = calculated_nodes.len() == current_operation.is_some() as usize;
+                let elements = parse_array_elements(meta_state);
+                let close_position = meta_state.tokens[*meta_state.cursor - 1].get_position();
+
+                if expects_operand {
+                    let position = TokenPosition::spanning(open_position, close_position);
+                    calculated_nodes.push(Rc::new(ValueNode::ArrayLiteral(ArrayLiteralNode::new(elements, (*meta_state.file_number, position)))));
+
+                    continue;
+                }
+
+                // Indexing applies to the operand right before it, just like casts.
+                let [index] = elements.as_slice() else {
+                    todo!("Expected exactly one index, but got {}", elements.len())
+                };
+
+                let array = calculated_nodes.pop().unwrap();
+                let position = TokenPosition::spanning(array.get_position().1, close_position);
+
+                calculated_nodes.push(Rc::new(ValueNode::Index(IndexNode::new(array, index.clone(), (*meta_state.file_number, position)))));
+            }
+
+            // Fields apply to the operand right before them, just like indices.
+            Token::Dot(_) => {
+                if calculated_nodes.len() == current_operation.is_some() as usize {
+                    todo!("Expected a value before '.'")
+                }
+
+                let Some(Token::Identifier(field, field_position)) = meta_state.tokens.get(*meta_state.cursor).cloned() else {
+                    todo!("Expected the name of a field after '.'")
+                };
+                *meta_state.cursor += 1;
+
+                let value = calculated_nodes.pop().unwrap();
+                let position = TokenPosition::spanning(value.get_position().1, field_position);
+
+                calculated_nodes.push(Rc::new(ValueNode::FieldAccess(FieldAccessNode::new(value, field, field_position, (*meta_state.file_number, position)))));
+            }
+
+            // A struct's name followed by a `{` starts a literal, other identifiers
+            // can be followed by code blocks (e.g. `if a {`).
+            Token::Identifier(name, position) if matches!(meta_state.tokens.get(*meta_state.cursor), Some(Token::CodeBlockParenthesisOpen(_)))
+                && meta_state.datatypes.iter().any(|x| x.name == name && get_struct_fields(x).is_some()) => {
+                let literal = parse_struct_literal(meta_state, name, position);
+
+                calculated_nodes.push(Rc::new(ValueNode::StructLiteral(literal)));
+            }
+
+            // An enum's name followed by `::` refers to one of its variants (e.g. `Color::Red`).
+            Token::Identifier(name, position) if matches!(meta_state.tokens.get(*meta_state.cursor), Some(Token::PathSeparator(_)))
+                && meta_state.datatypes.iter().any(|x| x.name == name && get_enum_variants(x).is_some()) => {
+                let variant = parse_enum_variant(meta_state, name, position);
+
+                calculated_nodes.push(Rc::new(ValueNode::EnumVariant(variant)));
+            }
+
+            // Other names followed by `::` refer to something in a module (e.g. `shapes::area`).
+            Token::Identifier(_, _) if matches!(meta_state.tokens.get(*meta_state.cursor), Some(Token::PathSeparator(_))) => {
+                *meta_state.cursor -= 1;
+
+                let Some(path) = parse_path(&meta_state.tokens, meta_state.cursor, false) else {
+                    todo!("Expected a name after `::`")
+                };
+
+                calculated_nodes.push(Rc::new(ValueNode::Identifier(IdentifierNode::new(path.get_name(), None, (*meta_state.file_number, path.position)))));
+            }
+
+            Token::ArithmeticParenthesisClose(_) | Token::ArrayParenthesisClose(_) => {
+                // Only the call that opened the parenthesis may consume it,
+                // calls for operands of stronger operators hand it back.
+                if min_op_importance > 0 {
+                    *meta_state.cursor -= 1;
+                }
                 break 'outerloop;
             }
 
-            Token::Operator(operation, _) => {
+            Token::KeywordType(Keyword::New, pos) => {
+                if calculated_nodes.len() != current_operation.is_some() as usize {
+                    todo!("Expected an operator before `new`")
+                }
+
+                let value = parse_arithmetic_expression(meta_state, Operation::UNARY_OPERATION_ORDER, false)?;
+                let position = TokenPosition::spanning(pos, value.get_position().1);
+
+                calculated_nodes.push(Rc::new(ValueNode::New(NewNode::new(value, (*meta_state.file_number, position)))));
+            }
+
+            // Casts apply to the operand right before them, so they bind stronger than any operator.
+            Token::KeywordType(Keyword::As, _) => {
+                if calculated_nodes.len() == current_operation.is_some() as usize {
+                    todo!("Expected a value to cast before `as`")
+                }
+
+                // Unary operators apply first (`-a as u8` is `(-a) as u8`).
+                if min_op_importance >= Operation::UNARY_OPERATION_ORDER {
+                    *meta_state.cursor -= 1;
+                    break;
+                }
+
+                let value = calculated_nodes.pop().unwrap();
+                let datatype = parse_datatype(meta_state.tokens.clone(), meta_state.cursor, meta_state.datatypes, meta_state.line_map);
+                let position = TokenPosition::spanning(value.get_position().1, meta_state.tokens[*meta_state.cursor - 1].get_position());
+
+                // Casts to unknown types were reported already, the value stays as it is.
+                let Some(datatype) = datatype else {
+                    calculated_nodes.push(value);
+                    continue;
+                };
+
+                calculated_nodes.push(Rc::new(ValueNode::Cast(CastNode::new(value, datatype, (*meta_state.file_number, position)))));
+            }
+
+            Token::Operator(operation, pos) => {
+                // An operator in place of an operand has to be a unary one (e.g. `-x`).
+                let expects_operand = calculated_nodes.len() == current_operation.is_some() as usize;
+
+                if expects_operand {
+                    if !operation.can_be_unary() {
+                        todo!("Expected an operand, but got the binary operator {}", operation.as_ref())
+                    }
+
+                    let operand = parse_arithmetic_expression(meta_state, Operation::UNARY_OPERATION_ORDER, false)?;
+                    calculated_nodes.push(generate_unary_node(operation, operand, pos, *meta_state.file_number));
+
+                    continue;
+                }
+
+                if operation.is_unary() {
+                    todo!("The unary operator {} can't be used with two operands", operation.as_ref())
+                }
+
                 if parenthesis_depth == 0 {
                     if operation.get_operation_order() < min_op_importance {
                         *meta_state.cursor -= 1;
@@ -145,10 +290,8 @@ pub fn parse_arithmetic_expression(meta_state: &mut ParserMetaState, min_op_impo
                         if operation.get_operation_order() <= previous_operation.get_operation_order() {
                             // The previous operation needs to happen first
                             // Combine the previous two nodes using the previous operation
-                            let start_pos = calculated_nodes[0].get_position().1.start;
-                            let end_pos = calculated_nodes[1].get_position().1.start + calculated_nodes[1].get_position().1.length;
-                            let length = end_pos - start_pos;
-                            let resulting_node = ValueNode::Arithmetic(ArithmeticNode::new(previous_operation, calculated_nodes.remove(0), calculated_nodes.remove(0), (*meta_state.file_number, TokenPosition::new(start_pos, length))));
+                            let position = TokenPosition::spanning(calculated_nodes[0].get_position().1, calculated_nodes[1].get_position().1);
+                            let resulting_node = ValueNode::Arithmetic(ArithmeticNode::new(previous_operation, calculated_nodes.remove(0), calculated_nodes.remove(0), (*meta_state.file_number, position)));
                             calculated_nodes = vec![Rc::new(resulting_node)];
 
                             current_operation = Some(operation.clone());
@@ -156,10 +299,8 @@ pub fn parse_arithmetic_expression(meta_state: &mut ParserMetaState, min_op_impo
                         }
 
                         let resulting_node = parse_arithmetic_expression(meta_state, operation.get_operation_order() + 1, false).unwrap();
-                        let start_pos = calculated_nodes.last().unwrap().get_position().1.start;
-                        let end_pos = resulting_node.get_position().1.start + calculated_nodes[1].get_position().1.length;
-                        let length = end_pos - start_pos;
-                        let multiplication = ValueNode::Arithmetic(ArithmeticNode::new(operation.clone(), calculated_nodes.remove(calculated_nodes.len() - 1), resulting_node, (*meta_state.file_number, TokenPosition::new(start_pos, length))));
+                        let position = TokenPosition::spanning(calculated_nodes.last().unwrap().get_position().1, resulting_node.get_position().1);
+                        let multiplication = ValueNode::Arithmetic(ArithmeticNode::new(operation.clone(), calculated_nodes.remove(calculated_nodes.len() - 1), resulting_node, (*meta_state.file_number, position)));
 
                         calculated_nodes.push(Rc::new(multiplication));
 
@@ -191,10 +332,8 @@ pub fn parse_arithmetic_expression(meta_state: &mut ParserMetaState, min_op_impo
 
     if let Some(current_operation) = current_operation {
         // Combine the previous two nodes using the previous operation
-        let start_pos = calculated_nodes[0].get_position().1.start;
-        let end_pos = calculated_nodes[1].get_position().1.start + calculated_nodes[1].get_position().1.length;
-        let length = end_pos - start_pos;
-        let resulting_node = ValueNode::Arithmetic(ArithmeticNode::new(current_operation, calculated_nodes.remove(0), calculated_nodes.remove(0), (*meta_state.file_number, TokenPosition::new(start_pos, length))));
+        let position = TokenPosition::spanning(calculated_nodes[0].get_position().1, calculated_nodes[1].get_position().1);
+        let resulting_node = ValueNode::Arithmetic(ArithmeticNode::new(current_operation, calculated_nodes.remove(0), calculated_nodes.remove(0), (*meta_state.file_number, position)));
         calculated_nodes = vec![Rc::new(resulting_node)];
     }
 
@@ -203,6 +342,73 @@ pub fn parse_arithmetic_expression(meta_state: &mut ParserMetaState, min_op_impo
 }
 
 
+/// Applies a unary operation to an operand. Negative integer literals are
+/// turned into literals right away, `*` and `&` become a [DereferenceNode]
+/// and an [AddressOfNode], everything else becomes a [UnaryNode].
+fn generate_unary_node(operation: Operation, operand: Rc<dyn Node>, operator_position: TokenPosition, file_number: usize) -> Rc<dyn Node> {
+    let position = TokenPosition::spanning(operator_position, operand.get_position().1);
+
+    match operation {
+        Operation::Multiplication => return Rc::new(ValueNode::Dereference(DereferenceNode::new(operand, (file_number, position)))),
+        Operation::BitwiseAnd => return Rc::new(ValueNode::AddressOf(AddressOfNode::new(operand, (file_number, position)))),
+        _ => {}
+    }
+
+    if operation == Operation::Subtraction
+        && let Ok(value_node) = operand.clone().downcast_rc::<ValueNode>()
+            && let ValueNode::Literal(LiteralValueNode::Integer(literal)) = value_node.deref() {
+                let negative_literal = IntegerLiteralNode::new(-literal.content, literal.kind.clone(), (file_number, position));
+                return Rc::new(ValueNode::Literal(LiteralValueNode::Integer(negative_literal)));
+            }
+
+    Rc::new(ValueNode::Unary(UnaryNode::new(operation, operand, (file_number, position))))
+}
+
+
+/// Parses the comma separated values of an array literal or index right after
+/// the opening `[` up to and including the closing `]`.
+fn parse_array_elements(meta_state: &mut ParserMetaState) -> Vec<Rc<dyn Node>> {
+    let mut elements: Vec<Rc<dyn Node>> = Vec::new();
+
+    loop {
+        skip_newlines(meta_state);
+
+        // Empty arrays and trailing commas
+        if let Some(Token::ArrayParenthesisClose(_)) = meta_state.tokens.get(*meta_state.cursor) {
+            *meta_state.cursor += 1;
+            break;
+        }
+
+        if let Some(element) = parse_arithmetic_expression(meta_state, 0, true) {
+            elements.push(element);
+        }
+
+        // The element either consumed the `]` or the `,` (and the newlines following it),
+        // or it stopped right before a newline.
+        if let Token::ArrayParenthesisClose(_) = meta_state.tokens[*meta_state.cursor - 1] {
+            break;
+        }
+
+        let previous_token = meta_state.tokens[..*meta_state.cursor].iter()
+            .rev()
+            .find(|x| !matches!(x, Token::HardNewline(_) | Token::SoftNewline(_)));
+
+        if let Some(Token::ArgumentSeparator(_)) = previous_token {
+            continue;
+        }
+
+        skip_newlines(meta_state);
+
+        match meta_state.tokens.get(*meta_state.cursor) {
+            Some(Token::ArrayParenthesisClose(_)) => { *meta_state.cursor += 1; break; }
+            token => todo!("Unexpected token in array: {:?}", token),
+        }
+    }
+
+    elements
+}
+
+
 pub fn skip_newlines(meta_state: &mut ParserMetaState) {
     while let Some(token) = meta_state.tokens.get(*meta_state.cursor) {
         if matches!(token, Token::HardNewline(_) | Token::SoftNewline(_)) {
@@ -0,0 +1,100 @@
+use std::rc::Rc;
+use crate::compiler::line_map::TokenPosition;
+use crate::compiler::parser::parse_arithmetic_expression::parse_arithmetic_expression;
+use crate::compiler::parser::parser_meta::ParserMetaState;
+use crate::compiler::parser::tree::node::{AsmOperand, IdentifierNode, InlineAssemblyNode, StringLiteralNode};
+use crate::compiler::tokenization::token::Token;
+
+/// ### Parses the Contents of an Inline Assembly Block
+///
+/// Expects the `{` following `asm`. The block consists of string literals holding the
+/// lines of assembly, followed by the operands (`in(reg) value` or `out(reg) variable`)
+/// and the clobbered registers (`clobber("x9", "x10")`), all separated by commas or
+/// newlines. Parsing stops right after the closing `}`.
+pub fn parse_asm_block(meta_state: &mut ParserMetaState) -> InlineAssemblyNode {
+    let Token::CodeBlockParenthesisOpen(open_position) = meta_state.tokens[*meta_state.cursor] else { todo!("Expected '{{' after asm") };
+    *meta_state.cursor += 1;
+
+    let mut lines: Vec<StringLiteralNode> = vec![];
+    let mut operands: Vec<AsmOperand> = vec![];
+    let mut clobbers: Vec<StringLiteralNode> = vec![];
+
+    loop {
+        while let Some(Token::SoftNewline(_) | Token::HardNewline(_) | Token::ArgumentSeparator(_)) = meta_state.tokens.get(*meta_state.cursor) {
+            *meta_state.cursor += 1;
+        }
+
+        match meta_state.tokens[*meta_state.cursor].clone() {
+            Token::CodeBlockParenthesisClose(_) => {
+                *meta_state.cursor += 1;
+                break;
+            }
+
+            Token::StringLiteral(line, position) => {
+                if !operands.is_empty() || !clobbers.is_empty() {
+                    todo!("Throw an error: the lines of assembly have to come before the operands")
+                }
+
+                lines.push(StringLiteralNode::new((*meta_state.file_number, position), Rc::new(line)));
+                *meta_state.cursor += 1;
+            }
+
+            Token::Identifier(kind, _) if kind == "in" || kind == "out" => {
+                *meta_state.cursor += 1;
+                parse_operand_class(meta_state);
+
+                if kind == "in" {
+                    let Some(value) = parse_arithmetic_expression(meta_state, 0, true) else { todo!("Expected the value of an input operand") };
+                    operands.push(AsmOperand::Input(value));
+                } else {
+                    let Token::Identifier(variable, position) = meta_state.tokens[*meta_state.cursor].clone() else { todo!("Expected the variable of an output operand") };
+                    *meta_state.cursor += 1;
+
+                    operands.push(AsmOperand::Output(IdentifierNode::new(variable, None, (*meta_state.file_number, position))));
+                }
+            }
+
+            Token::Identifier(kind, _) if kind == "clobber" => {
+                *meta_state.cursor += 1;
+
+                let Token::ArithmeticParenthesisOpen(_) = meta_state.tokens[*meta_state.cursor] else { todo!("Expected '(' after clobber") };
+                *meta_state.cursor += 1;
+
+                loop {
+                    match meta_state.tokens[*meta_state.cursor].clone() {
+                        Token::StringLiteral(register, position) => clobbers.push(StringLiteralNode::new((*meta_state.file_number, position), Rc::new(register))),
+                        Token::ArgumentSeparator(_) => {}
+                        Token::ArithmeticParenthesisClose(_) => break,
+                        other => todo!("Expected the name of a clobbered register, got: {:?}", other),
+                    }
+
+                    *meta_state.cursor += 1;
+                }
+
+                *meta_state.cursor += 1;
+            }
+
+            other => todo!("Expected a line of assembly, an operand or clobbered registers, got: {:?}", other),
+        }
+    }
+
+    let position = TokenPosition::spanning(open_position, meta_state.tokens[*meta_state.cursor - 1].get_position());
+
+    InlineAssemblyNode::new((*meta_state.file_number, position), lines, operands, clobbers)
+}
+
+/// Parses the kind of register an operand is placed in (e.g. the `(reg)` in `in(reg) a`).
+/// Only general purpose registers are supported.
+fn parse_operand_class(meta_state: &mut ParserMetaState) {
+    let Token::ArithmeticParenthesisOpen(_) = meta_state.tokens[*meta_state.cursor] else { todo!("Expected '(' after in or out") };
+    *meta_state.cursor += 1;
+
+    let Token::Identifier(class, _) = meta_state.tokens[*meta_state.cursor].clone() else { todo!("Expected the register class of an operand") };
+    if class != "reg" {
+        todo!("Throw an error: `{}` isn't a register class, use `reg`", class)
+    }
+    *meta_state.cursor += 1;
+
+    let Token::ArithmeticParenthesisClose(_) = meta_state.tokens[*meta_state.cursor] else { todo!("Expected ')' after the register class") };
+    *meta_state.cursor += 1;
+}
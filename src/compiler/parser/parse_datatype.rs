@@ -1,28 +1,106 @@
 use std::rc::Rc;
 use uuid::Uuid;
 use derive_new::new;
+use crate::compiler::data_types::array::build_array_type;
 use crate::compiler::data_types::object::ObjectType;
+use crate::compiler::data_types::reference::build_reference_type;
 use crate::compiler::line_map::{DisplayCodeInfo, DisplayCodeKind, LineMap, NotificationInfo};
 use crate::compiler::parser::function_meta::FunctionArgument;
 use crate::compiler::tokenization::token::Token;
+use crate::util::operator::Operation;
 
 /// Gets a datatype from the list of types and returns its uuid.
-pub fn parse_datatype(tokens: Rc<Vec<Token>>, cursor: &mut usize, types: Rc<Vec<ObjectType>>, _: &mut LineMap) -> Uuid {
-    if let Token::Identifier(type_name, _) = tokens[*cursor].clone() {
+///
+/// Array types (`[T; N]`) and reference types (`&T`) get added to
+/// the list of types if they haven't been used before. Unknown types
+/// are reported and result in `None`, all of their tokens are still consumed.
+pub fn parse_datatype(tokens: Rc<Vec<Token>>, cursor: &mut usize, types: &mut Rc<Vec<ObjectType>>, line_map: &mut LineMap) -> Option<Uuid> {
+    if let Token::ArrayParenthesisOpen(_) = tokens[*cursor].clone() {
         *cursor += 1;
 
-        return types.iter().find(|&x|x.name==type_name).unwrap().type_uuid
+        let element_uuid = parse_datatype(tokens.clone(), cursor, types, line_map);
+
+        let Token::HardNewline(_) = tokens[*cursor] else { todo!("Expected ';' in array type") };
+        *cursor += 1;
+
+        let Token::IntegerLiteral(length, _, _) = tokens[*cursor] else { todo!("Expected the length of the array type") };
+        *cursor += 1;
+
+        let Token::ArrayParenthesisClose(_) = tokens[*cursor] else { todo!("Expected ']' in array type") };
+        *cursor += 1;
+
+        // The whole array type is consumed even if the element type is unknown.
+        let element_uuid = element_uuid?;
+        let element_type = types.iter().find(|x| x.type_uuid == element_uuid).unwrap();
+        let array_type = build_array_type(element_type, length as usize);
+        let array_uuid = array_type.type_uuid;
+
+        if !types.iter().any(|x| x.type_uuid == array_uuid) {
+            Rc::make_mut(types).push(array_type);
+        }
+
+        return Some(array_uuid);
+    }
+
+    // `&&T` is tokenized as a logical and, but means `&(&T)`.
+    if let Token::Operator(operation @ (Operation::BitwiseAnd | Operation::And), _) = tokens[*cursor].clone() {
+        *cursor += 1;
+
+        let mut datatype = parse_datatype(tokens.clone(), cursor, types, line_map)?;
+
+        for _ in 0..if operation == Operation::And { 2 } else { 1 } {
+            let target = types.iter().find(|x| x.type_uuid == datatype).unwrap();
+            let reference_type = build_reference_type(target);
+            datatype = reference_type.type_uuid;
+
+            if !types.iter().any(|x| x.type_uuid == datatype) {
+                Rc::make_mut(types).push(reference_type);
+            }
+        }
+
+        return Some(datatype);
+    }
+
+    if let Token::Identifier(type_name, position) = tokens[*cursor].clone() {
+        *cursor += 1;
+
+        if let Some(type_) = types.iter().find(|&x|x.name==type_name) {
+            return Some(type_.type_uuid)
+        }
+
+        let error = line_map.display_code_for(
+            position,
+            vec![],
+            DisplayCodeKind::InitialError
+        );
+
+        let notification = NotificationInfo::new(
+            format!("Unknown Datatype '{}'", type_name),
+            "No datatype with this name exists".to_string(),
+            vec![error]
+        );
+
+        line_map.display_error(notification);
+
+        return None
     }
 
     todo!("Expected datatype")
 }
 
-/// Generates a parameter with external and (if applicable) internal name
-pub fn parse_parameter_descriptor(tokens: Rc<Vec<Token>>, cursor: &mut usize, types: Rc<Vec<ObjectType>>, line_map: &mut LineMap, parse_internal: bool) -> ParameterDescriptor {
+/// Generates a parameter with external and (if applicable) internal name.
+/// Parameters of unknown types are left out after being reported.
+pub fn parse_parameter_descriptor(tokens: Rc<Vec<Token>>, cursor: &mut usize, types: &mut Rc<Vec<ObjectType>>, line_map: &mut LineMap, parse_internal: bool) -> Option<ParameterDescriptor> {
     let name: Option<String>;
     let mut internal_name: Option<String> = None;
     let datatype: Option<Uuid>;
 
+    if let Token::Ellipsis(_) = tokens[*cursor].clone() {
+        *cursor += 1;
+
+        return Some(ParameterDescriptor::variadic());
+    }
+
     if let Token::Identifier(name_, _) = tokens[*cursor].clone() {
         *cursor += 1;
         name = Some(name_);
@@ -55,7 +133,7 @@ pub fn parse_parameter_descriptor(tokens: Rc<Vec<Token>>, cursor: &mut usize, ty
     *cursor += 1;
     match tokens[cursor_backup].clone() {
         Token::Colon(_) => {
-            datatype = Some(parse_datatype(tokens.clone(), cursor, types.clone(), line_map));
+            datatype = parse_datatype(tokens.clone(), cursor, types, line_map);
         }
 
         Token::Identifier(name_, _) => {
@@ -68,7 +146,7 @@ pub fn parse_parameter_descriptor(tokens: Rc<Vec<Token>>, cursor: &mut usize, ty
 
             *cursor += 1;
 
-            datatype = Some(parse_datatype(tokens.clone(), cursor, types.clone(), line_map));
+            datatype = parse_datatype(tokens.clone(), cursor, types, line_map);
         }
 
         _ => todo!("Not expected in parameter descriptor"),
@@ -76,7 +154,7 @@ pub fn parse_parameter_descriptor(tokens: Rc<Vec<Token>>, cursor: &mut usize, ty
 
     println!("Produced parameter descriptor; pointing to {:?}", tokens[*cursor]);
 
-    ParameterDescriptor::new(name, internal_name, datatype.unwrap())
+    Some(ParameterDescriptor::new(name, internal_name, datatype?))
 
 }
 
@@ -91,9 +169,18 @@ pub struct ParameterDescriptor {
     pub internal_name: Option<String>,
 
     pub datatype: Uuid,
+
+    /// Whether this is the `...` standing for the variadic arguments of a C function,
+    /// which has neither a name nor a type.
+    #[new(default)]
+    pub is_variadic: bool,
 }
 
 impl ParameterDescriptor {
+    pub fn variadic() -> ParameterDescriptor {
+        ParameterDescriptor { name: None, internal_name: None, datatype: Uuid::nil(), is_variadic: true }
+    }
+
     pub fn generate_function_argument(&self) -> FunctionArgument {
         FunctionArgument::new(self.name.clone(), Uuid::new_v4(), self.datatype)
     }
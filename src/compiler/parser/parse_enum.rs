@@ -0,0 +1,142 @@
+use std::rc::Rc;
+use crate::compiler::data_types::enumeration::{build_enum_type, EnumVariant};
+use crate::compiler::line_map::{DisplayCodeKind, NotificationInfo, TokenPosition};
+use crate::compiler::parser::parser_meta::ParserMetaState;
+use crate::compiler::parser::tree::node::{EnumDeclarationNode, EnumVariantNode};
+use crate::compiler::tokenization::token::Token;
+
+/// ### Parses the Declaration of an Enum
+///
+/// Expects the name followed by the comma separated variants (e.g. `Color { Red, Green = 5 }`).
+/// Variants without a value are one greater than the previous one, the first one defaults to 0.
+/// Just like structs, the type is made known right away.
+pub fn parse_enum_declaration(meta_state: &mut ParserMetaState) -> EnumDeclarationNode {
+    let Token::Identifier(name, name_position) = meta_state.tokens[*meta_state.cursor].clone() else { todo!("Expected the name of the enum") };
+    *meta_state.cursor += 1;
+
+    let Token::CodeBlockParenthesisOpen(_) = meta_state.tokens[*meta_state.cursor] else { todo!("Expected '{{' after the name of the enum") };
+    *meta_state.cursor += 1;
+
+    let mut variants: Vec<EnumVariant> = vec![];
+    let mut variant_positions: Vec<TokenPosition> = vec![];
+    let mut next_value: i128 = 0;
+
+    loop {
+        while let Token::SoftNewline(_) | Token::HardNewline(_) | Token::ArgumentSeparator(_) = meta_state.tokens[*meta_state.cursor] {
+            *meta_state.cursor += 1;
+        }
+
+        let Token::Identifier(variant_name, variant_position) = meta_state.tokens[*meta_state.cursor].clone() else {
+            let Token::CodeBlockParenthesisClose(_) = meta_state.tokens[*meta_state.cursor] else {
+                todo!("Unexpected token in enum: {:?}", meta_state.tokens[*meta_state.cursor])
+            };
+
+            *meta_state.cursor += 1;
+            break;
+        };
+        *meta_state.cursor += 1;
+
+        let mut value_position = variant_position;
+
+        if let Token::Assignment(_) = meta_state.tokens[*meta_state.cursor] {
+            let Token::IntegerLiteral(value, _, position) = meta_state.tokens[*meta_state.cursor + 1].clone() else { todo!("Expected an integer as the value of the variant") };
+            *meta_state.cursor += 2;
+
+            next_value = value;
+            value_position = position;
+        }
+
+        if !matches!(meta_state.tokens[*meta_state.cursor], Token::SoftNewline(_) | Token::HardNewline(_) | Token::ArgumentSeparator(_) | Token::CodeBlockParenthesisClose(_)) {
+            todo!("Expected ',' after the variant")
+        }
+
+        let value = next_value;
+        next_value += 1;
+
+        let Ok(value) = u32::try_from(value) else {
+            let notification = NotificationInfo::new(
+                "Enum Value Out Of Range".to_string(),
+                format!("The value of `{}::{}` has to be between 0 and {}, but it's {}", name, variant_name, u32::MAX, value),
+                vec![
+                    meta_state.line_map.display_code_for(
+                        value_position,
+                        vec![],
+                        DisplayCodeKind::InitialError
+                    ),
+                ],
+            );
+
+            meta_state.line_map.display_error(notification);
+            continue;
+        };
+
+        let duplicate = variants.iter().position(|x| x.name == variant_name)
+            .map(|x| ("Duplicate Variant", format!("The enum `{}` declares the variant `{}` more than once", name, variant_name), variant_position, x))
+            .or_else(|| variants.iter().position(|x| x.value == value)
+                .map(|x| ("Duplicate Enum Value", format!("`{}::{}` and `{}::{}` both have the value {}", name, variants[x].name, name, variant_name, value), value_position, x)));
+
+        if let Some((title, message, position, first_index)) = duplicate {
+            let notification = NotificationInfo::new(
+                title.to_string(),
+                message,
+                vec![
+                    meta_state.line_map.display_code_for(
+                        position,
+                        vec![],
+                        DisplayCodeKind::InitialError
+                    ),
+                    meta_state.line_map.display_code_for(
+                        variant_positions[first_index],
+                        vec!["*note:* first declared here".to_string()],
+                        DisplayCodeKind::AdditionalInfo
+                    ),
+                ],
+            );
+
+            meta_state.line_map.display_error(notification);
+            continue;
+        }
+
+        variants.push(EnumVariant::new(variant_name, value));
+        variant_positions.push(variant_position);
+    }
+
+    let enum_type = build_enum_type(name.clone(), &variants);
+
+    if meta_state.datatypes.iter().any(|x| x.name == name) {
+        let notification = NotificationInfo::new(
+            format!("Datatype '{}' Already Exists", name),
+            "Every datatype needs a unique name".to_string(),
+            vec![
+                meta_state.line_map.display_code_for(
+                    name_position,
+                    vec![],
+                    DisplayCodeKind::InitialError
+                ),
+            ],
+        );
+
+        meta_state.line_map.display_error(notification);
+    } else {
+        Rc::make_mut(meta_state.datatypes).push(enum_type);
+    }
+
+    EnumDeclarationNode::new((*meta_state.file_number, name_position))
+}
+
+/// ### Parses a Variant of an Enum
+///
+/// The cursor is expected to point to the `::` following the name of the enum
+/// (e.g. `Color` in `Color::Red`). Whether the variant exists is checked along
+/// with the types.
+pub fn parse_enum_variant(meta_state: &mut ParserMetaState, name: String, name_position: TokenPosition) -> EnumVariantNode {
+    let datatype = meta_state.datatypes.iter().find(|x| x.name == name).unwrap().type_uuid;
+    *meta_state.cursor += 1;
+
+    let Some(Token::Identifier(variant, variant_position)) = meta_state.tokens.get(*meta_state.cursor).cloned() else { todo!("Expected the name of a variant after '::'") };
+    *meta_state.cursor += 1;
+
+    let position = TokenPosition::spanning(name_position, variant_position);
+
+    EnumVariantNode::new(datatype, variant, variant_position, (*meta_state.file_number, position))
+}
@@ -1,10 +1,16 @@
 use std::rc::Rc;
+use uuid::Uuid;
 use crate::compiler::line_map::TokenPosition;
 use crate::compiler::parser::parse::ExpressionKind;
 use crate::compiler::parser::parse_arg_array::parse_arg_array;
 use crate::compiler::parser::parse_arithmetic_expression::parse_arithmetic_expression;
-use crate::compiler::parser::parse_datatype::{parse_parameter_descriptor, ParameterDescriptor};
+use crate::compiler::parser::parse_asm::parse_asm_block;
+use crate::compiler::parser::parse_datatype::{parse_datatype, parse_parameter_descriptor, ParameterDescriptor};
 use crate::compiler::parser::parse_line::parse_line;
+use crate::compiler::parser::parse_enum::parse_enum_declaration;
+use crate::compiler::parser::parse_match::parse_match_arms;
+use crate::compiler::parser::parse_path::parse_path;
+use crate::compiler::parser::parse_struct::parse_struct_declaration;
 use crate::compiler::parser::parser_meta::ParserMetaState;
 use crate::compiler::parser::tree::node::*;
 use crate::compiler::tokenization::token::Token;
@@ -13,7 +19,7 @@ use crate::compiler::tokenization::token::Token;
 pub fn parse_expression_kind(meta_state: &mut ParserMetaState, kind: ExpressionKind, required: bool) -> Vec<Rc<dyn Node>> {
 
     let mut arguments: Vec<Rc<dyn Node>> = Vec::new();
-    let initial_block_depth = *meta_state.code_block_depth;
+    let initial_block_depth = meta_state.block_stack.code_block_depth;
     match kind {
         ExpressionKind::Value => {
             // Parse an arithmetic expression
@@ -24,6 +30,21 @@ pub fn parse_expression_kind(meta_state: &mut ParserMetaState, kind: ExpressionK
                 todo!()
             }
         }
+        ExpressionKind::Values => {
+            while !meta_state.tokens[*meta_state.cursor].is_line_delimiting() {
+                let Some(result) = parse_arithmetic_expression(meta_state, 0, true) else { todo!("Expected a value") };
+                arguments.push(result);
+
+                // The value either consumed the `,` (and the newlines following it), or it was the last one.
+                let previous_token = meta_state.tokens[..*meta_state.cursor].iter()
+                    .rev()
+                    .find(|x| !matches!(x, Token::HardNewline(_) | Token::SoftNewline(_)));
+
+                if !matches!(previous_token, Some(Token::ArgumentSeparator(_))) {
+                    break;
+                }
+            }
+        }
         ExpressionKind::Assignment => {
             match meta_state.tokens[*meta_state.cursor].clone() {
                 Token::Assignment(pos) => arguments.push(Rc::new(AssignmentSymbolNode::new((*meta_state.file_number, pos)))),
@@ -51,29 +72,47 @@ pub fn parse_expression_kind(meta_state: &mut ParserMetaState, kind: ExpressionK
             }
             *meta_state.cursor += 1;
         }
+        ExpressionKind::DeclaredNames => {
+            let Token::ArithmeticParenthesisOpen(start_position) = meta_state.tokens[*meta_state.cursor].clone() else {
+                return parse_expression_kind(meta_state, ExpressionKind::Identifier(None), required);
+            };
+
+            let file_number = *meta_state.file_number;
+            let names = parse_arg_array::<IdentifierNode>(meta_state, &move |tokens, cursor, _, _| {
+                let Token::Identifier(name, position) = tokens[*cursor].clone() else { todo!("Expected the name of a variable") };
+                *cursor += 1;
+
+                IdentifierNode::new(name, None, (file_number, position))
+            });
+
+            let position = TokenPosition::spanning(start_position, meta_state.tokens[*meta_state.cursor - 1].get_position());
+            arguments.push(Rc::new(ArgumentsNode::new((file_number, position), Rc::new(names))));
+        }
         ExpressionKind::CodeBlock => {
             // Locate the opening
             if !matches!(meta_state.tokens[*meta_state.cursor].clone(), Token::CodeBlockParenthesisOpen(_)) {
+                if !required { return arguments; }
+
                 todo!("expected bracket to start code block")
             }
 
-            let initial_block_idx = *meta_state.current_block_idx;
+            let initial_block_idx = meta_state.block_stack.current_block_idx;
             *meta_state.cursor += 1;
-            *meta_state.code_block_depth += 1;
-            *meta_state.current_block_idx = meta_state.blocks.len();
+            meta_state.block_stack.code_block_depth += 1;
+            meta_state.block_stack.current_block_idx = meta_state.block_stack.blocks.len();
 
 
             // Create a new code block
             let block = CodeBlockNode::new((*meta_state.file_number, TokenPosition::new(0, 0)), None, vec![]);
-            meta_state.blocks.push(block);
+            meta_state.block_stack.blocks.push(block);
 
-            while *meta_state.code_block_depth > initial_block_depth {
+            while meta_state.block_stack.code_block_depth > initial_block_depth {
                 parse_line(meta_state);
             }
 
-            let block = meta_state.blocks.pop().unwrap();
+            let block = meta_state.block_stack.blocks.pop().unwrap();
 
-            *meta_state.current_block_idx = initial_block_idx;
+            meta_state.block_stack.current_block_idx = initial_block_idx;
             arguments.push(Rc::new(block));
         }
         ExpressionKind::Array(inner) => {
@@ -82,9 +121,12 @@ pub fn parse_expression_kind(meta_state: &mut ParserMetaState, kind: ExpressionK
                     ArgumentsNode::<ParameterDescriptor>::new(
                         (0, TokenPosition::new(0, 0)),
                         Rc::new(
-                            parse_arg_array::<ParameterDescriptor>(meta_state,
-                                                                   &|tokens, cursor, line_map, datatypes|
-                                                                       parse_parameter_descriptor(tokens, cursor, datatypes, line_map, true))
+                            parse_arg_array::<Option<ParameterDescriptor>>(meta_state,
+                                                                           &|tokens, cursor, line_map, datatypes|
+                                                                               parse_parameter_descriptor(tokens, cursor, datatypes, line_map, true))
+                                .into_iter()
+                                .flatten()
+                                .collect()
                         )
                     )
                 ));
@@ -95,16 +137,67 @@ pub fn parse_expression_kind(meta_state: &mut ParserMetaState, kind: ExpressionK
 
         ExpressionKind::StringLiteral => {
             if let Token::StringLiteral(string, pos) = meta_state.tokens[*meta_state.cursor].clone() {
-                arguments.push(Rc::new(StringLiteralNode::new((*meta_state.file_number, pos), Rc::new(string))))
-            } else {
+                arguments.push(Rc::new(StringLiteralNode::new((*meta_state.file_number, pos), Rc::new(string))));
+                *meta_state.cursor += 1;
+            } else if required {
                 todo!("Expected string literal")
             }
+        }
+        ExpressionKind::TypeAnnotation => {
+            if let Token::Colon(colon_position) = meta_state.tokens[*meta_state.cursor].clone() {
+                *meta_state.cursor += 1;
 
-            *meta_state.cursor += 1;
+                let datatype = parse_datatype(meta_state.tokens.clone(), meta_state.cursor, meta_state.datatypes, meta_state.line_map);
+                let position = TokenPosition::spanning(colon_position, meta_state.tokens[*meta_state.cursor - 1].get_position());
+
+                // Unknown types were reported already, the value is treated as if it had no annotation.
+                if let Some(datatype) = datatype {
+                    arguments.push(Rc::new(TypeAnnotationNode::new(datatype, (*meta_state.file_number, position))));
+                }
+            } else if required {
+                todo!("Expected type annotation")
+            }
+        }
+        ExpressionKind::ReturnType => {
+            if let Token::Arrow(arrow_position) = meta_state.tokens[*meta_state.cursor].clone() {
+                *meta_state.cursor += 1;
+
+                // Several types are listed in parentheses, each one is annotated by the entire list.
+                let datatypes = if let Token::ArithmeticParenthesisOpen(_) = meta_state.tokens[*meta_state.cursor] {
+                    parse_arg_array::<Option<Uuid>>(meta_state, &|tokens, cursor, line_map, datatypes| parse_datatype(tokens, cursor, datatypes, line_map))
+                } else {
+                    vec![parse_datatype(meta_state.tokens.clone(), meta_state.cursor, meta_state.datatypes, meta_state.line_map)]
+                };
+
+                let position = TokenPosition::spanning(arrow_position, meta_state.tokens[*meta_state.cursor - 1].get_position());
+
+                for datatype in datatypes.into_iter().flatten() {
+                    arguments.push(Rc::new(TypeAnnotationNode::new(datatype, (*meta_state.file_number, position))));
+                }
+            } else if required {
+                todo!("Expected return type")
+            }
         }
         ExpressionKind::Parameter => {
             todo!("Parameters can only be parsed in arrays now, should be easy to implement tho.")
         }
+        ExpressionKind::StructDeclaration => {
+            arguments.push(Rc::new(parse_struct_declaration(meta_state)));
+        }
+        ExpressionKind::EnumDeclaration => {
+            arguments.push(Rc::new(parse_enum_declaration(meta_state)));
+        }
+        ExpressionKind::MatchArms => {
+            arguments.push(Rc::new(parse_match_arms(meta_state)));
+        }
+        ExpressionKind::ImportPath => {
+            let Some(path) = parse_path(&meta_state.tokens, meta_state.cursor, true) else { todo!("Expected the path of a module") };
+
+            arguments.push(Rc::new(ImportNode::new((*meta_state.file_number, path.position))));
+        }
+        ExpressionKind::AsmBlock => {
+            arguments.push(Rc::new(parse_asm_block(meta_state)));
+        }
     }
 
 
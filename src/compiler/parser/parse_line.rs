@@ -1,11 +1,14 @@
+use std::ops::Deref;
 use std::rc::Rc;
 use crate::compiler::parser::modifier::Modifier;
 use crate::compiler::parser::parse_arithmetic_expression::parse_arithmetic_expression;
 use crate::compiler::parser::parse_expression_kind::parse_multiple_expression_kinds;
 use crate::compiler::parser::parser_meta::ParserMetaState;
 use crate::compiler::parser::statement::Statement;
-use crate::compiler::parser::tree::node::{AssignmentNode, IdentifierNode};
+use crate::compiler::line_map::TokenPosition;
+use crate::compiler::parser::tree::node::{ArithmeticNode, AssignmentNode, IdentifierNode, PlaceAssignmentNode, ValueNode};
 use crate::compiler::tokenization::token::Token;
+use crate::util::operator::Operation;
 
 /// Generates nodes for one logical line or statement.
 ///
@@ -20,7 +23,7 @@ use crate::compiler::tokenization::token::Token;
 /// parsed while this function gets invoked only once.
 pub fn parse_line(meta_state: &mut ParserMetaState) {
     let line_start = *meta_state.cursor;
-    let initial_block_depth = *meta_state.code_block_depth;
+    let initial_block_depth = meta_state.block_stack.code_block_depth;
 
     // Parse the modifiers
     let mut modifiers: Vec<Modifier> = vec![];
@@ -50,10 +53,11 @@ pub fn parse_line(meta_state: &mut ParserMetaState) {
                     );
 
 
-                    let statement_node = statement.generate_node(arguments, &mut modifiers);
+                    let position = statement_position(meta_state, first_token.get_position());
+                    let statement_node = statement.generate_node(arguments, &mut modifiers, position);
                     if let Some(statement_node) = statement_node {
-                        let _position = *meta_state.current_block_idx;
-                        meta_state.blocks[*meta_state.current_block_idx].push_code(statement_node);
+                        let _position = meta_state.block_stack.current_block_idx;
+                        meta_state.block_stack.blocks[meta_state.block_stack.current_block_idx].push_code(statement_node);
                     } else {
                         panic!("Statement didn't generate node")
                     }
@@ -65,29 +69,51 @@ pub fn parse_line(meta_state: &mut ParserMetaState) {
             let id_node = IdentifierNode::new(name.clone(), None, (*meta_state.file_number, pos.clone()));
 
             *meta_state.cursor = line_start;
-            match meta_state.tokens[*meta_state.cursor + 1].clone() {
-                Token::Assignment(_) => {},
+
+            // Elements of arrays and fields of structs can be assigned too (e.g. `a[2] = 5`)
+            if let Token::ArrayParenthesisOpen(_) | Token::Dot(_) = meta_state.tokens[*meta_state.cursor + 1] {
+                parse_place_assignment(meta_state);
+                return;
+            }
+
+            let operation = match meta_state.tokens[*meta_state.cursor + 1].clone() {
+                Token::Assignment(_) => None,
+                Token::CompoundAssignment(operation, _) => Some(operation),
                 _ => {
                     let value = parse_arithmetic_expression(meta_state, 0, false).unwrap();
 
-                    meta_state.blocks[*meta_state.current_block_idx].push_code(value);
+                    meta_state.block_stack.blocks[meta_state.block_stack.current_block_idx].push_code(value);
 
                     return;
                 }
-            }
+            };
 
             *meta_state.cursor = line_start + 2;
-            let value = parse_arithmetic_expression(meta_state, 0, false).unwrap();
+            let mut value = parse_arithmetic_expression(meta_state, 0, false).unwrap();
+            let position = TokenPosition::spanning(pos, value.get_position().1);
+
+            // `a += b` is the same as `a = a + b`
+            if let Some(operation) = operation {
+                let left_side = Rc::new(ValueNode::Identifier(id_node.clone()));
+                value = Rc::new(ValueNode::Arithmetic(ArithmeticNode::new(operation, left_side, value, (*meta_state.file_number, position))));
+            }
 
-            let assignment_node = AssignmentNode::new(Rc::new(id_node), value, (*meta_state.file_number, pos));
+            let assignment_node = AssignmentNode::new(Rc::new(id_node), value, (*meta_state.file_number, position));
 
-            meta_state.blocks[*meta_state.current_block_idx].push_code(Rc::new(assignment_node));
+            meta_state.block_stack.blocks[meta_state.block_stack.current_block_idx].push_code(Rc::new(assignment_node));
+        }
+
+        // Values behind references can be assigned (e.g. `*a = 5`)
+        Token::Operator(Operation::Multiplication, _) => {
+            *meta_state.cursor = line_start;
+            parse_place_assignment(meta_state);
+            return;
         }
 
         Token::CodeBlockParenthesisClose(_) => {
-            *meta_state.code_block_depth -= 1;
+            meta_state.block_stack.code_block_depth -= 1;
 
-            if *meta_state.code_block_depth < initial_block_depth {
+            if meta_state.block_stack.code_block_depth < initial_block_depth {
                 return;
             }
         }
@@ -103,12 +129,58 @@ pub fn parse_line(meta_state: &mut ParserMetaState) {
 
         match meta_state.tokens[*meta_state.cursor] {
             Token::SoftNewline(_) | Token::HardNewline(_) => {*meta_state.cursor += 1}
-            Token::CodeBlockParenthesisClose(_) => { *meta_state.code_block_depth -= 1; *meta_state.cursor += 1 }
+            Token::CodeBlockParenthesisClose(_) => { meta_state.block_stack.code_block_depth -= 1; *meta_state.cursor += 1 }
             _ => {return;}
         }
 
-        if *meta_state.code_block_depth < initial_block_depth {
+        if meta_state.block_stack.code_block_depth < initial_block_depth {
+            return;
+        }
+    }
+}
+
+
+/// Parses a line starting with an indexed array, a field access or a dereference, which is either
+/// an assignment to the value in memory or a plain expression.
+fn parse_place_assignment(meta_state: &mut ParserMetaState) {
+    let left_side = parse_arithmetic_expression(meta_state, 0, true).unwrap();
+
+    let operation = match meta_state.tokens.get(*meta_state.cursor) {
+        Some(Token::Assignment(_)) => None,
+        Some(Token::CompoundAssignment(operation, _)) => Some(operation.clone()),
+        _ => {
+            meta_state.block_stack.blocks[meta_state.block_stack.current_block_idx].push_code(left_side);
             return;
         }
+    };
+
+    let Ok(value_node) = left_side.clone().downcast_rc::<ValueNode>() else { todo!("Expected a value in memory to assign to") };
+    let (ValueNode::Index(_) | ValueNode::FieldAccess(_) | ValueNode::Dereference(_)) = value_node.deref() else { todo!("Expected a value in memory to assign to") };
+
+    *meta_state.cursor += 1;
+    let mut value = parse_arithmetic_expression(meta_state, 0, false).unwrap();
+    let position = TokenPosition::spanning(left_side.get_position().1, value.get_position().1);
+
+    // `a[0] += b` is the same as `a[0] = a[0] + b`
+    if let Some(operation) = operation {
+        value = Rc::new(ValueNode::Arithmetic(ArithmeticNode::new(operation, left_side.clone(), value, (*meta_state.file_number, position))));
     }
-}
\ No newline at end of file
+
+    let assignment_node = PlaceAssignmentNode::new(left_side, value, (*meta_state.file_number, position));
+
+    meta_state.block_stack.blocks[meta_state.block_stack.current_block_idx].push_code(Rc::new(assignment_node));
+}
+
+
+/// Gets the position of the statement that started with the given token and
+/// ends right before the cursor. Statements spanning multiple lines (e.g. ones
+/// containing a code block) are only represented by their first token.
+fn statement_position(meta_state: &ParserMetaState, first_token_position: TokenPosition) -> (usize, TokenPosition) {
+    let last_token_position = meta_state.tokens[*meta_state.cursor - 1].get_position();
+
+    if last_token_position.line != first_token_position.line {
+        return (*meta_state.file_number, first_token_position);
+    }
+
+    (*meta_state.file_number, TokenPosition::spanning(first_token_position, last_token_position))
+}
@@ -0,0 +1,64 @@
+use std::rc::Rc;
+use crate::compiler::parser::parse::ExpressionKind;
+use crate::compiler::parser::parse_arithmetic_expression::{parse_arithmetic_expression, skip_newlines};
+use crate::compiler::parser::parse_expression_kind::parse_expression_kind;
+use crate::compiler::parser::parser_meta::ParserMetaState;
+use crate::compiler::parser::tree::node::{ArgumentsNode, CodeBlockNode, MatchArm, Node};
+use crate::compiler::line_map::TokenPosition;
+use crate::compiler::tokenization::token::Token;
+
+/// ### Parses the Arms of a Match Statement
+///
+/// Expects the `{` following the matched value. Each arm consists of comma separated
+/// patterns, a `=>` and a code block (e.g. `1, 2 => { ... }`). Arms may be separated
+/// by commas. Parsing stops right after the closing `}`.
+pub fn parse_match_arms(meta_state: &mut ParserMetaState) -> ArgumentsNode<MatchArm> {
+    let Token::CodeBlockParenthesisOpen(open_position) = meta_state.tokens[*meta_state.cursor] else { todo!("Expected '{{' after the matched value") };
+    *meta_state.cursor += 1;
+
+    let mut arms: Vec<MatchArm> = vec![];
+
+    loop {
+        while let Some(Token::SoftNewline(_) | Token::HardNewline(_) | Token::ArgumentSeparator(_)) = meta_state.tokens.get(*meta_state.cursor) {
+            *meta_state.cursor += 1;
+        }
+
+        if let Some(Token::CodeBlockParenthesisClose(_)) = meta_state.tokens.get(*meta_state.cursor) {
+            *meta_state.cursor += 1;
+            break;
+        }
+
+        let mut patterns: Vec<Rc<dyn Node>> = vec![];
+
+        loop {
+            let Some(pattern) = parse_arithmetic_expression(meta_state, 0, true) else { todo!("Expected a pattern") };
+            patterns.push(pattern);
+
+            // The pattern either consumed the `,` (and the newlines following it),
+            // or it stopped right before the `=>`.
+            let previous_token = meta_state.tokens[..*meta_state.cursor].iter()
+                .rev()
+                .find(|x| !matches!(x, Token::HardNewline(_) | Token::SoftNewline(_)));
+
+            if !matches!(previous_token, Some(Token::ArgumentSeparator(_))) {
+                break;
+            }
+        }
+
+        skip_newlines(meta_state);
+
+        let Some(Token::FatArrow(_)) = meta_state.tokens.get(*meta_state.cursor) else {
+            todo!("Expected '=>' after the patterns, got: {:?}", meta_state.tokens.get(*meta_state.cursor))
+        };
+        *meta_state.cursor += 1;
+
+        let block = parse_expression_kind(meta_state, ExpressionKind::CodeBlock, true).remove(0);
+        let block = block.downcast_rc::<CodeBlockNode>().unwrap();
+
+        arms.push(MatchArm::new(patterns, block));
+    }
+
+    let position = TokenPosition::spanning(open_position, meta_state.tokens[*meta_state.cursor - 1].get_position());
+
+    ArgumentsNode::new((*meta_state.file_number, position), Rc::new(arms))
+}
@@ -0,0 +1,54 @@
+use derive_new::new;
+use crate::compiler::line_map::TokenPosition;
+use crate::compiler::tokenization::token::Token;
+use crate::util::operator::Operation;
+
+/// Names separated by `::` (e.g. `geometry::shapes`), referring to a module or something in it.
+#[derive(Clone, Debug, new)]
+pub struct Path {
+    pub segments: Vec<String>,
+
+    /// Whether the path ends with `*` (e.g. `std::*`), which is only allowed in imports.
+    pub is_glob: bool,
+
+    pub position: TokenPosition,
+}
+
+impl Path {
+    /// The path as written in the code, but without a trailing `*`.
+    pub fn get_name(&self) -> String {
+        self.segments.join("::")
+    }
+}
+
+/// ### Parses a Path
+///
+/// The cursor is expected to point to the first name. Parsing stops at the first token
+/// that doesn't continue the path, so the cursor points right after it afterward. With
+/// `allow_glob`, the last part may be `*` instead of a name.
+pub fn parse_path(tokens: &[Token], cursor: &mut usize, allow_glob: bool) -> Option<Path> {
+    let Some(Token::Identifier(first, start_position)) = tokens.get(*cursor).cloned() else { return None };
+    *cursor += 1;
+
+    let mut path = Path::new(vec![first], false, start_position);
+
+    while let Some(Token::PathSeparator(_)) = tokens.get(*cursor) {
+        match tokens.get(*cursor + 1).cloned() {
+            Some(Token::Identifier(name, position)) => {
+                path.segments.push(name);
+                path.position = TokenPosition::spanning(start_position, position);
+            }
+            Some(Token::Operator(Operation::Multiplication, position)) if allow_glob => {
+                path.is_glob = true;
+                path.position = TokenPosition::spanning(start_position, position);
+                *cursor += 2;
+                break;
+            }
+            _ => return None,
+        }
+
+        *cursor += 2;
+    }
+
+    Some(path)
+}
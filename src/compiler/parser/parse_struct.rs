@@ -0,0 +1,152 @@
+use std::rc::Rc;
+use crate::compiler::data_types::object::ObjectType;
+use crate::compiler::data_types::structure::build_struct_type;
+use crate::compiler::line_map::{DisplayCodeKind, NotificationInfo, TokenPosition};
+use crate::compiler::parser::parse_arithmetic_expression::{parse_arithmetic_expression, skip_newlines};
+use crate::compiler::parser::parse_datatype::parse_datatype;
+use crate::compiler::parser::parser_meta::ParserMetaState;
+use crate::compiler::parser::tree::node::{Node, StructDeclarationNode, StructLiteralNode};
+use crate::compiler::tokenization::token::Token;
+
+/// ### Parses the Declaration of a Struct
+///
+/// Expects the name followed by the comma separated fields (e.g. `Point { x: i32, y: i32 }`).
+/// The type is made known right away, so all following lines can use it.
+pub fn parse_struct_declaration(meta_state: &mut ParserMetaState) -> StructDeclarationNode {
+    let Token::Identifier(name, name_position) = meta_state.tokens[*meta_state.cursor].clone() else { todo!("Expected the name of the struct") };
+    *meta_state.cursor += 1;
+
+    let Token::CodeBlockParenthesisOpen(_) = meta_state.tokens[*meta_state.cursor] else { todo!("Expected '{{' after the name of the struct") };
+    *meta_state.cursor += 1;
+
+    let mut fields: Vec<(String, ObjectType)> = vec![];
+    let mut field_positions: Vec<TokenPosition> = vec![];
+
+    loop {
+        while let Token::SoftNewline(_) | Token::HardNewline(_) | Token::ArgumentSeparator(_) = meta_state.tokens[*meta_state.cursor] {
+            *meta_state.cursor += 1;
+        }
+
+        let Token::Identifier(field_name, field_position) = meta_state.tokens[*meta_state.cursor].clone() else {
+            let Token::CodeBlockParenthesisClose(_) = meta_state.tokens[*meta_state.cursor] else {
+                todo!("Unexpected token in struct: {:?}", meta_state.tokens[*meta_state.cursor])
+            };
+
+            *meta_state.cursor += 1;
+            break;
+        };
+        *meta_state.cursor += 1;
+
+        let Token::Colon(_) = meta_state.tokens[*meta_state.cursor] else { todo!("Expected ':' after the name of the field") };
+        *meta_state.cursor += 1;
+
+        let datatype = parse_datatype(meta_state.tokens.clone(), meta_state.cursor, meta_state.datatypes, meta_state.line_map);
+
+        if !matches!(meta_state.tokens[*meta_state.cursor], Token::SoftNewline(_) | Token::HardNewline(_) | Token::ArgumentSeparator(_) | Token::CodeBlockParenthesisClose(_)) {
+            todo!("Expected ',' after the field")
+        }
+
+        // Fields of unknown types were reported already and are left out.
+        let Some(datatype) = datatype else { continue };
+        let field_type = meta_state.datatypes.iter().find(|x| x.type_uuid == datatype).unwrap().clone();
+
+        if let Some(first_index) = fields.iter().position(|x| x.0 == field_name) {
+            let notification = NotificationInfo::new(
+                "Duplicate Field".to_string(),
+                format!("The struct `{}` declares the field `{}` more than once", name, field_name),
+                vec![
+                    meta_state.line_map.display_code_for(
+                        field_position,
+                        vec![],
+                        DisplayCodeKind::InitialError
+                    ),
+                    meta_state.line_map.display_code_for(
+                        field_positions[first_index],
+                        vec!["*note:* first declared here".to_string()],
+                        DisplayCodeKind::AdditionalInfo
+                    ),
+                ],
+            );
+
+            meta_state.line_map.display_error(notification);
+            continue;
+        }
+
+        fields.push((field_name, field_type));
+        field_positions.push(field_position);
+    }
+
+    let struct_type = build_struct_type(name.clone(), &fields, meta_state.datatypes);
+
+    if meta_state.datatypes.iter().any(|x| x.name == name) {
+        let notification = NotificationInfo::new(
+            format!("Datatype '{}' Already Exists", name),
+            "Every datatype needs a unique name".to_string(),
+            vec![
+                meta_state.line_map.display_code_for(
+                    name_position,
+                    vec![],
+                    DisplayCodeKind::InitialError
+                ),
+            ],
+        );
+
+        meta_state.line_map.display_error(notification);
+    } else {
+        Rc::make_mut(meta_state.datatypes).push(struct_type);
+    }
+
+    StructDeclarationNode::new((*meta_state.file_number, name_position))
+}
+
+/// ### Parses a Struct Literal
+///
+/// The cursor is expected to point to the `{` following the name of the struct (e.g. `Point`
+/// in `Point { x: 1, y: 2 }`). Just like in declarations, the fields are separated by commas.
+/// Parsing stops right after the closing `}`.
+pub fn parse_struct_literal(meta_state: &mut ParserMetaState, name: String, name_position: TokenPosition) -> StructLiteralNode {
+    let datatype = meta_state.datatypes.iter().find(|x| x.name == name).unwrap().type_uuid;
+    *meta_state.cursor += 1;
+
+    let mut fields: Vec<(String, Rc<dyn Node>, TokenPosition)> = vec![];
+
+    loop {
+        skip_newlines(meta_state);
+
+        // Empty structs and trailing commas
+        if let Some(Token::CodeBlockParenthesisClose(_)) = meta_state.tokens.get(*meta_state.cursor) {
+            *meta_state.cursor += 1;
+            break;
+        }
+
+        let Some(Token::Identifier(field_name, field_position)) = meta_state.tokens.get(*meta_state.cursor).cloned() else { todo!("Expected the name of a field") };
+        *meta_state.cursor += 1;
+
+        let Some(Token::Colon(_)) = meta_state.tokens.get(*meta_state.cursor) else { todo!("Expected ':' after the name of the field") };
+        *meta_state.cursor += 1;
+
+        let Some(value) = parse_arithmetic_expression(meta_state, 0, true) else { todo!("Expected a value for the field") };
+        fields.push((field_name, value, field_position));
+
+        // The value either consumed the `,` (and the newlines following it),
+        // or it stopped right before a newline or the `}`.
+        let previous_token = meta_state.tokens[..*meta_state.cursor].iter()
+            .rev()
+            .find(|x| !matches!(x, Token::HardNewline(_) | Token::SoftNewline(_)));
+
+        if let Some(Token::ArgumentSeparator(_)) = previous_token {
+            continue;
+        }
+
+        skip_newlines(meta_state);
+
+        match meta_state.tokens.get(*meta_state.cursor) {
+            Some(Token::CodeBlockParenthesisClose(_)) => { *meta_state.cursor += 1; break; }
+            token => todo!("Unexpected token in struct literal: {:?}", token),
+        }
+    }
+
+    let position = TokenPosition::spanning(name_position, meta_state.tokens[*meta_state.cursor - 1].get_position());
+
+    StructLiteralNode::new(datatype, fields, (*meta_state.file_number, position))
+}
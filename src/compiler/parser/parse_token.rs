@@ -1,12 +1,15 @@
 use std::rc::Rc;
 use crate::compiler::line_map::LineMap;
-use crate::compiler::parser::tree::node::{BoolLiteralNode, IdentifierNode, IntegerLiteralNode, LiteralValueNode, Node, ValueNode};
+use crate::compiler::parser::tree::node::{BoolLiteralNode, CharLiteralNode, IdentifierNode, IntegerLiteralNode, LiteralValueNode, Node, StringLiteralNode, ValueNode};
 use crate::compiler::tokenization::token::Token;
 
 pub fn parse_token(token: Token, line_number: usize, _line_map: LineMap) -> Option<Rc<dyn Node>>{
     match token {
         Token::UnspecifiedString(_, _) => {}
-        Token::StringLiteral(_, _) => {}
+        Token::StringLiteral(string, pos) => {
+            let string_literal: StringLiteralNode = StringLiteralNode::new((line_number, pos), Rc::new(string));
+            return Some(Rc::new(ValueNode::Literal(LiteralValueNode::String(string_literal))));
+        }
         Token::IntegerLiteral(int, kind, pos) => {
             let integer_literal: IntegerLiteralNode = IntegerLiteralNode::new(int, kind, (line_number, pos));
             return Some(Rc::new(ValueNode::Literal(LiteralValueNode::Integer(integer_literal))));
@@ -15,6 +18,10 @@ pub fn parse_token(token: Token, line_number: usize, _line_map: LineMap) -> Opti
             let boolean_literal: BoolLiteralNode = BoolLiteralNode::new(boolean, (line_number, pos));
             return Some(Rc::new(ValueNode::Literal(LiteralValueNode::Boolean(boolean_literal))));
         }
+        Token::CharLiteral(character, pos) => {
+            let character_literal: CharLiteralNode = CharLiteralNode::new(character, (line_number, pos));
+            return Some(Rc::new(ValueNode::Literal(LiteralValueNode::Char(character_literal))));
+        }
         Token::KeywordType(_, _) => {}
         Token::Identifier(identifier, pos) => {
             let identifier_node: IdentifierNode = IdentifierNode::new(identifier, None, (line_number, pos));
@@ -22,14 +29,22 @@ pub fn parse_token(token: Token, line_number: usize, _line_map: LineMap) -> Opti
         }
         Token::Operator(_, _) => {}
         Token::Assignment(_) => {}
+        Token::CompoundAssignment(_, _) => {}
         Token::ArithmeticParenthesisOpen(_) => {}
         Token::ArithmeticParenthesisClose(_) => {}
+        Token::ArrayParenthesisOpen(_) => {}
+        Token::ArrayParenthesisClose(_) => {}
         Token::ArgumentSeparator(_) => {}
         Token::SoftNewline(_) => {}
         Token::HardNewline(_) => {}
         Token::CodeBlockParenthesisClose(_) => {}
         Token::CodeBlockParenthesisOpen(_) => {}
         Token::Colon(_) => {}
+        Token::Dot(_) => {}
+        Token::PathSeparator(_) => {}
+        Token::FatArrow(_) => {}
+        Token::Arrow(_) => {}
+        Token::Ellipsis(_) => {}
     }
 
     None
@@ -33,17 +33,29 @@ pub struct ParserMetaState<'a> {
     /// tokens). This is helpful for producing debug information.
     pub file_number: &'a mut usize,
 
+    /// The code blocks being generated.
+    pub block_stack: &'a mut BlockStack,
+
+    /// A list of all the available datatypes.
+    /// **Note:** I might need to update this to a `Vec<Rc<ObjectType>>`
+    pub datatypes: &'a mut Rc<Vec<ObjectType>>,
+}
+
+/// ### The Code Blocks Being Generated
+///
+/// Blocks get pushed when they're opened and popped once they're
+/// closed, statements are written to the [current one](Self::current_block_idx).
+#[derive(Debug, new)]
+pub struct BlockStack {
     /// The generated code blocks are mostly used while the blocks
     /// get generated only.
-    pub blocks: &'a mut Vec<CodeBlockNode>,
+    pub blocks: Vec<CodeBlockNode>,
 
     /// The index of the [block](Self::blocks) that should be written to.
-    pub current_block_idx: &'a mut usize,
+    #[new(default)]
+    pub current_block_idx: usize,
 
     /// I can't fucking remember ever using or writing this, so it's whatever.
-    pub code_block_depth: &'a mut u32,
-
-    /// A list of all the available datatypes.
-    /// **Note:** I might need to update this to a `Vec<Rc<ObjectType>>`
-    pub datatypes: &'a mut Rc<Vec<ObjectType>>,
+    #[new(default)]
+    pub code_block_depth: u32,
 }
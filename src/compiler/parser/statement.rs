@@ -1,5 +1,6 @@
 use std::fmt::Debug;
 use std::rc::Rc;
+use crate::compiler::line_map::TokenPosition;
 use crate::compiler::parser::modifier::Modifier;
 use crate::compiler::parser::parse::ExpressionKind;
 use crate::compiler::parser::tree::node::Node;
@@ -34,6 +35,7 @@ pub trait Statement: Debug {
     /// **Note:** The affiliated keyword starting this statement is not expected to
     /// be transmitted.
     /// 
-    /// When a modifier gets used, it needs to be removed from the vector
-    fn generate_node(&self, arguments: Vec<Rc<dyn Node>>, modifiers: &mut Vec<Modifier>) -> Option<Rc<dyn Node>>;
+    /// When a modifier gets used, it needs to be removed from the vector.
+    /// The position covers the entire statement, including the keyword.
+    fn generate_node(&self, arguments: Vec<Rc<dyn Node>>, modifiers: &mut Vec<Modifier>, position: (usize, TokenPosition)) -> Option<Rc<dyn Node>>;
 }
\ No newline at end of file
@@ -6,8 +6,9 @@ use crate::compiler::parser::modifier::Modifier;
 use crate::config::tokenization_options::Keyword;
 use crate::compiler::parser::parse::ExpressionKind;
 use crate::compiler::parser::parse_datatype::ParameterDescriptor;
+use crate::compiler::parser::function_meta::FunctionStyle;
 use crate::compiler::parser::statement::Statement;
-use crate::compiler::parser::tree::node::{ArgumentsNode, CodeBlockNode, ExitNode, FunctionDeclarationNode, IdentifierNode, IfNode, LetNode, Node, StringLiteralNode};
+use crate::compiler::parser::tree::node::{ArgumentsNode, CodeBlockNode, DestructuringLetNode, ExitNode, ExternFunctionDeclarationNode, FunctionDeclarationNode, GlobalDeclarationNode, IdentifierNode, IfNode, LetNode, MatchArm, MatchNode, Node, ReturnNode, StringLiteralNode, TypeAnnotationNode};
 
 #[derive(Clone, Debug, EnumIter)]
 pub enum Statements {
@@ -15,7 +16,15 @@ pub enum Statements {
     Var,
     Exit,
     Function,
+    Return,
     If,
+    Struct,
+    Enum,
+    Match,
+    Const,
+    Static,
+    Import,
+    Asm,
 }
 
 impl Statement for Statements {
@@ -25,7 +34,15 @@ impl Statement for Statements {
             Statements::Var => Some(Keyword::Var),
             Statements::Exit => Some(Keyword::Exit),
             Statements::Function => Some(Keyword::Function),
+            Statements::Return => Some(Keyword::Return),
             Statements::If => Some(Keyword::If),
+            Statements::Struct => Some(Keyword::Struct),
+            Statements::Enum => Some(Keyword::Enum),
+            Statements::Match => Some(Keyword::Match),
+            Statements::Const => Some(Keyword::Const),
+            Statements::Static => Some(Keyword::Static),
+            Statements::Import => Some(Keyword::Import),
+            Statements::Asm => Some(Keyword::Asm),
         }
     }
 
@@ -34,20 +51,30 @@ impl Statement for Statements {
             Statements::Let => {
                 vec![
                     (
-                        ExpressionKind::Identifier(None),
+                        ExpressionKind::DeclaredNames,
                         true
+                    ),
+
+                    (
+                        ExpressionKind::TypeAnnotation,
+                        false
                     )
                 ]
             }
             Statements::Var => {
                 vec![
                     (
-                        ExpressionKind::Identifier(None),
+                        ExpressionKind::DeclaredNames,
                         true
+                    ),
+
+                    (
+                        ExpressionKind::TypeAnnotation,
+                        false
                     )
                 ]
             }
-            Statements::Exit => {
+            Statements::Exit | Statements::Return => {
                 vec![
                 ]
             }
@@ -62,6 +89,11 @@ impl Statement for Statements {
                     (
                         ExpressionKind::Array(Box::new(ExpressionKind::Parameter)),
                         true
+                    ),
+
+                    (
+                        ExpressionKind::ReturnType,
+                        false
                     )
                 ]
             }
@@ -74,6 +106,65 @@ impl Statement for Statements {
                     )
                 ]
             }
+
+            Statements::Struct => {
+                vec![
+                    (
+                        ExpressionKind::StructDeclaration,
+                        true
+                    )
+                ]
+            }
+
+            Statements::Enum => {
+                vec![
+                    (
+                        ExpressionKind::EnumDeclaration,
+                        true
+                    )
+                ]
+            }
+
+            Statements::Import => {
+                vec![
+                    (
+                        ExpressionKind::ImportPath,
+                        true
+                    )
+                ]
+            }
+
+            Statements::Asm => {
+                vec![
+                    (
+                        ExpressionKind::AsmBlock,
+                        true
+                    )
+                ]
+            }
+
+            Statements::Match => {
+                vec![
+                    (
+                        ExpressionKind::Value,
+                        true
+                    )
+                ]
+            }
+
+            Statements::Const | Statements::Static => {
+                vec![
+                    (
+                        ExpressionKind::Identifier(None),
+                        true
+                    ),
+
+                    (
+                        ExpressionKind::TypeAnnotation,
+                        true
+                    )
+                ]
+            }
         }
     }
 
@@ -113,11 +204,21 @@ impl Statement for Statements {
                     )
                 ]
             }
+            // Functions without return values can leave out the values.
+            Statements::Return => {
+                vec![
+                    (
+                        ExpressionKind::Values,
+                        false
+                    )
+                ]
+            }
+            // Only extern functions may leave out the body.
             Statements::Function => {
                 vec![
                     (
                         ExpressionKind::CodeBlock,
-                        true
+                        false
                     )
                 ]
             }
@@ -130,35 +231,83 @@ impl Statement for Statements {
                     )
                 ]
             }
+
+            Statements::Struct | Statements::Enum | Statements::Import | Statements::Asm => {
+                vec![
+                ]
+            }
+
+            Statements::Match => {
+                vec![
+                    (
+                        ExpressionKind::MatchArms,
+                        true
+                    )
+                ]
+            }
+
+            Statements::Const | Statements::Static => {
+                vec![
+                    (
+                        ExpressionKind::Assignment,
+                        true
+                    ),
+
+                    (
+                        ExpressionKind::Value,
+                        true
+                    )
+                ]
+            }
         }
     }
 
-    fn generate_node(&self, arguments: Vec<Rc<dyn Node>>, modifiers: &mut Vec<Modifier>) -> Option<Rc<dyn Node>> {
+    fn generate_node(&self, arguments: Vec<Rc<dyn Node>>, modifiers: &mut Vec<Modifier>, position: (usize, TokenPosition)) -> Option<Rc<dyn Node>> {
         match self {
             Statements::Exit => {
                 let arg = arguments[0].clone();
 
-                let node = ExitNode::new(arg, (0, TokenPosition::new(0, 0)));
+                let node = ExitNode::new(arg, position);
 
                 return Some(Rc::new(node));
             },
 
+            Statements::Return => {
+                return Some(Rc::new(ReturnNode::new(arguments, position)));
+            }
+
             Statements::Function => {
                 let identifier_arg = arguments[0].clone();
                 let identifier_node = identifier_arg.downcast_rc::<IdentifierNode>().unwrap();
                 let identifier = identifier_node.identifier.clone();
 
-                let block = arguments.last().unwrap().clone();
-                let mut block = block.downcast_rc::<CodeBlockNode>().unwrap().deref().clone();
-
+                let block = arguments.last().unwrap().clone().downcast_rc::<CodeBlockNode>().ok();
+                let return_types: Vec<TypeAnnotationNode> = arguments.iter()
+                    .filter_map(|x| x.clone().downcast_rc::<TypeAnnotationNode>().ok())
+                    .map(|x| x.deref().clone())
+                    .collect();
 
                 let argument_node = arguments[1].clone().downcast_rc::<ArgumentsNode<ParameterDescriptor>>().unwrap();
-                let parameters = argument_node.args.clone();
+                let mut parameters = argument_node.args.clone();
+
+                // `...` may only end the parameters, the function then takes any amount of further arguments.
+                let is_variadic = parameters.last().is_some_and(|x| x.is_variadic);
+
+                if is_variadic {
+                    Rc::make_mut(&mut parameters).pop();
+                }
+
+                if parameters.iter().any(|x| x.is_variadic) {
+                    todo!("Throw an error: '...' has to be the last parameter")
+                }
 
                 println!("parameters: {:?}", parameters);
 
                 // Go through the parameters
                 // 1. Find extern
+                let mut style = FunctionStyle::Smisc;
+                let mut asm_name: Option<Rc<String>> = None;
+
                 if let Some(extern_index) = modifiers.iter().position(|x|x.base == Keyword::Extern) {
                     if extern_index != modifiers.len() - 1 {
                         todo!("Throw an error: extern expect as last modifier")
@@ -167,17 +316,39 @@ impl Statement for Statements {
                     let extern_modifier = modifiers[extern_index].clone();
                     modifiers.remove(extern_index);
 
-                    let name_node = extern_modifier.arguments[1].clone().downcast_rc::<StringLiteralNode>().unwrap();
-                    let asm_name = name_node.string.clone();
+                    let abi_node = extern_modifier.arguments[0].clone().downcast_rc::<StringLiteralNode>().unwrap();
+                    if abi_node.string.as_str() != "C" {
+                        todo!("Throw an error: only the \"C\" ABI is supported by extern")
+                    }
 
-                    block.label = Some(asm_name);
+                    // Without a spelled out symbol, the one of the target's C functions is used.
+                    asm_name = extern_modifier.arguments.get(1).map(|name_node| name_node.clone().downcast_rc::<StringLiteralNode>().unwrap().string.clone());
+                    style = FunctionStyle::C;
                 }
 
+                // 2. Declarations without a body refer to functions linked in from elsewhere.
+                let Some(block) = block else {
+                    if style != FunctionStyle::C {
+                        todo!("Throw an error: only extern functions can be declared without a body")
+                    }
+
+                    return Some(Rc::new(ExternFunctionDeclarationNode::new(position, Rc::new(identifier), parameters, is_variadic, return_types, asm_name)));
+                };
+
+                if is_variadic {
+                    todo!("Throw an error: only extern functions declared without a body can be variadic")
+                }
+
+                let mut block = block.deref().clone();
+                block.label = asm_name.or(block.label);
+
                 let function_node = FunctionDeclarationNode::new(
-                    (0, TokenPosition::new(0, 0)),
+                    position,
                     Rc::new(identifier),
                     Rc::new(block).clone(),
-                    parameters.clone()
+                    parameters.clone(),
+                    return_types,
+                    style
                 );
 
 
@@ -189,7 +360,7 @@ impl Statement for Statements {
                 let condition = arguments[0].clone();
                 let code_block = arguments[1].clone().downcast_rc::<CodeBlockNode>().unwrap();
                 let if_node = IfNode::new(
-                    (0, TokenPosition::new(0, 0)),
+                    position,
                     condition,
                     code_block.clone(),
                     None
@@ -197,18 +368,51 @@ impl Statement for Statements {
 
                 return Some(Rc::new(if_node));
             }
+
+            Statements::Match => {
+                let value = arguments[0].clone();
+                let arms = arguments[1].clone().downcast_rc::<ArgumentsNode<MatchArm>>().unwrap();
+
+                return Some(Rc::new(MatchNode::new(value, arms.args.deref().clone(), position)));
+            }
+
+            Statements::Const | Statements::Static => {
+                let identifier = arguments[0].clone().downcast_rc::<IdentifierNode>().unwrap().identifier.clone();
+                let type_annotation = arguments[1].clone().downcast_rc::<TypeAnnotationNode>().unwrap().deref().clone();
+                let value = arguments.last().unwrap().clone();
+
+                let is_constant = matches!(self, Statements::Const);
+
+                return Some(Rc::new(GlobalDeclarationNode::new(identifier, value, is_constant, type_annotation, position)));
+            }
+
+            // The type was registered while parsing the declaration already.
+            Statements::Struct | Statements::Enum | Statements::Asm => return Some(arguments[0].clone()),
+            // The module was loaded before parsing.
+            Statements::Import => return Some(arguments[0].clone()),
             _ => {}
         }
 
+        let assigned_value = arguments.last().unwrap().clone();
+        
+        let is_mutable = matches!(self, Statements::Var);
+
+        // Several names receive the values a function returns (e.g. `let (q, r) = divide(a, b);`)
+        if let Ok(names) = arguments[0].clone().downcast_rc::<ArgumentsNode<IdentifierNode>>() {
+            if arguments[1].clone().downcast_rc::<TypeAnnotationNode>().is_ok() {
+                todo!("Throw an error: the types of several names can't be annotated")
+            }
+
+            return Some(Rc::new(DestructuringLetNode::new(names.args.deref().clone(), assigned_value, is_mutable, position)));
+        }
+
         let identifier_arg = arguments[0].clone();
         let identifier_node = identifier_arg.downcast_rc::<IdentifierNode>().unwrap();
         let identifier = identifier_node.identifier.clone();
         
-        let assigned_value = arguments[2].clone();
-        
-        let is_mutable = matches!(self, Statements::Var);
+        let type_annotation = arguments[1].clone().downcast_rc::<TypeAnnotationNode>().ok().map(|x| x.deref().clone());
         
-        let node = LetNode::new(identifier, Some(assigned_value), is_mutable, (0, TokenPosition::new(0, 0)));
+        let node = LetNode::new(identifier, Some(assigned_value), is_mutable, type_annotation, position);
         
         Some(Rc::new(node))
     }
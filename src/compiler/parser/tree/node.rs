@@ -5,15 +5,22 @@ use std::rc::Rc;
 use derive_new::*;
 use downcast_rs::{Downcast, impl_downcast};
 use uuid::Uuid;
-use crate::compiler::backend::context::Context;
-use crate::compiler::backend::flattener::{Instruction, JumpComparisonType, JumpCondition};
+use crate::compiler::backend::arch::RegisterKind;
+use crate::compiler::backend::context::{Context, OverflowBehaviour};
+use crate::compiler::backend::flattener::{InlineAssembly, Instruction, JumpComparisonType, JumpCondition};
+use crate::compiler::data_types::array::{build_array_type, get_array_info};
 use crate::compiler::data_types::datatypes_general::Buildable;
+use crate::compiler::data_types::enumeration::{get_enum_variants, EnumVariant};
 use crate::compiler::data_types::integer::IntegerType;
 use crate::compiler::data_types::object::{ObjectType, Trait};
-use crate::compiler::line_map::{DisplayCodeInfo, DisplayCodeKind, NotificationInfo, TokenPosition};
+use crate::compiler::data_types::reference::{build_reference_type, get_reference_target};
+use crate::compiler::data_types::string::{encode_basic_string, STRING_LENGTH_SIZE};
+use crate::compiler::data_types::structure::get_struct_fields;
+use crate::compiler::line_map::{DisplayCodeKind, NotificationInfo, TokenPosition};
 use crate::compiler::parser::function_meta::{FunctionArgument, FunctionMeta, FunctionStyle};
 use crate::compiler::parser::future::CodeFuture;
 use crate::compiler::parser::parse_datatype::ParameterDescriptor;
+use crate::config::target::DEFAULT_INTEGER_TYPE;
 use crate::util::operator::Operation;
 
 /// A trait requiring basic functionality for any node in an abstract
@@ -82,6 +89,31 @@ pub trait Node: Debug + Downcast {
     }
 
 
+    /// ### Checks Types
+    ///
+    /// Runs in the type checking pass, before [generate_instructions](Self::generate_instructions).
+    /// Errors about incompatible types should be displayed here (and only here), while
+    /// nodes that declare objects register them in the context, so that following
+    /// nodes can look up their types.
+    ///
+    /// By default, this checks all [subnodes](Self::get_sub_nodes).
+    fn check_types(&self, context: &mut Context) {
+        for sub_node in self.get_sub_nodes() {
+            sub_node.check_types(context);
+        }
+    }
+
+
+    /// ### Evaluates the Node at Compile Time
+    ///
+    /// Returns the value the node results in if it's already known while compiling
+    /// (e.g. `2 * 3` or a constant). Booleans are `0` or `1`, variants of enums
+    /// their value. Constants, statics and patterns require such values.
+    fn evaluate_constant(&self, _context: &Context) -> Option<i128> {
+        None
+    }
+
+
     /// Resets the position of the node to the TokenPositon::test_position()
     /// on the node itself and all subnodes
     #[cfg(test)]
@@ -90,6 +122,151 @@ pub trait Node: Debug + Downcast {
 
 impl_downcast!(Node);
 
+/// Describes possible types for notifications, e.g. "`u32`" or "`u16` or `u8`".
+fn describe_datatypes(datatypes: &[ObjectType]) -> String {
+    let mut names = datatypes.iter().map(|x| format!("`{}`", x.name)).collect::<Vec<String>>();
+    names.sort();
+
+    names.join(" or ")
+}
+
+/// Picks one of the possible types of a value. If the value could have
+/// several types (e.g. an integer literal without a suffix), the
+/// [default integer type](DEFAULT_INTEGER_TYPE) is preferred.
+fn pick_datatype(datatypes: &[ObjectType]) -> &ObjectType {
+    datatypes.iter()
+        .find(|x| x.name == DEFAULT_INTEGER_TYPE.get_code_name())
+        .unwrap_or(&datatypes[0])
+}
+
+/// Picks one of the possible types of a value like [pick_datatype], but prefers
+/// `expected` if the value can have it (e.g. `1 << 63` assigned to a `u64`).
+fn pick_expected_datatype(datatypes: &[ObjectType], expected: Option<&ObjectType>) -> ObjectType {
+    match expected {
+        Some(expected) if datatypes.iter().any(|x| x.type_uuid == expected.type_uuid) => expected.clone(),
+        _ => pick_datatype(datatypes).clone(),
+    }
+}
+
+/// Whether there's at least one type both lists have in common.
+fn datatypes_overlap(a: &[ObjectType], b: &[ObjectType]) -> bool {
+    a.iter().any(|x| b.iter().any(|y| x.type_uuid == y.type_uuid))
+}
+
+/// The code a program [traps](Instruction::Trap) with when integer arithmetic overflows.
+const OVERFLOW_TRAP_CODE: u16 = 1;
+
+/// ### Generates Checked Operations
+///
+/// Generates the `instruction` performing the `operation` on `result` and `operand`, followed by
+/// a check whether `result` still fits into its type, which traps with the source location otherwise.
+/// With [wrapping](OverflowBehaviour::Wrap), results of types narrower than a register get truncated instead.
+///
+/// 64-bit additions and subtractions are checked using the flags they set. For 64-bit
+/// multiplications, the upper half of the product is calculated beforehand, which has to
+/// be the sign extension of the lower half (or zero for unsigned types).
+fn generate_checked_operation(instruction: Instruction, result: Uuid, operand: Uuid, datatype: &ObjectType, operation: Operation, position: TokenPosition, context: &mut Context) -> Vec<Instruction> {
+    let Some(integer_type) = IntegerType::from_code_name(&datatype.name) else { return vec![instruction] };
+
+    let is_signed = integer_type.is_signed();
+    let is_full_width = integer_type.get_memory_size() == 8;
+
+    if context.options.overflow_behaviour == OverflowBehaviour::Wrap {
+        if is_full_width { return vec![instruction] }
+
+        return vec![
+            instruction,
+            Instruction::Extend(result, integer_type.get_memory_size(), is_signed),
+        ];
+    }
+
+    let trap_label = context.generate_label();
+    let end_label = context.generate_label();
+
+    let (preparation, check) = if is_full_width && operation == Operation::Multiplication {
+        let upper_half = Uuid::new_v4();
+        let expected = Uuid::new_v4();
+
+        let expected_instructions = if is_signed {
+            let shift = Uuid::new_v4();
+
+            vec![
+                Instruction::Move(expected, result),
+                Instruction::MoveData(shift, 63),
+                Instruction::Sar(expected, shift),
+            ]
+        } else {
+            vec![Instruction::MoveData(expected, 0)]
+        };
+
+        (
+            vec![
+                Instruction::Move(upper_half, result),
+                Instruction::MulHigh(upper_half, operand, is_signed),
+            ],
+            [
+                expected_instructions,
+                vec![
+                    Instruction::JumpConditional(JumpCondition::new(Some(upper_half), Some(expected), JumpComparisonType::Equal), end_label.clone()),
+                ],
+            ].concat()
+        )
+    } else if is_full_width {
+        // Subtractions set the carry flag if there was no borrow.
+        let comparison = match (is_signed, operation) {
+            (true, _) => JumpComparisonType::NotOverflow,
+            (false, Operation::Subtraction) => JumpComparisonType::Carry,
+            (false, _) => JumpComparisonType::NotCarry,
+        };
+
+        (
+            vec![],
+            vec![
+                Instruction::JumpConditional(JumpCondition::new(None, None, comparison), end_label.clone()),
+            ]
+        )
+    } else {
+        // Narrower types can't overflow the register, so the result can just be compared to the bounds.
+        let limit = Uuid::new_v4();
+
+        (
+            vec![],
+            vec![
+                Instruction::MoveData(limit, integer_type.get_upper_bound() as i64),
+                Instruction::JumpConditional(JumpCondition::new(Some(result), Some(limit), JumpComparisonType::Greater), trap_label.clone()),
+                Instruction::MoveData(limit, -(integer_type.get_lower_bound() as i64)),
+                Instruction::JumpConditional(JumpCondition::new(Some(result), Some(limit), JumpComparisonType::GreaterOrEqual), end_label.clone()),
+            ]
+        )
+    };
+
+    let message = format!("integer overflow at {}", describe_source_location(position, context));
+
+    [
+        preparation,
+        vec![instruction],
+        check,
+        vec![Instruction::Label(trap_label, false)],
+        generate_trap(OVERFLOW_TRAP_CODE, message, context),
+        vec![Instruction::Label(end_label, false)],
+    ].concat()
+}
+
+/// ### Generates Traps
+///
+/// Stops the program with the trap `code` after writing the `message` to the standard
+/// error. The message is placed in the read-only data, so it's still there at runtime.
+fn generate_trap(code: u16, message: String, context: &mut Context) -> Vec<Instruction> {
+    let symbol = Rc::new(format!("l_.trap.{}", context.generate_label()));
+    let bytes = (message + "\n").into_bytes();
+    let length = bytes.len();
+
+    vec![
+        Instruction::ReadOnlyData(symbol.clone(), bytes, 1),
+        Instruction::Trap(code, symbol, length),
+    ]
+}
+
 /// Any node that has a type that can be resolved to a value.
 /// **Note**: This is used in the parser to group values and parse
 /// expressions as arguments into statements.
@@ -98,6 +275,16 @@ pub enum ValueNode {
     Arithmetic(ArithmeticNode),
     Literal(LiteralValueNode),
     Identifier(IdentifierNode),
+    Unary(UnaryNode),
+    Cast(CastNode),
+    ArrayLiteral(ArrayLiteralNode),
+    Index(IndexNode),
+    New(NewNode),
+    Dereference(DereferenceNode),
+    AddressOf(AddressOfNode),
+    StructLiteral(StructLiteralNode),
+    FieldAccess(FieldAccessNode),
+    EnumVariant(EnumVariantNode),
 }
 
 
@@ -108,6 +295,16 @@ impl ValueNode {
             ValueNode::Arithmetic(node) => Box::new(node.clone()),
             ValueNode::Literal(node) => Box::new(node.clone()),
             ValueNode::Identifier(node) => Box::new(node.clone()),
+            ValueNode::Unary(node) => Box::new(node.clone()),
+            ValueNode::Cast(node) => Box::new(node.clone()),
+            ValueNode::ArrayLiteral(node) => Box::new(node.clone()),
+            ValueNode::Index(node) => Box::new(node.clone()),
+            ValueNode::New(node) => Box::new(node.clone()),
+            ValueNode::Dereference(node) => Box::new(node.clone()),
+            ValueNode::AddressOf(node) => Box::new(node.clone()),
+            ValueNode::StructLiteral(node) => Box::new(node.clone()),
+            ValueNode::FieldAccess(node) => Box::new(node.clone()),
+            ValueNode::EnumVariant(node) => Box::new(node.clone()),
         }
     }
 }
@@ -138,16 +335,34 @@ impl Node for ValueNode {
         self.unpack().generate_instructions(context)
     }
 
+    fn evaluate_constant(&self, context: &Context) -> Option<i128> {
+        self.get_sub_node().evaluate_constant(context)
+    }
+
     fn output_is_randomly_mutable(&self) -> Option<bool> {
         self.get_sub_node().output_is_randomly_mutable()
     }
 
+    fn check_types(&self, context: &mut Context) {
+        self.get_sub_node().check_types(context)
+    }
+
     #[cfg(test)]
     fn repeatedly_reset_position(&mut self) {
         match self {
             ValueNode::Arithmetic(node) => node.repeatedly_reset_position(),
             ValueNode::Literal(node) => node.repeatedly_reset_position(),
             ValueNode::Identifier(node) => node.repeatedly_reset_position(),
+            ValueNode::Unary(node) => node.repeatedly_reset_position(),
+            ValueNode::Cast(node) => node.repeatedly_reset_position(),
+            ValueNode::ArrayLiteral(node) => node.repeatedly_reset_position(),
+            ValueNode::Index(node) => node.repeatedly_reset_position(),
+            ValueNode::New(node) => node.repeatedly_reset_position(),
+            ValueNode::Dereference(node) => node.repeatedly_reset_position(),
+            ValueNode::AddressOf(node) => node.repeatedly_reset_position(),
+            ValueNode::StructLiteral(node) => node.repeatedly_reset_position(),
+            ValueNode::FieldAccess(node) => node.repeatedly_reset_position(),
+            ValueNode::EnumVariant(node) => node.repeatedly_reset_position(),
         }
     }
 }
@@ -167,6 +382,13 @@ pub struct IdentifierNode {
     position: (usize, TokenPosition)
 }
 
+impl IdentifierNode {
+    /// The object the identifier refers to, looked up in the module of its file.
+    pub fn resolve(&self, context: &Context) -> Option<Uuid> {
+        context.resolve_object(&self.identifier, self.position.0)
+    }
+}
+
 impl Node for IdentifierNode {
     fn get_position(&self) -> (usize, TokenPosition) {
         self.position.clone()
@@ -185,12 +407,12 @@ impl Node for IdentifierNode {
             return Some(vec![type_.clone()]);
         }
 
-        let object_uuid = context.name_map.get(&self.identifier);
+        let object_uuid = self.resolve(&context);
 
         println!("uuid: {object_uuid:?}");
 
 
-        let type_uuid = context.objects.get(object_uuid?);
+        let type_uuid = context.objects.get(&object_uuid?);
         let type_ = context.datatypes.get(type_uuid?);
 
 
@@ -202,9 +424,59 @@ impl Node for IdentifierNode {
     }
 
     fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
-        let object_uuid = context.name_map.get(&self.identifier);
+        let object_uuid = self.resolve(context);
+
+        // Constants are inserted wherever they're used.
+        if let Some(object_uuid) = object_uuid
+            && let Some(value) = context.constant_values.get(&object_uuid).copied() {
+            let result = Uuid::new_v4();
+
+            return (vec![Instruction::MoveData(result, value as i64)], Some(result));
+        }
+
+        // Variables in memory have to be loaded first.
+        if let Some(object_uuid) = object_uuid
+            && context.memory_objects.contains(&object_uuid) {
+            let datatype = context.datatypes.get(&context.objects[&object_uuid]).unwrap().clone();
+            let (mut instructions, address) = generate_object_address(object_uuid, context);
+            let (mut load_instructions, result) = generate_load(address, &datatype);
+
+            instructions.append(&mut load_instructions);
+            return (instructions, Some(result));
+        }
+
+        (vec![], object_uuid)
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        if self.data_type.is_some() || self.resolve(context).is_some() { return; }
+
+        let declaration = context.get_pending_declaration(&self.identifier);
+
+        let (title, message) = match declaration {
+            Some(_) => ("Use Before Declaration", format!("`{}` is used before it's declared", self.identifier)),
+            None => ("Unknown Identifier", format!("There's no variable, constant or static called `{}` here", self.identifier)),
+        };
+
+        let mut display_code = vec![
+            context.line_map.display_code_for(self.position.1, vec![], DisplayCodeKind::InitialError),
+        ];
+
+        if let Some(declaration) = declaration {
+            display_code.push(context.line_map.display_code_for(
+                declaration.1,
+                vec!["*note:* declared here".to_string()],
+                DisplayCodeKind::AdditionalInfo
+            ));
+        }
+
+        context.line_map.display_error(NotificationInfo::new(title.to_string(), message, display_code));
+    }
+
+    fn evaluate_constant(&self, context: &Context) -> Option<i128> {
+        let object_uuid = self.resolve(context)?;
 
-        (vec![], object_uuid.copied())
+        context.constant_values.get(&object_uuid).copied()
     }
 
     fn output_is_randomly_mutable(&self) -> Option<bool> {
@@ -220,7 +492,9 @@ impl Node for IdentifierNode {
 #[derive(Clone, Debug, PartialEq)]
 pub enum LiteralValueNode {
     Integer(IntegerLiteralNode),
-    Boolean(BoolLiteralNode)
+    Boolean(BoolLiteralNode),
+    String(StringLiteralNode),
+    Char(CharLiteralNode),
 }
 
 impl LiteralValueNode {
@@ -228,6 +502,8 @@ impl LiteralValueNode {
         match self {
             LiteralValueNode::Integer(node) => Box::new(node.clone()),
             LiteralValueNode::Boolean(node) => Box::new(node.clone()),
+            LiteralValueNode::String(node) => Box::new(node.clone()),
+            LiteralValueNode::Char(node) => Box::new(node.clone()),
         }
     }
 }
@@ -258,15 +534,25 @@ impl Node for LiteralValueNode {
         todo!()
     }
 
+    fn evaluate_constant(&self, context: &Context) -> Option<i128> {
+        self.get_sub_node().evaluate_constant(context)
+    }
+
     fn output_is_randomly_mutable(&self) -> Option<bool> {
         Some(true)
     }
 
+    fn check_types(&self, context: &mut Context) {
+        self.get_sub_node().check_types(context);
+    }
+
     #[cfg(test)]
     fn repeatedly_reset_position(&mut self) {
         match self {
             LiteralValueNode::Integer(node) => node.repeatedly_reset_position(),
             LiteralValueNode::Boolean(node) => node.repeatedly_reset_position(),
+            LiteralValueNode::String(node) => node.repeatedly_reset_position(),
+            LiteralValueNode::Char(node) => node.repeatedly_reset_position(),
         }
     }
 }
@@ -317,6 +603,58 @@ impl Node for BoolLiteralNode {
         )
     }
 
+    fn evaluate_constant(&self, _context: &Context) -> Option<i128> {
+        Some(self.content as i128)
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        Some(true)
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+    }
+}
+
+/// A [node](Node) containing a single character (e.g. `'a'`). Its value is the code point.
+#[derive(Clone, Debug, new, PartialEq)]
+pub struct CharLiteralNode {
+    content: char,
+    position: (usize, TokenPosition),
+}
+
+impl Node for CharLiteralNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position
+    }
+
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        Vec::new()
+    }
+
+    fn get_datatypes(&self, all_types: Vec<ObjectType>, _context: Context) -> Option<Vec<ObjectType>> {
+        Some(vec![all_types.into_iter().find(|x| x.has_trait(Trait::CHARACTER))?])
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn generate_instructions(&self, _context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let uuid = Uuid::new_v4();
+
+        (vec![Instruction::MoveData(uuid, self.content as i64)], Some(uuid))
+    }
+
+    fn evaluate_constant(&self, _context: &Context) -> Option<i128> {
+        Some(self.content as i128)
+    }
+
     fn output_is_randomly_mutable(&self) -> Option<bool> {
         Some(true)
     }
@@ -357,12 +695,13 @@ impl Node for IntegerLiteralNode {
             }
         }
 
-        // Get all integer types
+        // Get all integer types the value fits into
         let mut compatible_types: Vec<ObjectType> = vec![];
 
         for type_ in all_types.iter().clone() {
-            // Integer?
-            if type_.has_trait(Trait::INTEGER) {
+            let fits = IntegerType::from_code_name(&type_.name).is_none_or(|x| x.contains(self.content));
+
+            if type_.has_trait(Trait::INTEGER) && fits {
                 compatible_types.push(type_.clone());
             }
         }
@@ -386,10 +725,43 @@ impl Node for IntegerLiteralNode {
         )
     }
 
+    fn evaluate_constant(&self, _context: &Context) -> Option<i128> {
+        Some(self.content)
+    }
+
     fn output_is_randomly_mutable(&self) -> Option<bool> {
         Some(true)
     }
 
+    fn check_types(&self, context: &mut Context) {
+        // Suffixed literals only need to fit into their own type, others into any integer type.
+        let candidates = match self.kind.clone() {
+            Some(kind) => vec![kind],
+            None => IntegerType::ALL.to_vec(),
+        };
+
+        if candidates.iter().any(|x| x.contains(self.content)) { return; }
+
+        let note = match self.kind.clone() {
+            Some(kind) => format!("*note:* `{}` ranges from {}", kind.get_code_name(), kind.describe_range()),
+            None => "*note:* no integer type can hold this value".to_string(),
+        };
+
+        let notification = NotificationInfo::new(
+            "Integer Literal Out Of Range".to_string(),
+            format!("`{}` doesn't fit into {}", self.content, candidates.iter().map(|x| format!("`{}`", x.get_code_name())).collect::<Vec<String>>().join(" or ")),
+            vec![
+                context.line_map.display_code_for(
+                    self.position.1,
+                    vec![note],
+                    DisplayCodeKind::InitialError
+                )
+            ],
+        );
+
+        context.line_map.display_error(notification);
+    }
+
     #[cfg(test)]
     fn repeatedly_reset_position(&mut self) {
         self.position = (0, TokenPosition::test_value());
@@ -415,41 +787,103 @@ pub struct ArithmeticNode {
     position: (usize, TokenPosition),
 }
 
-impl Node for ArithmeticNode {
-    fn get_position(&self) -> (usize, TokenPosition) {
-        self.position.clone()
+impl ArithmeticNode {
+    /// Gets the instruction that applies the operation to (a) and (b),
+    /// storing the result in (a).
+    fn generate_binary_instruction(&self, a: Uuid, b: Uuid, datatype: Option<&ObjectType>) -> Instruction {
+        let is_signed = datatype.is_some_and(|x| x.has_trait(Trait::SIGNED));
+
+        match self.operation {
+            Operation::Addition => Instruction::Add(a, b),
+            Operation::Subtraction => Instruction::Sub(a, b),
+            Operation::Multiplication => Instruction::Mul(a, b),
+            Operation::Division => Instruction::Div(a, b, is_signed),
+            Operation::Modulo => Instruction::Mod(a, b, is_signed),
+            Operation::BitwiseAnd => Instruction::And(a, b),
+            Operation::BitwiseOr => Instruction::Or(a, b),
+            Operation::BitwiseXor => Instruction::Xor(a, b),
+            Operation::ShiftLeft => Instruction::Shl(a, b),
+            // Unsigned values have no sign to keep, so they're shifted logically.
+            Operation::ShiftRight if is_signed => Instruction::Sar(a, b),
+            Operation::ShiftRight => Instruction::Shr(a, b),
+            Operation::LogicalShiftRight => Instruction::Shr(a, b),
+
+            _ => todo!()
+        }
     }
 
-    fn get_future(&self, current: CodeFuture) -> CodeFuture {
-        current
-    }
+    /// Picks the type the operation is performed on, preferring `expected`. It decides
+    /// how divisions, remainders and shifts are calculated and what overflows.
+    fn pick_operation_type(&self, expected: Option<&ObjectType>, context: &Context) -> Option<ObjectType> {
+        let all_types = context.datatypes.values().cloned().collect();
+        let datatypes = self.get_datatypes(all_types, context.clone()).filter(|x| !x.is_empty())?;
 
-    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
-        vec![self.argument_a.clone(), self.argument_b.clone()]
+        Some(pick_expected_datatype(&datatypes, expected))
     }
 
-    fn get_datatypes(&self, all_types: Vec<ObjectType>, context: Context) -> Option<Vec<ObjectType>> {
-        if self.operation.is_boolean() {
-            // Find the boolean type in the types
-            for type_ in all_types.iter().clone() {
-                if type_.has_trait(Trait::BOOLEAN_COMPATIBLE){
-                    return Some(vec![type_.clone()]);
-                }
-            }
+    /// Generates the instruction storing the result in `a` and, for additions, subtractions
+    /// and multiplications, the [overflow checks](generate_checked_operation) after it.
+    /// Shifts are [kept within their type](Self::generate_narrow_shift) instead.
+    fn generate_checked_instruction(&self, a: Uuid, b: Uuid, datatype: Option<&ObjectType>, context: &mut Context) -> Vec<Instruction> {
+        let instruction = self.generate_binary_instruction(a, b, datatype);
+        let Some(datatype) = datatype else { return vec![instruction] };
+
+        match self.operation {
+            Operation::Addition | Operation::Subtraction | Operation::Multiplication =>
+                generate_checked_operation(instruction, a, b, datatype, self.operation.clone(), self.position.1, context),
+            Operation::ShiftLeft | Operation::ShiftRight | Operation::LogicalShiftRight => self.generate_narrow_shift(instruction, a, datatype),
+            _ => vec![instruction],
         }
+    }
 
-        // It should just be the data type of the first and second argument, which should be equivalent
-        self.argument_a.get_datatypes(all_types, context)
+    /// Generates the shift `instruction` of `a` for types narrower than a register.
+    ///
+    /// Values are kept extended to the whole register, so bits shifted beyond the type
+    /// by `<<` are cut off afterwards. `>>>` has to shift in zeros right after the
+    /// type's own bits, so signed values lose their extension for the shift.
+    fn generate_narrow_shift(&self, instruction: Instruction, a: Uuid, datatype: &ObjectType) -> Vec<Instruction> {
+        let Some(integer_type) = IntegerType::from_code_name(&datatype.name) else { return vec![instruction] };
+
+        let size = integer_type.get_memory_size();
+        let is_signed = integer_type.is_signed();
+
+        if size == 8 { return vec![instruction] }
+
+        match self.operation {
+            Operation::ShiftLeft => vec![instruction, Instruction::Extend(a, size, is_signed)],
+            Operation::LogicalShiftRight if is_signed => vec![
+                Instruction::Extend(a, size, false),
+                instruction,
+                Instruction::Extend(a, size, true),
+            ],
+            _ => vec![instruction],
+        }
     }
 
-    fn unpack(&self) -> Box<dyn Node> {
-        Box::new(self.clone())
+    /// Generates the operation as `datatype`, which is used if it's one of the possible types
+    /// of the operation, e.g. for literals that would be of the default integer type otherwise.
+    pub fn generate_instructions_as(&self, datatype: &ObjectType, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        self.generate_operation(Some(datatype), context)
     }
 
-    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
-        let self_ = self.clone();
-        let a = self_.argument_a.generate_instructions(context);
-        let b = self_.argument_b.generate_instructions(context);
+    /// Generates the operation on the [type picked](Self::pick_operation_type) for it.
+    /// The arguments are generated as that type as well, except for the amount of a shift.
+    fn generate_operation(&self, expected: Option<&ObjectType>, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        if self.operation.is_boolean() {
+            return self.generate_boolean_instructions(context);
+        }
+
+        let datatype = self.pick_operation_type(expected, context);
+        let is_shift = matches!(self.operation, Operation::ShiftLeft | Operation::ShiftRight | Operation::LogicalShiftRight);
+
+        let a = match &datatype {
+            Some(datatype) => generate_value_as(&self.argument_a, datatype, context),
+            None => self.argument_a.generate_instructions(context),
+        };
+        let b = match &datatype {
+            Some(datatype) if !is_shift => generate_value_as(&self.argument_b, datatype, context),
+            _ => self.argument_b.generate_instructions(context),
+        };
 
         let mut x = a.1.unwrap();
 
@@ -459,15 +893,7 @@ impl Node for ArithmeticNode {
                     a.0,
                     b.0,
                     vec![],
-                    match self.operation {
-                        Operation::Addition => vec![Instruction::Add(b.1.unwrap(), x)],
-                        Operation::Subtraction => vec![Instruction::Sub(b.1.unwrap(), x)],
-                        Operation::Multiplication => vec![Instruction::Mul(b.1.unwrap(), x)],
-                        Operation::Division => vec![Instruction::Div(b.1.unwrap(), x)],
-                        Operation::Modulo => vec![Instruction::Mod(b.1.unwrap(), x)],
-
-                        _ => todo!()
-                    }
+                    self.generate_checked_instruction(b.1.unwrap(), x, datatype.as_ref(), context),
                 ].concat(),
                 Some(b.1.unwrap())
             )
@@ -484,24 +910,211 @@ impl Node for ArithmeticNode {
                 if x!= a.1.unwrap() {vec![
                     Instruction::Move(x, a.1.unwrap())
                 ]} else {vec![]},
-                match self.operation {
-                    Operation::Addition => vec![Instruction::Add(x, b.1.unwrap())],
-                    Operation::Subtraction => vec![Instruction::Sub(x, b.1.unwrap())],
-                    Operation::Multiplication => vec![Instruction::Mul(x, b.1.unwrap())],
-                    Operation::Division => vec![Instruction::Div(x, b.1.unwrap())],
-                    Operation::Modulo => vec![Instruction::Mod(x, b.1.unwrap())],
-
-                    _ => todo!()
-                }
+                self.generate_checked_instruction(x, b.1.unwrap(), datatype.as_ref(), context),
             ].concat(),
             Some(x)
         )
     }
 
+    /// Generates the instructions for operations resulting in a boolean.
+    ///
+    /// `&&` and `||` short-circuit, so the right side is only evaluated
+    /// if the left side didn't decide the result already. `^^` and `==`
+    /// always evaluate both sides and compare them.
+    fn generate_boolean_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let a = self.argument_a.generate_instructions(context);
+        let result = Uuid::new_v4();
+        let end_label = context.generate_label();
+
+        let instructions = match self.operation {
+            Operation::And | Operation::Or => {
+                let zero = Uuid::new_v4();
+
+                // A false left side decides an "and", a true one decides an "or".
+                let comparison = if self.operation == Operation::And { JumpComparisonType::Equal } else { JumpComparisonType::NotEqual };
+
+                let b = self.argument_b.generate_instructions(context);
+
+                [
+                    a.0,
+                    vec![
+                        Instruction::Move(result, a.1.unwrap()),
+                        Instruction::MoveData(zero, 0),
+                        Instruction::JumpConditional(
+                            JumpCondition::new(Some(result), Some(zero), comparison),
+                            end_label.clone()
+                        ),
+                    ],
+                    b.0,
+                    vec![
+                        Instruction::Move(result, b.1.unwrap()),
+                        Instruction::Label(end_label, false),
+                    ]
+                ].concat()
+            }
+
+            Operation::Xor | Operation::Equals => {
+                // Booleans are always 0 or 1, so an exclusive or is just an inequality.
+                let comparison = if self.operation == Operation::Xor { JumpComparisonType::NotEqual } else { JumpComparisonType::Equal };
+
+                let b = self.argument_b.generate_instructions(context);
+
+                [
+                    a.0,
+                    b.0,
+                    vec![
+                        Instruction::MoveData(result, 1),
+                        Instruction::JumpConditional(
+                            JumpCondition::new(a.1, b.1, comparison),
+                            end_label.clone()
+                        ),
+                        Instruction::MoveData(result, 0),
+                        Instruction::Label(end_label, false),
+                    ]
+                ].concat()
+            }
+
+            _ => unreachable!("{:?} doesn't produce a boolean", self.operation),
+        };
+
+        (instructions, Some(result))
+    }
+}
+
+impl Node for ArithmeticNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position.clone()
+    }
+
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![self.argument_a.clone(), self.argument_b.clone()]
+    }
+
+    fn get_datatypes(&self, all_types: Vec<ObjectType>, context: Context) -> Option<Vec<ObjectType>> {
+        if self.operation.is_boolean() {
+            // Find the boolean type in the types
+            for type_ in all_types.iter().clone() {
+                if type_.has_trait(Trait::BOOLEAN_COMPATIBLE){
+                    return Some(vec![type_.clone()]);
+                }
+            }
+        }
+
+        // It should just be the data type of the first and second argument, which should be equivalent.
+        // If one side is more specific (e.g. `a + 1` with `a: u8`), its type is the one that fits both.
+        let types_a = self.argument_a.get_datatypes(all_types.clone(), context.clone())?;
+
+        if matches!(self.operation, Operation::ShiftLeft | Operation::ShiftRight | Operation::LogicalShiftRight) {
+            return Some(types_a);
+        }
+
+        let Some(types_b) = self.argument_b.get_datatypes(all_types, context) else { return Some(types_a) };
+        let shared_types: Vec<ObjectType> = types_a.iter().filter(|x| types_b.iter().any(|y| x.type_uuid == y.type_uuid)).cloned().collect();
+
+        if shared_types.is_empty() {
+            return Some(types_a);
+        }
+
+        Some(shared_types)
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        self.generate_operation(None, context)
+    }
+
+    fn evaluate_constant(&self, context: &Context) -> Option<i128> {
+        let a = self.argument_a.evaluate_constant(context)?;
+        let b = self.argument_b.evaluate_constant(context)?;
+
+        match self.operation {
+            Operation::Addition => a.checked_add(b),
+            Operation::Subtraction => a.checked_sub(b),
+            Operation::Multiplication => a.checked_mul(b),
+            Operation::Division => a.checked_div(b),
+            Operation::Modulo => a.checked_rem(b),
+            Operation::Equals => Some((a == b) as i128),
+            Operation::And => Some((a != 0 && b != 0) as i128),
+            Operation::Or => Some((a != 0 || b != 0) as i128),
+            Operation::Xor => Some(((a != 0) != (b != 0)) as i128),
+            Operation::BitwiseAnd => Some(a & b),
+            Operation::BitwiseOr => Some(a | b),
+            Operation::BitwiseXor => Some(a ^ b),
+            Operation::ShiftLeft => a.checked_shl(u32::try_from(b).ok()?),
+            Operation::ShiftRight => a.checked_shr(u32::try_from(b).ok()?),
+            // Depends on the width of the type, which isn't known here
+            _ => None,
+        }
+    }
+
     fn output_is_randomly_mutable(&self) -> Option<bool> {
         Some(self.argument_a.output_is_randomly_mutable()? || self.argument_b.output_is_randomly_mutable()?)
     }
 
+    fn check_types(&self, context: &mut Context) {
+        self.argument_a.check_types(context);
+        self.argument_b.check_types(context);
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let Some(types_a) = self.argument_a.get_datatypes(all_types.clone(), context.clone()) else { return };
+        let Some(types_b) = self.argument_b.get_datatypes(all_types, context.clone()) else { return };
+
+        let required_trait = match self.operation {
+            Operation::And | Operation::Or | Operation::Xor => Some(Trait::BOOLEAN_COMPATIBLE),
+            Operation::Equals => None,
+            Operation::BitwiseAnd | Operation::BitwiseOr | Operation::BitwiseXor |
+            Operation::ShiftLeft | Operation::ShiftRight | Operation::LogicalShiftRight => Some(Trait::INTEGER),
+            _ => Some(Trait::ARITHMETIC_COMPATIBLE),
+        };
+
+        let supports_operation = |types: &Vec<ObjectType>| required_trait.is_none_or(|required_trait| types.iter().any(|x| x.has_trait(required_trait)));
+
+        // The shift amount doesn't need to have the same type as the shifted value.
+        let is_shift = matches!(self.operation, Operation::ShiftLeft | Operation::ShiftRight | Operation::LogicalShiftRight);
+
+        let (title, message) = if !supports_operation(&types_a) || !supports_operation(&types_b) {
+            let unsupported_types = if supports_operation(&types_a) { &types_b } else { &types_a };
+
+            (
+                "Unsupported Operation",
+                format!("`{}` can't be applied to a value of type {}", self.operation.as_ref(), describe_datatypes(unsupported_types))
+            )
+        } else if !is_shift && !datatypes_overlap(&types_a, &types_b) {
+            (
+                "Mismatched Types",
+                format!("`{}` expects both sides to have the same type, but got {} and {}", self.operation.as_ref(), describe_datatypes(&types_a), describe_datatypes(&types_b))
+            )
+        } else {
+            return;
+        };
+
+        let notification = NotificationInfo::new(
+            title.to_string(),
+            message,
+            vec![
+                context.line_map.display_code_for(
+                    self.argument_a.get_position().1,
+                    vec![format!("*note:* this is of type {}", describe_datatypes(&types_a))],
+                    DisplayCodeKind::InitialError
+                ),
+                context.line_map.display_code_for(
+                    self.argument_b.get_position().1,
+                    vec![format!("*note:* this is of type {}", describe_datatypes(&types_b))],
+                    DisplayCodeKind::AdditionalInfo
+                ),
+            ],
+        );
+
+        context.line_map.display_error(notification);
+    }
+
     #[cfg(test)]
     fn repeatedly_reset_position(&mut self) {
         self.position = (0, TokenPosition::test_value());
@@ -527,293 +1140,3406 @@ impl Node for ArithmeticNode {
     }
 }
 
+/// A [node](Node) applying an operation to a single argument (e.g. `-a`,
+/// `!a` or `~a`).
 #[derive(Clone, Debug, new)]
-pub struct AssignmentNode {
-    left_side: Rc<IdentifierNode>,
-    right_side: Rc<dyn Node>,
+pub struct UnaryNode {
+    /// The [operation](Operation) this node should perform (e.g. **-**a).
+    operation: Operation,
+
+    /// The argument of the operation (e.g. -**a**)
+    argument: Rc<dyn Node>,
+
     position: (usize, TokenPosition),
 }
 
+impl UnaryNode {
+    /// Generates the operation as `datatype`, which is used if it's one of the possible
+    /// types of the argument, just like for [arithmetic](ArithmeticNode::generate_instructions_as).
+    pub fn generate_instructions_as(&self, datatype: &ObjectType, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        self.generate_operation(Some(datatype), context)
+    }
+
+    /// Generates the operation on the argument, which is generated as the type picked for it.
+    fn generate_operation(&self, expected: Option<&ObjectType>, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let all_types = context.datatypes.values().cloned().collect();
+        let datatype = self.argument.get_datatypes(all_types, context.clone())
+            .filter(|datatypes| !datatypes.is_empty() && self.operation != Operation::LogicalNot)
+            .map(|datatypes| pick_expected_datatype(&datatypes, expected));
+
+        let argument = match &datatype {
+            Some(datatype) => generate_value_as(&self.argument, datatype, context),
+            None => self.argument.generate_instructions(context),
+        };
+
+        let argument_uuid = argument.1.unwrap();
+        let result = Uuid::new_v4();
+
+        let operation_instructions = match self.operation {
+            // -a => 0 - a
+            Operation::Subtraction => {
+                let subtraction = Instruction::Sub(result, argument_uuid);
+                let subtraction = match &datatype {
+                    // Negating the minimum of a signed type overflows, just like `0 - MIN`.
+                    Some(datatype) => generate_checked_operation(subtraction, result, argument_uuid, datatype, Operation::Subtraction, self.position.1, context),
+                    None => vec![subtraction],
+                };
 
-impl Node for AssignmentNode {
-    fn get_position(&self) -> (usize, TokenPosition) {
-        self.position.clone()
-    }
+                [
+                    vec![
+                        Instruction::MoveData(result, 0),
+                    ],
+                    subtraction,
+                ].concat()
+            }
+
+            // Booleans are 0 or 1, so flipping the lowest bit inverts them.
+            // For bitwise not, all bits get flipped.
+            Operation::LogicalNot | Operation::BitwiseNot => {
+                let mask = Uuid::new_v4();
+                let mask_value = if self.operation == Operation::LogicalNot { 1 } else { -1 };
+
+                let mut instructions = vec![
+                    Instruction::Move(result, argument_uuid),
+                    Instruction::MoveData(mask, mask_value),
+                    Instruction::Xor(result, mask),
+                ];
+
+                // The bits above narrower types got flipped as well, but values are kept extended.
+                if let Some(integer_type) = datatype.and_then(|x| IntegerType::from_code_name(&x.name)) {
+                    instructions.push(Instruction::Extend(result, integer_type.get_memory_size(), integer_type.is_signed()));
+                }
+
+                instructions
+            }
+
+            _ => unreachable!("{:?} is not a unary operation", self.operation),
+        };
+
+        ([argument.0, operation_instructions].concat(), Some(result))
+    }
+}
+
+impl Node for UnaryNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position
+    }
+
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![self.argument.clone()]
+    }
+
+    fn get_datatypes(&self, all_types: Vec<ObjectType>, context: Context) -> Option<Vec<ObjectType>> {
+        if self.operation == Operation::LogicalNot {
+            for type_ in all_types.iter() {
+                if type_.has_trait(Trait::BOOLEAN_COMPATIBLE) {
+                    return Some(vec![type_.clone()]);
+                }
+            }
+        }
+
+        self.argument.get_datatypes(all_types, context)
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        self.generate_operation(None, context)
+    }
+
+    fn evaluate_constant(&self, context: &Context) -> Option<i128> {
+        let value = self.argument.evaluate_constant(context)?;
+
+        match self.operation {
+            Operation::Subtraction => value.checked_neg(),
+            Operation::LogicalNot => Some((value == 0) as i128),
+            Operation::BitwiseNot => {
+                // The bits above the type's width stay cleared for unsigned types.
+                let datatypes = self.argument.get_datatypes(context.datatypes.values().cloned().collect(), context.clone())?;
+                let integer_type = IntegerType::from_code_name(&pick_datatype(&datatypes).name)?;
+
+                Some(integer_type.wrap(!value))
+            }
+            _ => None,
+        }
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        Some(true)
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        self.argument.check_types(context);
+
+        let all_types = context.datatypes.values().cloned().collect();
+        let Some(types) = self.argument.get_datatypes(all_types, context.clone()) else { return };
+
+        let (required_trait, title, hint) = match self.operation {
+            Operation::Subtraction => (Trait::SIGNED, "Negation Of Unsigned Value", "*hint:* cast to a signed type first"),
+            Operation::LogicalNot => (Trait::BOOLEAN_COMPATIBLE, "Logical Not On Non-Boolean", "*hint:* use `~` to invert all bits of an integer"),
+            Operation::BitwiseNot => (Trait::INTEGER, "Bitwise Not On Non-Integer", "*hint:* use `!` to invert a boolean"),
+            _ => unreachable!("{:?} is not a unary operation", self.operation),
+        };
+
+        // Untyped literals may still turn out to be of a fitting type.
+        if types.iter().any(|x| x.has_trait(required_trait)) { return; }
+
+        let display_info = context.line_map.display_code_for(
+            self.position.1,
+            vec![hint.to_string()],
+            DisplayCodeKind::InitialError
+        );
+
+        let notification = NotificationInfo::new(
+            title.to_string(),
+            format!("`{}` can't be applied to a value of type {}", self.operation.as_ref(), describe_datatypes(&types)),
+            vec![display_info],
+        );
+
+        context.line_map.display_error(notification);
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+
+        let argument = self.argument.clone().downcast_rc::<ValueNode>().unwrap();
+        let mut argument = argument.deref().clone();
+
+        argument.repeatedly_reset_position();
+        self.argument = Rc::new(argument);
+    }
+}
+
+/// A [node](Node) converting a value into another type explicitly (e.g. `a as u8`).
+///
+/// Integers get truncated or extended to fit the new type, booleans become
+/// `0` or `1`, integers become `true` unless they're zero and variants of
+/// enums become their value.
+#[derive(Clone, Debug, new)]
+pub struct CastNode {
+    /// The value that gets converted (e.g. **a** as u8)
+    value: Rc<dyn Node>,
+
+    /// The uuid of the type the value gets converted to (e.g. a as **u8**)
+    datatype: Uuid,
+
+    position: (usize, TokenPosition),
+}
+
+impl CastNode {
+    /// Whether a value of the type `from` can be cast to the type `to`.
+    fn is_valid_cast(from: &ObjectType, to: &ObjectType) -> bool {
+        if from.type_uuid == to.type_uuid { return true; }
+
+        let is_number_or_bool = |x: &ObjectType| x.has_trait(Trait::INTEGER) || x.has_trait(Trait::BOOLEAN_COMPATIBLE);
+
+        // Enums and characters can be turned into their values, but not the other way around.
+        if from.has_trait(Trait::ENUM) || from.has_trait(Trait::CHARACTER) { return to.has_trait(Trait::INTEGER); }
+
+        // Booleans can't be cast to booleans of other types, but they don't exist yet anyway.
+        is_number_or_bool(from) && is_number_or_bool(to) && (from.has_trait(Trait::INTEGER) || to.has_trait(Trait::INTEGER))
+    }
+}
+
+impl Node for CastNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position
+    }
+
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![self.value.clone()]
+    }
+
+    fn get_datatypes(&self, all_types: Vec<ObjectType>, _context: Context) -> Option<Vec<ObjectType>> {
+        Some(all_types.into_iter().filter(|x| x.type_uuid == self.datatype).collect())
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let value = self.value.generate_instructions(context);
+        let value_uuid = value.1.unwrap();
+        let result = Uuid::new_v4();
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let source_types = self.value.get_datatypes(all_types, context.clone()).unwrap_or_default();
+        let target = context.datatypes.get(&self.datatype).unwrap().clone();
+
+        // Casting to a boolean is a comparison to zero.
+        if target.has_trait(Trait::BOOLEAN_COMPATIBLE) && !source_types.iter().any(|x| x.has_trait(Trait::BOOLEAN_COMPATIBLE)) {
+            let zero = Uuid::new_v4();
+            let end_label = context.generate_label();
+
+            return (
+                [
+                    value.0,
+                    vec![
+                        Instruction::MoveData(result, 0),
+                        Instruction::MoveData(zero, 0),
+                        Instruction::JumpConditional(
+                            JumpCondition::new(Some(value_uuid), Some(zero), JumpComparisonType::Equal),
+                            end_label.clone()
+                        ),
+                        Instruction::MoveData(result, 1),
+                        Instruction::Label(end_label, false),
+                    ]
+                ].concat(),
+                Some(result)
+            );
+        }
+
+        let mut instructions = [value.0, vec![Instruction::Move(result, value_uuid)]].concat();
+
+        // Integers fill the entire register, so widening them is free as long as
+        // the value stays the same. Everything else needs the value to be truncated
+        // and extended again.
+        if let Some(to) = IntegerType::from_code_name(&target.name) {
+            let from = match source_types.first() {
+                None => None,
+                // The values of enums and characters are stored as `u32`s
+                Some(x) if x.has_trait(Trait::ENUM) || x.has_trait(Trait::CHARACTER) => Some(IntegerType::Unsigned32BitInteger),
+                Some(_) => IntegerType::from_code_name(&pick_datatype(&source_types).name),
+            };
+
+            // Booleans (which aren't integer types) are always 0 or 1, so they fit into any integer.
+            let keeps_value = from.as_ref().is_none_or(|from| *from == to
+                || (to.get_memory_size() > from.get_memory_size() && (to.is_signed() || !from.is_signed())));
+
+            if !keeps_value {
+                instructions.push(Instruction::Extend(result, to.get_memory_size(), to.is_signed()));
+            }
+        }
+
+        (instructions, Some(result))
+    }
+
+    fn evaluate_constant(&self, context: &Context) -> Option<i128> {
+        let value = self.value.evaluate_constant(context)?;
+        let target = context.datatypes.get(&self.datatype)?;
+
+        if target.has_trait(Trait::BOOLEAN_COMPATIBLE) {
+            return Some((value != 0) as i128);
+        }
+
+        Some(IntegerType::from_code_name(&target.name)?.wrap(value))
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        Some(true)
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        self.value.check_types(context);
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let Some(source_types) = self.value.get_datatypes(all_types, context.clone()) else { return };
+        let Some(target) = context.datatypes.get(&self.datatype).cloned() else { return };
+
+        // Untyped literals may still turn out to be of a castable type.
+        if source_types.iter().any(|x| CastNode::is_valid_cast(x, &target)) { return; }
+
+        let notification = NotificationInfo::new(
+            "Invalid Cast".to_string(),
+            format!("A value of type {} can't be cast to `{}`", describe_datatypes(&source_types), target.name),
+            vec![
+                context.line_map.display_code_for(
+                    self.position.1,
+                    vec!["*note:* only integers, booleans, characters and enums can be cast".to_string()],
+                    DisplayCodeKind::InitialError
+                ),
+            ],
+        );
+
+        context.line_map.display_error(notification);
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+
+        let value = self.value.clone().downcast_rc::<ValueNode>().unwrap();
+        let mut value = value.deref().clone();
+
+        value.repeatedly_reset_position();
+        self.value = Rc::new(value);
+    }
+}
+
+/// The code a program [traps](Instruction::Trap) with when an array is indexed out of bounds.
+const BOUNDS_TRAP_CODE: u16 = 2;
+
+/// Looks up the element type (.0) and the length (.1) of an array type.
+fn get_array_element(datatype: &ObjectType, all_types: &[ObjectType]) -> Option<(ObjectType, usize)> {
+    let (element_uuid, length) = get_array_info(datatype)?;
+
+    Some((all_types.iter().find(|x| x.type_uuid == element_uuid)?.clone(), length))
+}
+
+/// Looks up the type a reference type refers to.
+fn get_reference_target_type(datatype: &ObjectType, all_types: &[ObjectType]) -> Option<ObjectType> {
+    let target_uuid = get_reference_target(datatype)?;
+
+    all_types.iter().find(|x| x.type_uuid == target_uuid).cloned()
+}
+
+/// Generates a value, laying out array literals and allocations and calculating
+/// operations as `datatype`. Their contents could fit several types otherwise.
+fn generate_value_as(value: &Rc<dyn Node>, datatype: &ObjectType, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+    let unpacked = value.unpack();
+
+    if let Some(literal) = unpacked.downcast_ref::<ArrayLiteralNode>() {
+        return literal.generate_instructions_as(datatype, context);
+    }
+
+    if let Some(allocation) = unpacked.downcast_ref::<NewNode>() {
+        return allocation.generate_instructions_as(datatype, context);
+    }
+
+    if let Some(operation) = unpacked.downcast_ref::<ArithmeticNode>() {
+        return operation.generate_instructions_as(datatype, context);
+    }
+
+    if let Some(operation) = unpacked.downcast_ref::<UnaryNode>() {
+        return operation.generate_instructions_as(datatype, context);
+    }
+
+    value.generate_instructions(context)
+}
+
+/// Whether values of the type are the address of the memory holding them (arrays and structs).
+fn is_aggregate(datatype: &ObjectType) -> bool {
+    get_array_info(datatype).is_some() || get_struct_fields(datatype).is_some()
+}
+
+/// Loads a value of the type `datatype` from `address`. Arrays and structs don't
+/// get loaded, as their value is their address.
+fn generate_load(address: Uuid, datatype: &ObjectType) -> (Vec<Instruction>, Uuid) {
+    if is_aggregate(datatype) {
+        return (vec![], address);
+    }
+
+    let result = Uuid::new_v4();
+    let size = datatype.get_size().unwrap() as u8;
+
+    (vec![Instruction::Load(result, address, size, datatype.has_trait(Trait::SIGNED))], result)
+}
+
+/// Stores `value` of the type `datatype` at `address`. Arrays and structs get their contents copied.
+fn generate_store(value: Uuid, address: Uuid, datatype: &ObjectType) -> Vec<Instruction> {
+    let size = datatype.get_size().unwrap();
+
+    if is_aggregate(datatype) {
+        return generate_memory_copy(address, value, size);
+    }
+
+    vec![Instruction::Store(value, address, size as u8)]
+}
+
+/// Gets an object containing the address of the given object in memory. Statics
+/// are only known by their symbol, so their address is loaded into a new object.
+fn generate_object_address(object: Uuid, context: &Context) -> (Vec<Instruction>, Uuid) {
+    let Some(symbol) = context.global_symbols.get(&object) else { return (vec![], object) };
+    let address = Uuid::new_v4();
+
+    (vec![Instruction::LoadAddress(address, symbol.clone())], address)
+}
+
+/// Calculates `base` + `offset` into a new object.
+fn generate_address(base: Uuid, offset: usize) -> (Vec<Instruction>, Uuid) {
+    let address = Uuid::new_v4();
+
+    (
+        vec![
+            Instruction::MoveData(address, offset as i64),
+            Instruction::Add(address, base),
+        ],
+        address
+    )
+}
+
+/// ### Generates Memory Copies
+///
+/// Copies `size` bytes from the address in `source` to the address in `destination`,
+/// which is how values that don't fit into registers (e.g. arrays and structs) get assigned.
+fn generate_memory_copy(destination: Uuid, source: Uuid, size: usize) -> Vec<Instruction> {
+    let mut instructions = vec![];
+    let mut offset = 0;
+
+    while offset < size {
+        let chunk = [8, 4, 2, 1].into_iter().find(|x| size - offset >= *x).unwrap();
+        let value = Uuid::new_v4();
+
+        let (mut source_address, source_uuid) = generate_address(source, offset);
+        let (mut destination_address, destination_uuid) = generate_address(destination, offset);
+
+        instructions.append(&mut source_address);
+        instructions.append(&mut destination_address);
+        instructions.push(Instruction::Load(value, source_uuid, chunk as u8, false));
+        instructions.push(Instruction::Store(value, destination_uuid, chunk as u8));
+
+        offset += chunk;
+    }
+
+    instructions
+}
+
+/// Gets the variable an element or a field is stored in (e.g. `a` for `a[1].b[2]`) if there is one.
+/// Values behind references aren't stored in a variable.
+fn get_root_identifier(place: &dyn Node, context: &Context) -> Option<IdentifierNode> {
+    if let Some(identifier) = place.downcast_ref::<IdentifierNode>() {
+        return Some(identifier.clone());
+    }
+
+    if let Some(index) = place.downcast_ref::<IndexNode>() {
+        return get_root_identifier(index.array.unpack().as_ref(), context);
+    }
+
+    let field_access = place.downcast_ref::<FieldAccessNode>()?;
+
+    if field_access.is_through_reference(context) {
+        return None;
+    }
+
+    get_root_identifier(field_access.value.unpack().as_ref(), context)
+}
+
+/// Describes where a node is located in the source code for runtime messages (e.g. "main.smisc:4:12").
+fn describe_source_location(position: TokenPosition, context: &Context) -> String {
+    let line = &context.line_map.files[position.line];
+
+    format!("{}:{}:{}", line.source_file_name, line.line_number, position.start + 1)
+}
+
+/// Reports that `identifier` (stored in `object`) gets changed although it was declared immutable.
+fn report_immutable_modification(identifier: &str, object: Uuid, position: TokenPosition, context: &mut Context) {
+    let mut display_code = vec![
+        context.line_map.display_code_for(
+            position,
+            vec![
+                format!("*note:* `{}` can't be changed after its declaration", identifier)
+            ],
+            DisplayCodeKind::InitialError
+        )
+    ];
+
+    if let Some(declaration_position) = context.declaration_positions.get(&object) {
+        display_code.push(
+            context.line_map.display_code_for(
+                declaration_position.1,
+                vec![
+                    "*hint:* consider making this variable mutable by using `var` instead of `let`".to_string()
+                ],
+                DisplayCodeKind::AdditionalInfo
+            )
+        );
+    }
+
+    let notification = NotificationInfo::new(
+        "Attempt To Modify Immutable Variable".to_string(),
+        "This assignment tries to alter a left side that is immutable".to_string(),
+        display_code,
+    );
+
+    context.line_map.display_error(notification);
+}
+
+/// A [node](Node) creating a fixed-size array from its elements (e.g. `[1, 2, 3]`).
+///
+/// The array lives on the stack of the current function, its value is the
+/// address of the first element.
+#[derive(Clone, Debug, new)]
+pub struct ArrayLiteralNode {
+    elements: Vec<Rc<dyn Node>>,
+    position: (usize, TokenPosition),
+}
+
+impl ArrayLiteralNode {
+    /// Gets the types all elements have in common, preferring the one
+    /// [picked](pick_datatype) for the first element.
+    fn get_element_types(&self, all_types: Vec<ObjectType>, context: Context) -> Option<Vec<ObjectType>> {
+        let mut element_types = self.elements.first()?.get_datatypes(all_types.clone(), context.clone())?;
+
+        for element in self.elements.iter().skip(1) {
+            let datatypes = element.get_datatypes(all_types.clone(), context.clone())?;
+
+            element_types.retain(|x| datatypes.iter().any(|y| x.type_uuid == y.type_uuid));
+        }
+
+        if element_types.is_empty() { return Some(element_types); }
+
+        let preferred = pick_datatype(&element_types).type_uuid;
+        element_types.sort_by_key(|x| x.type_uuid != preferred);
+
+        Some(element_types)
+    }
+
+    /// Makes the possible types of the literal known, so objects holding it can be looked up.
+    fn register_datatypes(&self, context: &mut Context) {
+        // The element types of nested literals have to be known first.
+        for element in &self.elements {
+            if let Some(literal) = element.unpack().downcast_ref::<ArrayLiteralNode>() {
+                literal.register_datatypes(context);
+            }
+        }
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+
+        for datatype in self.get_datatypes(all_types, context.clone()).unwrap_or_default() {
+            context.datatypes.entry(datatype.type_uuid).or_insert(datatype);
+        }
+    }
+
+    /// Generates the array laid out as `datatype`, which has to be one of its possible types.
+    pub fn generate_instructions_as(&self, datatype: &ObjectType, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        self.register_datatypes(context);
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let (element_type, _) = get_array_element(datatype, &all_types).unwrap();
+        let element_size = element_type.get_size().unwrap();
+
+        let result = Uuid::new_v4();
+        let mut instructions = vec![
+            Instruction::StackAllocate(result, datatype.get_size().unwrap() as u64),
+        ];
+
+        for (i, element) in self.elements.iter().enumerate() {
+            let (mut element_instructions, element_uuid) = generate_value_as(element, &element_type, context);
+            let (mut address_instructions, address) = generate_address(result, i * element_size);
+
+            instructions.append(&mut element_instructions);
+            instructions.append(&mut address_instructions);
+            instructions.append(&mut generate_store(element_uuid.unwrap(), address, &element_type));
+        }
+
+        (instructions, Some(result))
+    }
+}
+
+impl Node for ArrayLiteralNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position
+    }
+
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        self.elements.clone()
+    }
+
+    fn get_datatypes(&self, all_types: Vec<ObjectType>, context: Context) -> Option<Vec<ObjectType>> {
+        let element_types = self.get_element_types(all_types, context)?;
+
+        Some(element_types.iter().map(|x| build_array_type(x, self.elements.len())).collect())
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let datatypes = self.get_datatypes(all_types, context.clone()).unwrap();
+
+        self.generate_instructions_as(&datatypes[0], context)
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        Some(true)
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        for element in &self.elements {
+            element.check_types(context);
+        }
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+
+        if self.elements.is_empty() {
+            let notification = NotificationInfo::new(
+                "Empty Array Literal".to_string(),
+                "The type of an empty array can't be inferred".to_string(),
+                vec![
+                    context.line_map.display_code_for(
+                        self.position.1,
+                        vec!["*note:* arrays need at least one element".to_string()],
+                        DisplayCodeKind::InitialError
+                    ),
+                ],
+            );
+
+            context.line_map.display_error(notification);
+            return;
+        }
+
+        let Some(element_types) = self.get_element_types(all_types.clone(), context.clone()) else { return };
+
+        if element_types.is_empty() {
+            let first = &self.elements[0];
+            let first_types = first.get_datatypes(all_types.clone(), context.clone()).unwrap_or_default();
+
+            // Point at the first element that doesn't fit in with the first one.
+            let mismatch = self.elements.iter()
+                .find(|x| !datatypes_overlap(&first_types, &x.get_datatypes(all_types.clone(), context.clone()).unwrap_or_default()))
+                .unwrap_or(first);
+            let mismatch_types = mismatch.get_datatypes(all_types.clone(), context.clone()).unwrap_or_default();
+
+            let notification = NotificationInfo::new(
+                "Mismatched Array Elements".to_string(),
+                format!("All elements of an array need to be of the same type, but {} and {} were found", describe_datatypes(&first_types), describe_datatypes(&mismatch_types)),
+                vec![
+                    context.line_map.display_code_for(
+                        mismatch.get_position().1,
+                        vec![format!("*note:* expected {}, found {}", describe_datatypes(&first_types), describe_datatypes(&mismatch_types))],
+                        DisplayCodeKind::InitialError
+                    ),
+                    context.line_map.display_code_for(
+                        first.get_position().1,
+                        vec!["*note:* the type is inferred from the first element".to_string()],
+                        DisplayCodeKind::AdditionalInfo
+                    ),
+                ],
+            );
+
+            context.line_map.display_error(notification);
+            return;
+        }
+
+        self.register_datatypes(context);
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+
+        self.elements = self.elements.iter().map(|element| {
+            let element = element.clone().downcast_rc::<ValueNode>().unwrap();
+            let mut element = element.deref().clone();
+
+            element.repeatedly_reset_position();
+            Rc::new(element) as Rc<dyn Node>
+        }).collect();
+    }
+}
+
+/// A [node](Node) reading an element of an array (e.g. `a[2]`).
+///
+/// Elements that are arrays themselves aren't copied, their value is the
+/// address inside the outer array.
+#[derive(Clone, Debug, new)]
+pub struct IndexNode {
+    /// The indexed array (e.g. **a**[2])
+    array: Rc<dyn Node>,
+
+    /// The index of the element (e.g. a[**2**])
+    index: Rc<dyn Node>,
+
+    position: (usize, TokenPosition),
+}
+
+impl IndexNode {
+    /// ### Generates the Address of the Element
+    ///
+    /// Returns the instructions, the object containing the address and the type of the element.
+    /// With [bounds checks](crate::compiler::backend::context::CompileOptions::check_bounds) enabled, the program traps if the index
+    /// isn't within the array.
+    fn generate_element_address(&self, context: &mut Context) -> (Vec<Instruction>, Uuid, ObjectType) {
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+
+        let (mut array_instructions, array_uuid) = self.array.generate_instructions(context);
+        let (mut index_instructions, index_uuid) = self.index.generate_instructions(context);
+
+        let array_types = self.array.get_datatypes(all_types.clone(), context.clone()).unwrap();
+        let (element_type, length) = get_array_element(pick_datatype(&array_types), &all_types).unwrap();
+
+        let mut instructions = vec![];
+        instructions.append(&mut array_instructions);
+        instructions.append(&mut index_instructions);
+
+        if context.options.check_bounds {
+            let length_uuid = Uuid::new_v4();
+            let trap_label = context.generate_label();
+            let end_label = context.generate_label();
+            let message = format!("index out of bounds at {}", describe_source_location(self.position.1, context));
+
+            // Negative indices are huge when treated as unsigned, so one comparison covers both bounds.
+            instructions.append(&mut vec![
+                Instruction::MoveData(length_uuid, length as i64),
+                Instruction::JumpConditional(JumpCondition::new(index_uuid, Some(length_uuid), JumpComparisonType::UnsignedLess), end_label.clone()),
+                Instruction::Label(trap_label, false),
+            ]);
+            instructions.append(&mut generate_trap(BOUNDS_TRAP_CODE, message, context));
+            instructions.push(Instruction::Label(end_label, false));
+        }
+
+        let address = Uuid::new_v4();
+        let element_size = Uuid::new_v4();
+
+        instructions.append(&mut vec![
+            Instruction::Move(address, index_uuid.unwrap()),
+            Instruction::MoveData(element_size, element_type.get_size().unwrap() as i64),
+            Instruction::Mul(address, element_size),
+            Instruction::Add(address, array_uuid.unwrap()),
+        ]);
+
+        (instructions, address, element_type)
+    }
+}
+
+impl Node for IndexNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position
+    }
+
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![self.array.clone(), self.index.clone()]
+    }
+
+    fn get_datatypes(&self, all_types: Vec<ObjectType>, context: Context) -> Option<Vec<ObjectType>> {
+        let array_types = self.array.get_datatypes(all_types.clone(), context)?;
+        let mut element_types: Vec<ObjectType> = vec![];
+
+        for (element_type, _) in array_types.iter().filter_map(|x| get_array_element(x, &all_types)) {
+            if !element_types.iter().any(|x| x.type_uuid == element_type.type_uuid) {
+                element_types.push(element_type);
+            }
+        }
+
+        Some(element_types)
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let (mut instructions, address, element_type) = self.generate_element_address(context);
+        let (mut load, result) = generate_load(address, &element_type);
+
+        instructions.append(&mut load);
+
+        (instructions, Some(result))
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        // Nested arrays point into the indexed array.
+        Some(false)
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        self.array.check_types(context);
+        self.index.check_types(context);
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let Some(array_types) = self.array.get_datatypes(all_types.clone(), context.clone()) else { return };
+        let Some(index_types) = self.index.get_datatypes(all_types.clone(), context.clone()) else { return };
+
+        if !array_types.iter().any(|x| get_array_info(x).is_some()) {
+            let notification = NotificationInfo::new(
+                "Indexing Non-Array".to_string(),
+                format!("A value of type {} can't be indexed", describe_datatypes(&array_types)),
+                vec![
+                    context.line_map.display_code_for(
+                        self.array.get_position().1,
+                        vec!["*note:* only arrays can be indexed".to_string()],
+                        DisplayCodeKind::InitialError
+                    ),
+                ],
+            );
+
+            context.line_map.display_error(notification);
+            return;
+        }
+
+        if !index_types.iter().any(|x| x.has_trait(Trait::INTEGER)) {
+            let notification = NotificationInfo::new(
+                "Non-Integer Index".to_string(),
+                format!("Arrays have to be indexed using integers, but the index is of type {}", describe_datatypes(&index_types)),
+                vec![
+                    context.line_map.display_code_for(
+                        self.index.get_position().1,
+                        vec![format!("*note:* expected an integer, found {}", describe_datatypes(&index_types))],
+                        DisplayCodeKind::InitialError
+                    ),
+                ],
+            );
+
+            context.line_map.display_error(notification);
+            return;
+        }
+
+        // Indices known at compile time are always checked.
+        let Some(literal) = self.index.unpack().downcast_ref::<IntegerLiteralNode>().cloned() else { return };
+        let Some((_, length)) = get_array_info(pick_datatype(&array_types)) else { return };
+
+        if literal.content >= 0 && literal.content < length as i128 { return; }
+
+        let notification = NotificationInfo::new(
+            "Index Out Of Bounds".to_string(),
+            format!("The index `{}` is outside of the array", literal.content),
+            vec![
+                context.line_map.display_code_for(
+                    self.index.get_position().1,
+                    vec![format!("*note:* the array has a length of {}", length)],
+                    DisplayCodeKind::InitialError
+                ),
+            ],
+        );
+
+        context.line_map.display_error(notification);
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+
+        for node in [&mut self.array, &mut self.index] {
+            let value = node.clone().downcast_rc::<ValueNode>().unwrap();
+            let mut value = value.deref().clone();
+
+            value.repeatedly_reset_position();
+            *node = Rc::new(value);
+        }
+    }
+}
+
+/// A [node](Node) changing a value in memory, which is either an element of
+/// an array (e.g. `a[2] = 5`), a field of a struct (e.g. `a.x = 5`) or the value
+/// a reference refers to (e.g. `*a = 5`).
+#[derive(Clone, Debug, new)]
+pub struct PlaceAssignmentNode {
+    /// Either an [index](IndexNode), a [field access](FieldAccessNode) or a [dereference](DereferenceNode)
+    left_side: Rc<dyn Node>,
+    right_side: Rc<dyn Node>,
+    position: (usize, TokenPosition),
+}
+
+impl PlaceAssignmentNode {
+    /// Generates the address of the changed value and gets its type.
+    fn generate_place_address(&self, context: &mut Context) -> (Vec<Instruction>, Uuid, ObjectType) {
+        let left_side = self.left_side.unpack();
+
+        if let Some(index) = left_side.downcast_ref::<IndexNode>() {
+            return index.generate_element_address(context);
+        }
+
+        if let Some(field_access) = left_side.downcast_ref::<FieldAccessNode>() {
+            return field_access.generate_field_address(context);
+        }
+
+        left_side.downcast_ref::<DereferenceNode>().unwrap().generate_target_address(context)
+    }
+}
+
+impl Node for PlaceAssignmentNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position
+    }
+
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![]
+    }
+
+    fn get_datatypes(&self, _types: Vec<ObjectType>, _context: Context) -> Option<Vec<ObjectType>> {
+        None
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let (mut instructions, address, datatype) = self.generate_place_address(context);
+        let (mut right_side, right_uuid) = generate_value_as(&self.right_side, &datatype, context);
+
+        instructions.append(&mut right_side);
+        instructions.append(&mut generate_store(right_uuid.unwrap(), address, &datatype));
+
+        (instructions, None)
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        None
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        self.left_side.check_types(context);
+        self.right_side.check_types(context);
+
+        // Values behind references can always be changed, elements and fields only if the variable can.
+        if let Some(identifier) = get_root_identifier(self.left_side.unpack().as_ref(), context)
+            && let Some(object) = identifier.resolve(context)
+            && !context.mutable_objects.contains(&object) {
+            report_immutable_modification(&identifier.identifier, object, self.position.1, context);
+        }
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let Some(left_types) = self.left_side.get_datatypes(all_types.clone(), context.clone()) else { return };
+        let Some(right_types) = self.right_side.get_datatypes(all_types, context.clone()) else { return };
+
+        if left_types.is_empty() || datatypes_overlap(&left_types, &right_types) { return; }
+
+        let notification = NotificationInfo::new(
+            "Mismatched Types".to_string(),
+            format!("The changed value is of type {}, but the assigned value is of type {}", describe_datatypes(&left_types), describe_datatypes(&right_types)),
+            vec![
+                context.line_map.display_code_for(
+                    self.right_side.get_position().1,
+                    vec![format!("*note:* expected {}, found {}", describe_datatypes(&left_types), describe_datatypes(&right_types))],
+                    DisplayCodeKind::InitialError
+                ),
+            ],
+        );
+
+        context.line_map.display_error(notification);
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+
+        for node in [&mut self.left_side, &mut self.right_side] {
+            let value = node.clone().downcast_rc::<ValueNode>().unwrap();
+            let mut value = value.deref().clone();
+
+            value.repeatedly_reset_position();
+            *node = Rc::new(value);
+        }
+    }
+}
+
+/// A [node](Node) storing a value in newly allocated memory (e.g. `new 15`).
+///
+/// The memory is requested from the [allocator](crate::compiler::backend::context::CompileOptions::allocator), the
+/// resulting value is a reference to it.
+#[derive(Clone, Debug, new)]
+pub struct NewNode {
+    value: Rc<dyn Node>,
+    position: (usize, TokenPosition),
+}
+
+impl NewNode {
+    /// Makes the possible types of the value and the references to it known.
+    fn register_datatypes(&self, context: &mut Context) {
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let value_types = self.value.get_datatypes(all_types.clone(), context.clone()).unwrap_or_default();
+        let reference_types = self.get_datatypes(all_types, context.clone()).unwrap_or_default();
+
+        for datatype in [value_types, reference_types].concat() {
+            context.datatypes.entry(datatype.type_uuid).or_insert(datatype);
+        }
+    }
+
+    /// Generates the allocation as `datatype`, which has to be one of its possible types.
+    pub fn generate_instructions_as(&self, datatype: &ObjectType, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        self.register_datatypes(context);
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let target = get_reference_target_type(datatype, &all_types).unwrap();
+
+        let (mut instructions, value_uuid) = generate_value_as(&self.value, &target, context);
+        let size = Uuid::new_v4();
+        let result = Uuid::new_v4();
+
+        instructions.push(Instruction::MoveData(size, target.get_size().unwrap() as i64));
+        instructions.push(Instruction::Call(context.options.allocator.deref().clone(), vec![size], vec![result], FunctionStyle::C, None, None));
+        instructions.append(&mut generate_store(value_uuid.unwrap(), result, &target));
+
+        (instructions, Some(result))
+    }
+}
+
+impl Node for NewNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position
+    }
+
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![self.value.clone()]
+    }
+
+    fn get_datatypes(&self, all_types: Vec<ObjectType>, context: Context) -> Option<Vec<ObjectType>> {
+        let mut value_types = self.value.get_datatypes(all_types, context)?;
+
+        if value_types.is_empty() { return Some(value_types); }
+
+        let preferred = pick_datatype(&value_types).type_uuid;
+        value_types.sort_by_key(|x| x.type_uuid != preferred);
+
+        Some(value_types.iter().map(build_reference_type).collect())
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let datatypes = self.get_datatypes(all_types, context.clone()).unwrap();
+
+        self.generate_instructions_as(&datatypes[0], context)
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        Some(true)
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        self.value.check_types(context);
+        self.register_datatypes(context);
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+
+        let value = self.value.clone().downcast_rc::<ValueNode>().unwrap();
+        let mut value = value.deref().clone();
+
+        value.repeatedly_reset_position();
+        self.value = Rc::new(value);
+    }
+}
+
+/// A [node](Node) reading the value a reference refers to (e.g. `*a`).
+#[derive(Clone, Debug, new)]
+pub struct DereferenceNode {
+    value: Rc<dyn Node>,
+    position: (usize, TokenPosition),
+}
+
+impl DereferenceNode {
+    /// Generates the address the reference holds and gets the type of the referenced value.
+    fn generate_target_address(&self, context: &mut Context) -> (Vec<Instruction>, Uuid, ObjectType) {
+        let (instructions, address) = self.value.generate_instructions(context);
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let datatypes = self.value.get_datatypes(all_types.clone(), context.clone()).unwrap();
+        let target = datatypes.iter().find_map(|x| get_reference_target_type(x, &all_types)).unwrap();
+
+        (instructions, address.unwrap(), target)
+    }
+}
+
+impl Node for DereferenceNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position
+    }
+
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![self.value.clone()]
+    }
+
+    fn get_datatypes(&self, all_types: Vec<ObjectType>, context: Context) -> Option<Vec<ObjectType>> {
+        let value_types = self.value.get_datatypes(all_types.clone(), context)?;
+        let mut target_types: Vec<ObjectType> = vec![];
+
+        for target_type in value_types.iter().filter_map(|x| get_reference_target_type(x, &all_types)) {
+            if !target_types.iter().any(|x| x.type_uuid == target_type.type_uuid) {
+                target_types.push(target_type);
+            }
+        }
+
+        Some(target_types)
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let (mut instructions, address, target) = self.generate_target_address(context);
+        let (mut load, result) = generate_load(address, &target);
+
+        instructions.append(&mut load);
+
+        (instructions, Some(result))
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        // Referenced arrays aren't copied.
+        Some(false)
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        self.value.check_types(context);
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let Some(value_types) = self.value.get_datatypes(all_types, context.clone()) else { return };
+
+        if value_types.iter().any(|x| get_reference_target(x).is_some()) { return; }
+
+        let notification = NotificationInfo::new(
+            "Dereferencing Non-Reference".to_string(),
+            format!("A value of type {} can't be dereferenced", describe_datatypes(&value_types)),
+            vec![
+                context.line_map.display_code_for(
+                    self.value.get_position().1,
+                    vec!["*note:* only references can be dereferenced".to_string()],
+                    DisplayCodeKind::InitialError
+                ),
+            ],
+        );
+
+        context.line_map.display_error(notification);
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+
+        let value = self.value.clone().downcast_rc::<ValueNode>().unwrap();
+        let mut value = value.deref().clone();
+
+        value.repeatedly_reset_position();
+        self.value = Rc::new(value);
+    }
+}
+
+/// A [node](Node) taking the address of a value (e.g. `&a`), resulting in a reference to it.
+///
+/// Variables whose address is taken are stored in memory for their entire lifetime.
+/// Any other value (e.g. `&5`) gets copied to the stack first.
+#[derive(Clone, Debug, new)]
+pub struct AddressOfNode {
+    value: Rc<dyn Node>,
+    position: (usize, TokenPosition),
+}
+
+impl AddressOfNode {
+    /// Makes the possible types of the reference known.
+    fn register_datatypes(&self, context: &mut Context) {
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+
+        for datatype in self.get_datatypes(all_types, context.clone()).unwrap_or_default() {
+            context.datatypes.entry(datatype.type_uuid).or_insert(datatype);
+        }
+    }
+}
+
+impl Node for AddressOfNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position
+    }
+
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![self.value.clone()]
+    }
+
+    fn get_datatypes(&self, all_types: Vec<ObjectType>, context: Context) -> Option<Vec<ObjectType>> {
+        let mut value_types = self.value.get_datatypes(all_types, context)?;
+
+        if value_types.is_empty() { return Some(value_types); }
+
+        let preferred = pick_datatype(&value_types).type_uuid;
+        value_types.sort_by_key(|x| x.type_uuid != preferred);
+
+        Some(value_types.iter().map(build_reference_type).collect())
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        self.register_datatypes(context);
+
+        let value = self.value.unpack();
+
+        if let Some(index) = value.downcast_ref::<IndexNode>() {
+            let (instructions, address, _) = index.generate_element_address(context);
+            return (instructions, Some(address));
+        }
+
+        if let Some(field_access) = value.downcast_ref::<FieldAccessNode>() {
+            let (instructions, address, _) = field_access.generate_field_address(context);
+            return (instructions, Some(address));
+        }
+
+        if let Some(dereference) = value.downcast_ref::<DereferenceNode>() {
+            let (instructions, address, _) = dereference.generate_target_address(context);
+            return (instructions, Some(address));
+        }
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let datatype = pick_datatype(&self.value.get_datatypes(all_types, context.clone()).unwrap()).clone();
+        let result = Uuid::new_v4();
+
+        // Arrays and structs are addresses already.
+        if is_aggregate(&datatype) {
+            let (mut instructions, aggregate) = self.value.generate_instructions(context);
+            instructions.push(Instruction::Move(result, aggregate.unwrap()));
+
+            return (instructions, Some(result));
+        }
+
+        if let Some(identifier) = value.downcast_ref::<IdentifierNode>()
+            && let Some(object) = identifier.resolve(context)
+            && context.memory_objects.contains(&object) {
+            let (mut instructions, address) = generate_object_address(object, context);
+            instructions.push(Instruction::Move(result, address));
+
+            return (instructions, Some(result));
+        }
+
+        // Temporary values (and parameters, which can't change anyway) get copied to the stack.
+        let (mut instructions, value_uuid) = generate_value_as(&self.value, &datatype, context);
+
+        instructions.push(Instruction::StackAllocate(result, datatype.get_size().unwrap() as u64));
+        instructions.append(&mut generate_store(value_uuid.unwrap(), result, &datatype));
+
+        (instructions, Some(result))
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        Some(true)
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        self.value.check_types(context);
+        self.register_datatypes(context);
+
+        // Variables need to be stored in memory to have an address.
+        if let Some(identifier) = self.value.unpack().downcast_ref::<IdentifierNode>()
+            && let Some(object) = identifier.resolve(context)
+            && let Some(declaration_position) = context.declaration_positions.get(&object).copied()
+            && !context.addressed_variables.contains(&declaration_position) {
+            context.addressed_variables.push(declaration_position);
+        }
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+
+        let value = self.value.clone().downcast_rc::<ValueNode>().unwrap();
+        let mut value = value.deref().clone();
+
+        value.repeatedly_reset_position();
+        self.value = Rc::new(value);
+    }
+}
+
+/// A [node](Node) creating a struct from its fields (e.g. `Point { x: 1, y: 2 }`).
+///
+/// Just like [array literals](ArrayLiteralNode), the struct lives on the stack of
+/// the current function and its value is its address.
+#[derive(Clone, Debug, new)]
+pub struct StructLiteralNode {
+    /// The uuid of the struct's [type](ObjectType)
+    datatype: Uuid,
+
+    /// The name, value and position of the name of every given field
+    fields: Vec<(String, Rc<dyn Node>, TokenPosition)>,
+
+    position: (usize, TokenPosition),
+}
+
+impl Node for StructLiteralNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position
+    }
+
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        self.fields.iter().map(|x| x.1.clone()).collect()
+    }
+
+    fn get_datatypes(&self, all_types: Vec<ObjectType>, _: Context) -> Option<Vec<ObjectType>> {
+        Some(vec![all_types.into_iter().find(|x| x.type_uuid == self.datatype)?])
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let datatype = context.datatypes[&self.datatype].clone();
+        let struct_fields = get_struct_fields(&datatype).unwrap();
+
+        let result = Uuid::new_v4();
+        let mut instructions = vec![
+            Instruction::StackAllocate(result, datatype.get_size().unwrap() as u64),
+        ];
+
+        for (name, value, _) in &self.fields {
+            let field = struct_fields.iter().find(|x| &x.name == name).unwrap();
+            let field_type = all_types.iter().find(|x| x.type_uuid == field.datatype).unwrap();
+
+            let (mut value_instructions, value_uuid) = generate_value_as(value, field_type, context);
+            let (mut address_instructions, address) = generate_address(result, field.offset);
+
+            instructions.append(&mut value_instructions);
+            instructions.append(&mut address_instructions);
+            instructions.append(&mut generate_store(value_uuid.unwrap(), address, field_type));
+        }
+
+        (instructions, Some(result))
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        Some(true)
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        for (_, value, _) in &self.fields {
+            value.check_types(context);
+        }
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let datatype = context.datatypes[&self.datatype].clone();
+        let struct_fields = get_struct_fields(&datatype).unwrap();
+
+        for (i, (name, value, name_position)) in self.fields.iter().enumerate() {
+            if let Some(first_position) = self.fields[..i].iter().find(|x| &x.0 == name).map(|x| x.2) {
+                let notification = NotificationInfo::new(
+                    "Duplicate Field".to_string(),
+                    format!("The field `{}` is given more than once", name),
+                    vec![
+                        context.line_map.display_code_for(
+                            *name_position,
+                            vec![],
+                            DisplayCodeKind::InitialError
+                        ),
+                        context.line_map.display_code_for(
+                            first_position,
+                            vec!["*note:* first given here".to_string()],
+                            DisplayCodeKind::AdditionalInfo
+                        ),
+                    ],
+                );
+
+                context.line_map.display_error(notification);
+                continue;
+            }
+
+            let Some(field) = struct_fields.iter().find(|x| &x.name == name) else {
+                report_unknown_field(name, &datatype, *name_position, context);
+                continue;
+            };
+
+            let field_type = all_types.iter().find(|x| x.type_uuid == field.datatype).unwrap();
+            let Some(value_types) = value.get_datatypes(all_types.clone(), context.clone()) else { continue };
+
+            if value_types.iter().any(|x| x.type_uuid == field_type.type_uuid) { continue; }
+
+            let notification = NotificationInfo::new(
+                "Mismatched Types".to_string(),
+                format!("The field `{}` is of type `{}`, but the given value is of type {}", name, field_type.name, describe_datatypes(&value_types)),
+                vec![
+                    context.line_map.display_code_for(
+                        value.get_position().1,
+                        vec![format!("*note:* expected `{}`, found {}", field_type.name, describe_datatypes(&value_types))],
+                        DisplayCodeKind::InitialError
+                    ),
+                ],
+            );
+
+            context.line_map.display_error(notification);
+        }
+
+        let missing_fields: Vec<String> = struct_fields.iter()
+            .filter(|x| !self.fields.iter().any(|y| y.0 == x.name))
+            .map(|x| format!("`{}`", x.name))
+            .collect();
+
+        if missing_fields.is_empty() { return; }
+
+        let notification = NotificationInfo::new(
+            "Missing Fields".to_string(),
+            format!("All fields of `{}` have to be given, but {} {} missing", datatype.name, missing_fields.join(", "), if missing_fields.len() == 1 { "is" } else { "are" }),
+            vec![
+                context.line_map.display_code_for(
+                    self.position.1,
+                    vec![format!("*note:* missing {}", missing_fields.join(", "))],
+                    DisplayCodeKind::InitialError
+                ),
+            ],
+        );
+
+        context.line_map.display_error(notification);
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+
+        for (_, value, name_position) in self.fields.iter_mut() {
+            let node = value.clone().downcast_rc::<ValueNode>().unwrap();
+            let mut node = node.deref().clone();
+
+            node.repeatedly_reset_position();
+            *value = Rc::new(node);
+            *name_position = TokenPosition::test_value();
+        }
+    }
+}
+
+/// Reports that the struct type `datatype` doesn't have a field called `name`.
+fn report_unknown_field(name: &str, datatype: &ObjectType, position: TokenPosition, context: &mut Context) {
+    let field_names: Vec<String> = get_struct_fields(datatype).unwrap_or_default().iter().map(|x| format!("`{}`", x.name)).collect();
+
+    let notification = NotificationInfo::new(
+        "Unknown Field".to_string(),
+        format!("`{}` doesn't have a field called `{}`", datatype.name, name),
+        vec![
+            context.line_map.display_code_for(
+                position,
+                vec![format!("*note:* the fields are {}", field_names.join(", "))],
+                DisplayCodeKind::InitialError
+            ),
+        ],
+    );
+
+    context.line_map.display_error(notification);
+}
+
+/// A [node](Node) reading a field of a struct (e.g. `a.x`). References to
+/// structs get dereferenced automatically.
+///
+/// Just like elements of arrays, fields that are arrays or structs themselves
+/// aren't copied, their value is the address inside the outer struct.
+#[derive(Clone, Debug, new)]
+pub struct FieldAccessNode {
+    /// The struct or the reference to it (e.g. **a**.x)
+    value: Rc<dyn Node>,
+
+    /// The name of the field (e.g. a.**x**)
+    field: String,
+    field_position: TokenPosition,
+
+    position: (usize, TokenPosition),
+}
+
+impl FieldAccessNode {
+    /// Gets the struct type of a value, which is either the struct itself or a reference to it.
+    fn get_struct_type(datatype: &ObjectType, all_types: &[ObjectType]) -> Option<ObjectType> {
+        if get_struct_fields(datatype).is_some() {
+            return Some(datatype.clone());
+        }
+
+        get_reference_target_type(datatype, all_types).filter(|x| get_struct_fields(x).is_some())
+    }
+
+    /// Whether the struct is accessed through a reference (e.g. `a.x` where `a` is a `&Point`).
+    fn is_through_reference(&self, context: &Context) -> bool {
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let datatypes = self.value.get_datatypes(all_types, context.clone()).unwrap_or_default();
+
+        datatypes.iter().any(|x| get_reference_target(x).is_some())
+    }
+
+    /// Generates the address of the field and gets its type. Both structs
+    /// and references to them are the address of the struct.
+    fn generate_field_address(&self, context: &mut Context) -> (Vec<Instruction>, Uuid, ObjectType) {
+        let (mut instructions, base) = self.value.generate_instructions(context);
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let datatypes = self.value.get_datatypes(all_types.clone(), context.clone()).unwrap();
+        let struct_type = datatypes.iter().find_map(|x| Self::get_struct_type(x, &all_types)).unwrap();
+
+        let field = get_struct_fields(&struct_type).unwrap().into_iter().find(|x| x.name == self.field).unwrap();
+        let field_type = all_types.iter().find(|x| x.type_uuid == field.datatype).unwrap().clone();
+
+        let (mut address_instructions, address) = generate_address(base.unwrap(), field.offset);
+        instructions.append(&mut address_instructions);
+
+        (instructions, address, field_type)
+    }
+}
+
+impl Node for FieldAccessNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position
+    }
+
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![self.value.clone()]
+    }
+
+    fn get_datatypes(&self, all_types: Vec<ObjectType>, context: Context) -> Option<Vec<ObjectType>> {
+        let value_types = self.value.get_datatypes(all_types.clone(), context)?;
+        let mut field_types: Vec<ObjectType> = vec![];
+
+        for struct_type in value_types.iter().filter_map(|x| Self::get_struct_type(x, &all_types)) {
+            let Some(field) = get_struct_fields(&struct_type)?.into_iter().find(|x| x.name == self.field) else { continue };
+            let Some(field_type) = all_types.iter().find(|x| x.type_uuid == field.datatype) else { continue };
+
+            if !field_types.iter().any(|x| x.type_uuid == field_type.type_uuid) {
+                field_types.push(field_type.clone());
+            }
+        }
+
+        Some(field_types)
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let (mut instructions, address, field_type) = self.generate_field_address(context);
+        let (mut load, result) = generate_load(address, &field_type);
+
+        instructions.append(&mut load);
+
+        (instructions, Some(result))
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        // Nested arrays and structs point into the accessed struct.
+        Some(false)
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        self.value.check_types(context);
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let Some(value_types) = self.value.get_datatypes(all_types.clone(), context.clone()) else { return };
+
+        let Some(struct_type) = value_types.iter().find_map(|x| Self::get_struct_type(x, &all_types)) else {
+            let notification = NotificationInfo::new(
+                "Accessing Field Of Non-Struct".to_string(),
+                format!("A value of type {} doesn't have any fields", describe_datatypes(&value_types)),
+                vec![
+                    context.line_map.display_code_for(
+                        self.value.get_position().1,
+                        vec!["*note:* only structs (and references to them) have fields".to_string()],
+                        DisplayCodeKind::InitialError
+                    ),
+                ],
+            );
+
+            context.line_map.display_error(notification);
+            return;
+        };
+
+        if get_struct_fields(&struct_type).unwrap().iter().any(|x| x.name == self.field) { return; }
+
+        report_unknown_field(&self.field, &struct_type, self.field_position, context);
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+        self.field_position = TokenPosition::test_value();
+
+        let value = self.value.clone().downcast_rc::<ValueNode>().unwrap();
+        let mut value = value.deref().clone();
+
+        value.repeatedly_reset_position();
+        self.value = Rc::new(value);
+    }
+}
+
+#[derive(Clone, Debug, new)]
+pub struct AssignmentNode {
+    left_side: Rc<IdentifierNode>,
+    right_side: Rc<dyn Node>,
+    position: (usize, TokenPosition),
+}
+
+
+impl Node for AssignmentNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position.clone()
+    }
+    
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+    
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![]
+    }
+    
+    fn get_datatypes(&self, _types: Vec<ObjectType>, _context: Context) -> Option<Vec<ObjectType>> {
+        None
+    }
+    
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let mut instructions: Vec<Instruction> = vec![];
+
+        // The object itself is changed, so variables in memory mustn't be loaded.
+        let left_side = self.left_side.resolve(context);
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let left_type = self.left_side.get_datatypes(all_types, context.clone()).and_then(|x| x.first().cloned());
+        let mut right_side = match &left_type {
+            Some(datatype) => generate_value_as(&self.right_side, datatype, context),
+            None => self.right_side.generate_instructions(context),
+        };
+
+        instructions.append(right_side.0.as_mut());
+
+        // Unknown and immutable variables are reported while checking the types.
+        let Some(object) = left_side else { return (instructions, None) };
+
+        // Arrays, structs and variables in memory are addresses, so the value has to be stored there instead.
+        if let Some(datatype) = left_type
+            && (is_aggregate(&datatype) || context.memory_objects.contains(&object)) {
+            let (mut address_instructions, address) = generate_object_address(object, context);
+
+            instructions.append(&mut address_instructions);
+            instructions.append(&mut generate_store(right_side.1.unwrap(), address, &datatype));
+
+            return (instructions, None);
+        }
+
+        instructions.push(Instruction::Move(object, right_side.1.unwrap()));
+
+
+        (instructions, None)
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        None
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        self.left_side.check_types(context);
+        self.right_side.check_types(context);
+
+        if let Some(object) = self.left_side.resolve(context)
+            && !context.mutable_objects.contains(&object) {
+            report_immutable_modification(&self.left_side.identifier, object, self.position.1, context);
+        }
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+        let Some(left_types) = self.left_side.get_datatypes(all_types.clone(), context.clone()) else { return };
+        let Some(right_types) = self.right_side.get_datatypes(all_types, context.clone()) else { return };
+
+        if datatypes_overlap(&left_types, &right_types) { return; }
+
+        let notification = NotificationInfo::new(
+            "Mismatched Types".to_string(),
+            format!("`{}` is of type {}, but the assigned value is of type {}", self.left_side.identifier, describe_datatypes(&left_types), describe_datatypes(&right_types)),
+            vec![
+                context.line_map.display_code_for(
+                    self.right_side.get_position().1,
+                    vec![format!("*note:* expected {}, found {}", describe_datatypes(&left_types), describe_datatypes(&right_types))],
+                    DisplayCodeKind::InitialError
+                ),
+            ],
+        );
+
+        context.line_map.display_error(notification);
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+
+        let mut left_side = self.left_side.deref().clone();
+        left_side.repeatedly_reset_position();
+        self.left_side = Rc::new(left_side);
+
+        let value = self.right_side.clone().downcast_rc::<ValueNode>().unwrap();
+        let mut value = value.deref().clone();
+
+        value.repeatedly_reset_position();
+        self.right_side = Rc::new(value);
+    }
+}
+
+#[derive(Clone, Debug, new)]
+pub struct AssignmentSymbolNode {
+    position: (usize, TokenPosition),
+}
+
+
+impl Node for AssignmentSymbolNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position.clone()
+    }
+
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![]
+    }
+
+    fn get_datatypes(&self, _: Vec<ObjectType>, _: Context) -> Option<Vec<ObjectType>> {
+        None
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn generate_instructions(&self, _context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        todo!()
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        None
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+    }
+}
+
+/// Warns that the variable declared at the position has the name of another one, which can't be used anymore.
+fn report_shadowing(identifier: &str, position: TokenPosition, shadowed: Uuid, context: &mut Context) {
+    let mut display_code = vec![
+        context.line_map.display_code_for(
+            position,
+            vec!["*hint:* rename the variable if the other one is still needed".to_string()],
+            DisplayCodeKind::InitialWarning
+        ),
+    ];
+
+    if let Some(declaration) = context.declaration_positions.get(&shadowed) {
+        display_code.push(context.line_map.display_code_for(
+            declaration.1,
+            vec!["*note:* previously declared here".to_string()],
+            DisplayCodeKind::AdditionalInfo
+        ));
+    }
+
+    let notification = NotificationInfo::new(
+        "Shadowed Variable".to_string(),
+        format!("`{}` shadows an earlier declaration with the same name", identifier),
+        display_code,
+    );
+
+    context.line_map.display_warning(notification);
+}
+
+#[derive(Clone, Debug, new)]
+pub struct LetNode {
+    identifier: String,
+    assigned_value: Option<Rc<dyn Node>>,
+    is_mutable: bool,
+
+    /// The explicitly annotated type (e.g. `let a: u32 = ...`) if given.
+    type_annotation: Option<TypeAnnotationNode>,
+
+    position: (usize, TokenPosition),
+}
+
+impl LetNode {
+    /// Picks the type of the declared object, which is the annotated type
+    /// if there is one.
+    fn resolve_datatype(&self, value: &Rc<dyn Node>, context: &Context) -> Uuid {
+        if let Some(annotation) = self.type_annotation.clone() {
+            return annotation.datatype;
+        }
+
+        let datatypes = value.get_datatypes(context.datatypes.values().cloned().collect(), context.clone()).unwrap();
+
+        pick_datatype(&datatypes).type_uuid
+    }
+}
+
+impl Node for LetNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position.clone()
+    }
+
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![]
+    }
+
+    fn get_datatypes(&self, _: Vec<ObjectType>, _: Context) -> Option<Vec<ObjectType>> {
+        None
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let mut instructions: Vec<Instruction> = vec![];
+        let mut result_uuid: Option<Uuid> = None;
+
+        if let Some(assigned_value) = self.assigned_value.clone() {
+            let annotated_type = self.type_annotation.as_ref().and_then(|x| context.datatypes.get(&x.datatype).cloned());
+            let assignment_result = match &annotated_type {
+                Some(datatype) => generate_value_as(&assigned_value, datatype, context),
+                None => assigned_value.clone().generate_instructions(context),
+            };
+            println!("assigned value: {assigned_value:?} created: {assignment_result:?}");
+
+
+            let mut assignment_instructions = assignment_result.0;
+            instructions.append(&mut assignment_instructions);
+
+            let datatype = self.resolve_datatype(&assigned_value, context);
+            let aggregate_size = context.datatypes.get(&datatype).filter(|x| is_aggregate(x)).and_then(|x| x.get_size());
+
+            let is_addressed = context.addressed_variables.contains(&self.position);
+
+            if is_addressed && aggregate_size.is_none() {
+                // The address of the variable is taken, so it has to live in memory.
+                let datatype = context.datatypes.get(&datatype).unwrap().clone();
+
+                result_uuid = Some(Uuid::new_v4());
+                instructions.push(Instruction::StackAllocate(result_uuid.unwrap(), datatype.get_size().unwrap() as u64));
+                instructions.append(&mut generate_store(assignment_result.1.unwrap(), result_uuid.unwrap(), &datatype));
+                context.memory_objects.push(result_uuid.unwrap());
+            } else if assigned_value.output_is_randomly_mutable() == Some(true) {
+                result_uuid = assignment_result.1;
+            } else if let Some(aggregate_size) = aggregate_size {
+                // Arrays and structs are addresses, so the declared variable needs its own copy.
+                result_uuid = Some(Uuid::new_v4());
+                instructions.push(Instruction::StackAllocate(result_uuid.unwrap(), aggregate_size as u64));
+                instructions.append(&mut generate_memory_copy(result_uuid.unwrap(), assignment_result.1.unwrap(), aggregate_size));
+            } else {
+                result_uuid = Some(Uuid::new_v4());
+                instructions.push(Instruction::Move(result_uuid.unwrap(), assignment_result.1.unwrap()));
+            }
+        }
+
+        if result_uuid.is_none() {
+            // If there's no uuid, one needs to be assigned still
+            result_uuid = Some(Uuid::new_v4());
+        }
+
+        let value = self.assigned_value.clone().unwrap();
+        let datatype = self.resolve_datatype(&value, context);
+
+        context.objects.insert(result_uuid.unwrap(), datatype);
+        context.declare(self.identifier.clone(), result_uuid.unwrap());
+        context.declaration_positions.insert(result_uuid.unwrap(), self.position);
+
+        if self.is_mutable {
+            context.mutable_objects.push(result_uuid.unwrap());
+        }
+
+
+        (instructions, None)
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        None
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        let value = self.assigned_value.clone().unwrap();
+        value.check_types(context);
+
+        // Several return values have to be received by as many names (e.g. `let (q, r) = divide(a, b);`).
+        if let Some(call) = value.unpack().downcast_ref::<FunctionCallNode>()
+            && let Some(function) = context.resolve_function(&call.name, call.position.0)
+            && function.return_types.len() > 1 {
+            let notification = NotificationInfo::new(
+                "Wrong Amount of Names".to_string(),
+                format!("Function '{}' returns {} values, but one name was given.", call.name, function.return_types.len()),
+                vec![context.line_map.display_code_for(self.position.1, vec!["*hint:* list a name for each value in parentheses".to_string()], DisplayCodeKind::InitialError)],
+            );
+
+            context.line_map.display_error(notification);
+        }
+
+        // If there's a type annotation, the assigned value has to be compatible with it.
+        if let Some(annotation) = self.type_annotation.clone()
+            && let Some(datatypes) = value.get_datatypes(context.datatypes.values().cloned().collect(), context.clone())
+            && !datatypes.iter().any(|x| x.type_uuid == annotation.datatype) {
+            let expected_name = context.datatypes.get(&annotation.datatype).map(|x| x.name.clone()).unwrap_or_default();
+            let found_names = describe_datatypes(&datatypes);
+
+            // Integer literals only get the types they fit into, so a mismatch means they're out of range.
+            let (title, message) = match (value.unpack().downcast_ref::<IntegerLiteralNode>(), IntegerType::from_code_name(&expected_name)) {
+                (Some(literal), Some(expected_type)) if literal.kind.is_none() => (
+                    "Integer Literal Out Of Range",
+                    format!("`{}` doesn't fit into `{}` ({})", literal.content, expected_name, expected_type.describe_range())
+                ),
+                _ => (
+                    "Mismatched Types",
+                    format!("`{}` was declared as `{}`, but the assigned value is of type {}", self.identifier, expected_name, found_names)
+                ),
+            };
+
+            let notification = NotificationInfo::new(
+                title.to_string(),
+                message,
+                vec![
+                    context.line_map.display_code_for(
+                        value.get_position().1,
+                        vec![format!("*note:* expected `{}`, found {}", expected_name, found_names)],
+                        DisplayCodeKind::InitialError
+                    ),
+                    context.line_map.display_code_for(
+                        annotation.position.1,
+                        vec!["*note:* type annotated here".to_string()],
+                        DisplayCodeKind::AdditionalInfo
+                    ),
+                ],
+            );
+
+            context.line_map.display_error(notification);
+        }
+
+        // Register the object, so following nodes know its type.
+        // An annotated type is used even after a mismatch to prevent follow-up errors.
+        let object_uuid = Uuid::new_v4();
+        let is_typed = self.type_annotation.is_some()
+            || value.get_datatypes(context.datatypes.values().cloned().collect(), context.clone()).is_some_and(|x| !x.is_empty());
+
+        if is_typed {
+            let datatype = self.resolve_datatype(&value, context);
+            context.objects.insert(object_uuid, datatype);
+        }
+
+        // Without a type, the name is still known, so its uses aren't reported as unknown.
+        let shadowed = context.declare(self.identifier.clone(), object_uuid);
+        context.declaration_positions.insert(object_uuid, self.position);
+
+        if self.is_mutable {
+            context.mutable_objects.push(object_uuid);
+        }
+
+        if context.options.warn_shadowing && let Some(shadowed) = shadowed {
+            report_shadowing(&self.identifier, self.position.1, shadowed, context);
+        }
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+
+        if self.assigned_value.is_none() { return }
+        todo!()
+        //self.assigned_value.unwrap().repeatedly_reset_position();
+    }
+}
+
+/// A [node](Node) declaring a variable for each value a function returns
+/// (e.g. `let (quotient, remainder) = divide(a, b);`).
+#[derive(Clone, Debug, new)]
+pub struct DestructuringLetNode {
+    names: Vec<IdentifierNode>,
+    value: Rc<dyn Node>,
+    is_mutable: bool,
+    position: (usize, TokenPosition),
+}
+
+impl DestructuringLetNode {
+    /// The types of the values the called function returns, if the value calls a known function.
+    fn get_return_types(&self, context: &Context) -> Option<Vec<Uuid>> {
+        let call = self.value.clone().downcast_rc::<FunctionCallNode>().ok()?;
+
+        Some(context.resolve_function(&call.name, call.position.0)?.return_types.clone())
+    }
+}
+
+impl Node for DestructuringLetNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position
+    }
+
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![]
+    }
+
+    fn get_datatypes(&self, _: Vec<ObjectType>, _: Context) -> Option<Vec<ObjectType>> {
+        None
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let call = self.value.clone().downcast_rc::<FunctionCallNode>().unwrap();
+        let return_types = self.get_return_types(context).unwrap();
+        let (mut instructions, results) = call.generate_call(context);
+
+        for ((name, result), type_uuid) in self.names.iter().zip(results).zip(return_types) {
+            let mut object = result;
+
+            // The address of the variable is taken, so it has to live in memory.
+            if context.addressed_variables.contains(&name.position) {
+                let datatype = context.datatypes.get(&type_uuid).unwrap().clone();
+
+                object = Uuid::new_v4();
+                instructions.push(Instruction::StackAllocate(object, datatype.get_size().unwrap() as u64));
+                instructions.append(&mut generate_store(result, object, &datatype));
+                context.memory_objects.push(object);
+            }
+
+            context.objects.insert(object, type_uuid);
+            context.declare(name.identifier.clone(), object);
+            context.declaration_positions.insert(object, name.position);
+
+            if self.is_mutable {
+                context.mutable_objects.push(object);
+            }
+        }
+
+        (instructions, None)
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        None
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        self.value.check_types(context);
+
+        let return_types = self.get_return_types(context);
+
+        if self.value.clone().downcast_rc::<FunctionCallNode>().is_err() {
+            let notification = NotificationInfo::new(
+                "Expected a Function Call".to_string(),
+                "Several names can only receive the values returned by a function".to_string(),
+                vec![context.line_map.display_code_for(self.value.get_position().1, vec![], DisplayCodeKind::InitialError)],
+            );
+
+            context.line_map.display_error(notification);
+        } else if let Some(return_types) = return_types.as_ref().filter(|x| x.len() != self.names.len()) {
+            let call = self.value.clone().downcast_rc::<FunctionCallNode>().unwrap();
+
+            let notification = NotificationInfo::new(
+                "Wrong Amount of Names".to_string(),
+                format!("Function '{}' returns {} value(s), but {} names were given.", call.name, return_types.len(), self.names.len()),
+                vec![context.line_map.display_code_for(self.position.1, vec![], DisplayCodeKind::InitialError)],
+            );
+
+            context.line_map.display_error(notification);
+        }
+
+        // Without a type, the names are still known, so their uses aren't reported as unknown.
+        for (i, name) in self.names.iter().enumerate() {
+            let object_uuid = Uuid::new_v4();
+
+            if let Some(type_uuid) = return_types.as_ref().and_then(|x| x.get(i)) {
+                context.objects.insert(object_uuid, *type_uuid);
+            }
+
+            let shadowed = context.declare(name.identifier.clone(), object_uuid);
+            context.declaration_positions.insert(object_uuid, name.position);
+
+            if self.is_mutable {
+                context.mutable_objects.push(object_uuid);
+            }
+
+            if context.options.warn_shadowing && let Some(shadowed) = shadowed {
+                report_shadowing(&name.identifier, name.position.1, shadowed, context);
+            }
+        }
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+    }
+}
+
+/// A [node](Node) declaring a global value, either a constant (`const`) or a
+/// static (`static`). Both need a type annotation and a value known at compile time.
+///
+/// Constants are inserted wherever they're used. Statics are placed into the data
+/// section under their own symbol and can be modified by all functions.
+#[derive(Clone, Debug, new)]
+pub struct GlobalDeclarationNode {
+    identifier: String,
+    value: Rc<dyn Node>,
+    is_constant: bool,
+    type_annotation: TypeAnnotationNode,
+    position: (usize, TokenPosition),
+}
+
+impl GlobalDeclarationNode {
+    /// Makes the declared object known. Statics live in memory and can be
+    /// found by their symbol, constants only have their value.
+    fn register_object(&self, value: i128, context: &mut Context) -> Uuid {
+        let object_uuid = Uuid::new_v4();
+        let full_name = context.qualify(&self.identifier, self.position.0);
+
+        context.objects.insert(object_uuid, self.type_annotation.datatype);
+        context.name_map.insert(full_name.clone(), object_uuid);
+        context.declaration_positions.insert(object_uuid, self.position);
+
+        if self.is_constant {
+            context.constant_values.insert(object_uuid, value);
+        } else {
+            context.mutable_objects.push(object_uuid);
+            context.memory_objects.push(object_uuid);
+            context.global_symbols.insert(object_uuid, Rc::new(format!("_{}", full_name.replace("::", "."))));
+        }
+
+        object_uuid
+    }
+
+    fn report_error(&self, title: &str, message: String, note: String, context: &mut Context) {
+        let notification = NotificationInfo::new(
+            title.to_string(),
+            message,
+            vec![
+                context.line_map.display_code_for(
+                    self.value.get_position().1,
+                    vec![note],
+                    DisplayCodeKind::InitialError
+                ),
+                context.line_map.display_code_for(
+                    self.type_annotation.position.1,
+                    vec!["*note:* type annotated here".to_string()],
+                    DisplayCodeKind::AdditionalInfo
+                ),
+            ],
+        );
+
+        context.line_map.display_error(notification);
+    }
+}
+
+impl Node for GlobalDeclarationNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position
+    }
+
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![]
+    }
+
+    fn get_datatypes(&self, _: Vec<ObjectType>, _: Context) -> Option<Vec<ObjectType>> {
+        None
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let value = self.value.evaluate_constant(context).unwrap_or_default();
+        let object_uuid = self.register_object(value, context);
+
+        if self.is_constant {
+            return (vec![], None);
+        }
+
+        let size = context.datatypes.get(&self.type_annotation.datatype).and_then(|x| x.get_size()).unwrap_or(8);
+        let bytes = value.to_le_bytes()[..size].to_vec();
+
+        (vec![Instruction::StaticData(context.global_symbols[&object_uuid].clone(), bytes, size as u8)], None)
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        None
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        self.value.check_types(context);
+
+        let expected = context.datatypes.get(&self.type_annotation.datatype).cloned().unwrap();
+        let integer_type = IntegerType::from_code_name(&expected.name);
+        let value = self.value.evaluate_constant(context);
+
+        let kind = if self.is_constant { "constants" } else { "statics" };
+
+        if integer_type.is_none() && !expected.has_trait(Trait::BOOLEAN_COMPATIBLE) && get_enum_variants(&expected).is_none() {
+            self.report_error(
+                "Unsupported Global Type",
+                format!("The type of {} has to be an integer, a bool or an enum, but `{}` is `{}`", kind, self.identifier, expected.name),
+                format!("*note:* `{}` isn't supported", expected.name),
+                context
+            );
+        } else if value.is_none() {
+            self.report_error(
+                "Non-Constant Initializer",
+                format!("The value of {} has to be known at compile time", kind),
+                "*note:* this can't be calculated while compiling".to_string(),
+                context
+            );
+        } else if let Some(integer_type) = integer_type
+            && !integer_type.contains(value.unwrap()) {
+            self.report_error(
+                "Value Out Of Range",
+                format!("`{}` doesn't fit into `{}` ({})", value.unwrap(), expected.name, integer_type.describe_range()),
+                format!("*note:* evaluates to `{}`", value.unwrap()),
+                context
+            );
+        } else if let Some(datatypes) = self.value.get_datatypes(context.datatypes.values().cloned().collect(), context.clone())
+            && !datatypes.iter().any(|x| x.type_uuid == expected.type_uuid) {
+            let found_names = describe_datatypes(&datatypes);
+
+            self.report_error(
+                "Mismatched Types",
+                format!("`{}` was declared as `{}`, but the assigned value is of type {}", self.identifier, expected.name, found_names),
+                format!("*note:* expected `{}`, found {}", expected.name, found_names),
+                context
+            );
+        }
+
+        self.register_object(value.unwrap_or_default(), context);
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+    }
+}
+
+/// A node containing multiple lines of code. Note that this node is always an expression,
+/// never a statement. Use with caution.
+#[derive(Clone, Debug, new)]
+pub struct CodeBlockNode {
+    position: (usize, TokenPosition),
+    pub label: Option<Rc<String>>,
+    code: Vec<Rc<dyn Node>>,
+}
+
+impl CodeBlockNode {
+    pub fn push_code(&mut self, code: Rc<dyn Node>) {
+        self.code.push(code);
+    }
     
+    pub fn append_code(&mut self, code: Vec<Rc<dyn Node>>) {
+        self.code.append(&mut code.clone());
+    }
+
+    pub fn assign_label(&mut self, context: &mut Context) -> Rc<String> {
+        let name: Rc<String> = {if let Some(label) = self.label.clone() { label.clone() } else { context.generate_label() }};
+
+        self.label = Some(name.clone());
+
+        name
+    }
+
+    /// The variables declared directly in the block (not in nested ones), see [Scope](crate::compiler::backend::context::Scope).
+    fn get_declarations(&self) -> Vec<(String, (usize, TokenPosition))> {
+        self.code.iter()
+            .flat_map(|x| {
+                if let Some(declaration) = x.downcast_ref::<DestructuringLetNode>() {
+                    return declaration.names.iter().map(|x| (x.identifier.clone(), x.position)).collect();
+                }
+
+                x.downcast_ref::<LetNode>().map(|x| (x.identifier.clone(), x.position)).into_iter().collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+impl Node for CodeBlockNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position.clone()
+    }
+
+    fn get_future(&self, _current: CodeFuture) -> CodeFuture {
+        todo!()
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        self.code.clone()
+    }
+
+    fn get_datatypes(&self, _all_types: Vec<ObjectType>, _: Context) -> Option<Vec<ObjectType>> {
+        None
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        todo!()
+    }
+
+    fn perform_early_context_changes(&mut self, context: &mut Context) {
+        for i in 0..self.code.iter().len() {
+            if let Ok(declaration) = self.code[i].clone().downcast_rc::<ExternFunctionDeclarationNode>() {
+                declaration.deref().clone().perform_early_context_changes(context);
+                continue;
+            }
+
+            let clone = self.code[i].clone().downcast_rc::<FunctionDeclarationNode>();
+
+            if clone.is_err() {
+                continue;
+            }
+
+            let mut clone = clone.unwrap().deref().clone();
+            clone.perform_early_context_changes(context);
+            self.code[i] = Rc::new(clone);
+        }
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let mut copy = self.clone();
+        let name: Rc<String> = copy.assign_label(context);
+        
+        let mut instructions: Vec<Instruction> = vec![];
+
+        instructions.push(Instruction::Label(name, false));
+        context.enter_scope(self.get_declarations());
+
+        for code in self.code.iter() {
+            instructions.append(code.generate_instructions(context).0.as_mut());
+        }
+
+        context.exit_scope();
+
+        (instructions, None)
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        None
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        context.enter_scope(self.get_declarations());
+
+        for code in self.code.iter() {
+            code.check_types(context);
+        }
+
+        context.exit_scope();
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+    }
+}
+
+
+/// A node that exits the process. No dark web stuff.
+#[derive(Debug, new)]
+pub struct ExitNode {
+    return_value: Rc<dyn Node>,
+    position: (usize, TokenPosition),
+}
+
+
+impl Node for ExitNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position.clone()
+    }
+
+    fn get_future(&self, _current: CodeFuture) -> CodeFuture {
+        CodeFuture::Never
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![]
+    }
+
+    fn get_datatypes(&self, _all_types: Vec<ObjectType>, _: Context) -> Option<Vec<ObjectType>> {
+        None
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        todo!()
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let mut instructions: Vec<Instruction> = vec![];
+        let mut return_value = self.return_value.generate_instructions(context);
+
+        instructions.append(return_value.0.as_mut());
+
+        instructions.push(Instruction::Exit(return_value.1.unwrap()));
+
+        (instructions, None)
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        None
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        self.return_value.check_types(context);
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+    }
+}
+
+/// A [node](Node) leaving the current function, handing the values to the caller
+/// (e.g. `return quotient, remainder;`).
+#[derive(Debug, new)]
+pub struct ReturnNode {
+    values: Vec<Rc<dyn Node>>,
+    position: (usize, TokenPosition),
+}
+
+impl Node for ReturnNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position
+    }
+
+    fn get_future(&self, _current: CodeFuture) -> CodeFuture {
+        CodeFuture::Never
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![]
+    }
+
+    fn get_datatypes(&self, _all_types: Vec<ObjectType>, _: Context) -> Option<Vec<ObjectType>> {
+        None
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        todo!()
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let function_meta = context.current_function.clone().unwrap();
+        let mut instructions: Vec<Instruction> = vec![];
+        let mut values: Vec<Uuid> = vec![];
+
+        for (value, type_uuid) in self.values.iter().zip(function_meta.return_types.iter()) {
+            let datatype = context.datatypes.get(type_uuid).unwrap().clone();
+            let (mut value_instructions, result) = generate_value_as(value, &datatype, context);
+
+            instructions.append(&mut value_instructions);
+
+            // Structs are returned like they're passed, unless they're too large for registers.
+            // Those get copied to the memory the caller provided.
+            if get_struct_fields(&datatype).is_some() {
+                if let Some(destination) = context.indirect_result {
+                    instructions.append(&mut generate_memory_copy(destination, result.unwrap(), datatype.get_size().unwrap()));
+                } else {
+                    let (mut part_instructions, mut parts) = generate_struct_argument(result.unwrap(), &datatype);
+
+                    instructions.append(&mut part_instructions);
+                    values.append(&mut parts);
+                }
+            } else if value.output_is_randomly_mutable() == Some(true) {
+                values.push(result.unwrap());
+            } else {
+                let copy = Uuid::new_v4();
+                instructions.push(Instruction::Move(copy, result.unwrap()));
+                values.push(copy);
+            }
+        }
+
+        instructions.push(Instruction::Return(values, function_meta.assembly_style));
+
+        (instructions, None)
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        None
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        for value in self.values.iter() {
+            value.check_types(context);
+        }
+
+        let Some(function_meta) = context.current_function.clone() else {
+            let notification = NotificationInfo::new(
+                "Return Outside of a Function".to_string(),
+                "Only functions can be returned from".to_string(),
+                vec![context.line_map.display_code_for(self.position.1, vec![], DisplayCodeKind::InitialError)],
+            );
+
+            context.line_map.display_error(notification);
+            return;
+        };
+
+        if self.values.len() != function_meta.return_types.len() {
+            let notification = NotificationInfo::new(
+                "Wrong Amount of Return Values".to_string(),
+                format!("Function '{}' returns {} value(s), but {} were given.", function_meta.code_name, function_meta.return_types.len(), self.values.len()),
+                vec![context.line_map.display_code_for(self.position.1, vec![], DisplayCodeKind::InitialError)],
+            );
+
+            context.line_map.display_error(notification);
+            return;
+        }
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+
+        for (value, expected) in self.values.iter().zip(function_meta.return_types.iter()) {
+            let Some(datatypes) = value.get_datatypes(all_types.clone(), context.clone()) else { continue };
+
+            if datatypes.iter().any(|x| x.type_uuid == *expected) { continue; }
+
+            let expected_name = context.datatypes.get(expected).map(|x| x.name.clone()).unwrap_or_default();
+            let found_names = describe_datatypes(&datatypes);
+
+            let notification = NotificationInfo::new(
+                "Mismatched Return Type".to_string(),
+                format!("Function '{}' returns `{}`, but the value is of type {}.", function_meta.code_name, expected_name, found_names),
+                vec![
+                    context.line_map.display_code_for(
+                        value.get_position().1,
+                        vec![format!("*note:* expected `{}`, found {}", expected_name, found_names)],
+                        DisplayCodeKind::InitialError
+                    ),
+                ],
+            );
+
+            context.line_map.display_error(notification);
+        }
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+    }
+}
+
+/// ### Checks the Types a Function Returns
+///
+/// Every value is returned in its own register, so there can't be more of them than the
+/// calling convention has registers for. C functions return at most one value.
+fn check_return_types(name: &str, style: FunctionStyle, return_types: &[TypeAnnotationNode], context: &mut Context) {
+    let max_return_values = context.options.target.generate_architecture().get_max_return_values(style);
+
+    if return_types.len() > max_return_values {
+        let position = TokenPosition::spanning(return_types[0].position.1, return_types.last().unwrap().position.1);
+
+        let notification = NotificationInfo::new(
+            "Too Many Return Values".to_string(),
+            format!("Function '{}' returns {} values, but its calling convention allows at most {}.", name, return_types.len(), max_return_values),
+            vec![context.line_map.display_code_for(position, vec![], DisplayCodeKind::InitialError)],
+        );
+
+        context.line_map.display_error(notification);
+    }
+}
+
+/// The largest struct (in bytes) that gets passed in registers according to the C ABI.
+const MAX_STRUCT_SIZE_IN_REGISTERS: usize = 16;
+
+/// ### Gets the Registers a Struct Is Passed In
+///
+/// Following the C ABI, structs of up to 16 bytes are passed and returned in (up to two)
+/// registers, each one holding the next 8 bytes. Larger ones get copied by the caller and
+/// are passed by reference instead, and they're returned in memory whose address the caller
+/// passes along. Returns the size of each part if the struct is passed in registers.
+///
+/// **Note:** The last part gets rounded up to a power of two, which doesn't leave the memory
+/// of the struct, as memory is allocated in multiples of 8 bytes.
+fn get_struct_register_parts(datatype: &ObjectType) -> Option<Vec<usize>> {
+    get_struct_fields(datatype)?;
+
+    let size = datatype.get_size()?;
+
+    if size > MAX_STRUCT_SIZE_IN_REGISTERS {
+        return None;
+    }
+
+    Some((0..size).step_by(8).map(|offset| (size - offset).min(8).next_power_of_two()).collect())
+}
+
+/// Generates the objects a struct gets passed to a function in (see [get_struct_register_parts]).
+fn generate_struct_argument(value: Uuid, datatype: &ObjectType) -> (Vec<Instruction>, Vec<Uuid>) {
+    let Some(parts) = get_struct_register_parts(datatype) else {
+        let copy = Uuid::new_v4();
+        let size = datatype.get_size().unwrap();
+
+        return (
+            [vec![Instruction::StackAllocate(copy, size as u64)], generate_memory_copy(copy, value, size)].concat(),
+            vec![copy]
+        );
+    };
+
+    let mut instructions = vec![];
+    let mut objects = vec![];
+
+    for (i, part_size) in parts.into_iter().enumerate() {
+        let part = Uuid::new_v4();
+        let (mut address_instructions, address) = generate_address(value, i * 8);
+
+        instructions.append(&mut address_instructions);
+        instructions.push(Instruction::Load(part, address, part_size as u8, false));
+        objects.push(part);
+    }
+
+    (instructions, objects)
+}
+
+#[derive(Clone, Debug, new)]
+pub struct FunctionCallNode {
+    name: Rc<String>,
+    arguments: Vec<Rc<dyn Node>>,
+    position: (usize, TokenPosition),
+}
+
+impl FunctionCallNode {
+    /// Generates the call, resulting in an object for each value the function returns.
+    fn generate_call(&self, context: &mut Context) -> (Vec<Instruction>, Vec<Uuid>) {
+        let function_meta = context.resolve_function(&self.name, self.position.0).cloned().unwrap();
+        let asm_fn_name = function_meta.assembly_name.clone();
+
+        let mut results: Vec<Uuid> = vec![];
+        let mut return_uuids: Vec<Uuid> = vec![];
+        let mut result_memory: Vec<Instruction> = vec![];
+        let mut struct_result: Vec<Instruction> = vec![];
+        let mut indirect_result: Option<Uuid> = None;
+
+        let return_types: Vec<ObjectType> = function_meta.return_types.iter().map(|x| context.datatypes[x].clone()).collect();
+
+        if let [return_type] = &return_types[..] && get_struct_fields(return_type).is_some() {
+            let result = Uuid::new_v4();
+            results.push(result);
+            result_memory.push(Instruction::StackAllocate(result, return_type.get_size().unwrap() as u64));
+
+            // Structs are returned just like they're passed, but they're stored right away. Those
+            // too large for registers are written to the memory whose address gets passed along.
+            let parts = get_struct_register_parts(return_type).unwrap_or_default();
+
+            if parts.is_empty() {
+                let address = Uuid::new_v4();
+                result_memory.push(Instruction::Move(address, result));
+                indirect_result = Some(address);
+            }
+
+            for (i, part_size) in parts.into_iter().enumerate() {
+                let part = Uuid::new_v4();
+                let (mut address_instructions, address) = generate_address(result, i * 8);
+
+                return_uuids.push(part);
+                struct_result.append(&mut address_instructions);
+                struct_result.push(Instruction::Store(part, address, part_size as u8));
+            }
+        } else {
+            return_uuids = return_types.iter().map(|_| Uuid::new_v4()).collect();
+            results = return_uuids.clone();
+        }
+
+        let mut args: Vec<Uuid> = vec![];
+        let mut instructions: Vec<Instruction> = vec![];
+        let mut moves: Vec<(Uuid, Uuid)> = vec![];
+
+        let expected_args = function_meta.arguments.len();
+        let actual_args = self.arguments.len();
+
+        for (arg, parameter) in self.arguments.iter().zip(function_meta.arguments.iter()) {
+            let arg_result = arg.generate_instructions(context);
+
+            instructions.append(&mut arg_result.0.clone());
+
+            // Structs are passed by value, split up into registers or as a copy.
+            if let Some(datatype) = context.datatypes.get(&parameter.type_uuid).cloned()
+                && get_struct_fields(&datatype).is_some() {
+                let (mut struct_instructions, mut objects) = generate_struct_argument(arg_result.1.unwrap(), &datatype);
+
+                instructions.append(&mut struct_instructions);
+                args.append(&mut objects);
+            } else if arg.output_is_randomly_mutable() == Some(true) {
+                args.push(arg_result.1.unwrap());
+            } else {
+                let arg_uuid = Uuid::new_v4();
+                args.push(arg_uuid);
+                moves.push((arg_uuid, arg_result.1.unwrap()));
+            }
+        }
+
+        // The arguments covered by `...` have no declared type, so they're passed as they are.
+        let variadic_start = function_meta.is_variadic.then_some(args.len());
+        let variadic_arguments = if function_meta.is_variadic { &self.arguments[expected_args.min(actual_args)..] } else { &[] };
+
+        for arg in variadic_arguments.iter() {
+            let arg_result = arg.generate_instructions(context);
+            let arg_uuid = Uuid::new_v4();
+
+            instructions.append(&mut arg_result.0.clone());
+            args.push(arg_uuid);
+            moves.push((arg_uuid, arg_result.1.unwrap()));
+        }
+
+        for move_ in moves {
+            instructions.push(Instruction::Move(move_.0, move_.1));
+        }
+
+
+        (
+            [
+                instructions,
+                result_memory,
+                vec![Instruction::Call(asm_fn_name, args, return_uuids, function_meta.assembly_style, variadic_start, indirect_result)],
+                struct_result,
+            ].concat()
+            ,
+            results
+        )
+    }
+}
+
+impl Node for FunctionCallNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position.clone()
+    }
+
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![]
+    }
+
+    fn get_datatypes(&self, _all_types: Vec<ObjectType>, context: Context) -> Option<Vec<ObjectType>> {
+        let function_meta = context.resolve_function(&self.name, self.position.0)?;
+
+        // Several values can only be received by listing names (e.g. `let (q, r) = divide(a, b);`).
+        let [type_uuid] = function_meta.return_types[..] else { return None };
+        let type_ = context.datatypes.get(&type_uuid)?;
+
+        Some(vec![type_.clone()])
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let (instructions, results) = self.generate_call(context);
+
+        (instructions, results.first().copied())
+    }
+
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        None
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        for argument in self.arguments.iter() {
+            argument.check_types(context);
+        }
+
+        let Some(function_meta) = context.resolve_function(&self.name, self.position.0).cloned() else {
+            let notification = NotificationInfo::new(
+                "Unknown Function".to_string(),
+                format!("There's no function called `{}` here", self.name),
+                vec![context.line_map.display_code_for(self.position.1, vec![], DisplayCodeKind::InitialError)],
+            );
+
+            context.line_map.display_error(notification);
+            return;
+        };
+
+        let expected_args = function_meta.arguments.len();
+        let actual_args = self.arguments.len();
+
+        if actual_args < expected_args || (actual_args > expected_args && !function_meta.is_variadic) {
+            let at_least = if function_meta.is_variadic { "at least " } else { "" };
+
+            let notification = NotificationInfo::new(
+                "Wrong Amount of Arguments".to_string(),
+                format!("Function '{}' expected {}{} argument(s), but received {}.", function_meta.code_name, at_least, expected_args, actual_args),
+                vec![context.line_map.display_code_for(self.position.1, vec![], DisplayCodeKind::InitialError)],
+            );
+
+            context.line_map.display_error(notification);
+        }
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+
+        for (argument, expected) in self.arguments.iter().zip(function_meta.arguments.iter()) {
+            let Some(datatypes) = argument.get_datatypes(all_types.clone(), context.clone()) else { continue };
+
+            if datatypes.iter().any(|x| x.type_uuid == expected.type_uuid) { continue; }
+
+            let expected_name = context.datatypes.get(&expected.type_uuid).map(|x| x.name.clone()).unwrap_or_default();
+            let found_names = describe_datatypes(&datatypes);
+
+            let notification = NotificationInfo::new(
+                "Mismatched Argument Type".to_string(),
+                format!("Function '{}' expected an argument of type `{}`, but received {}.", function_meta.code_name, expected_name, found_names),
+                vec![
+                    context.line_map.display_code_for(
+                        argument.get_position().1,
+                        vec![format!("*note:* expected `{}`, found {}", expected_name, found_names)],
+                        DisplayCodeKind::InitialError
+                    ),
+                ],
+            );
+
+            context.line_map.display_error(notification);
+        }
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+    }
+}
+
+/// ### Syscall Intrinsic
+///
+/// `syscall(number, arguments...)` performs a system call directly. The registers
+/// and the instruction used depend on the target's
+/// [convention](crate::compiler::backend::arch::SyscallConvention).
+/// The kernel's result is returned as `isize`.
+#[derive(Clone, Debug, new)]
+pub struct SyscallNode {
+    arguments: Vec<Rc<dyn Node>>,
+    position: (usize, TokenPosition),
+}
+
+impl Node for SyscallNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position
+    }
+
     fn get_future(&self, current: CodeFuture) -> CodeFuture {
         current
     }
-    
+
     fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
         vec![]
     }
-    
-    fn get_datatypes(&self, _types: Vec<ObjectType>, _context: Context) -> Option<Vec<ObjectType>> {
-        None
+
+    fn get_datatypes(&self, all_types: Vec<ObjectType>, _context: Context) -> Option<Vec<ObjectType>> {
+        let type_ = all_types.into_iter().find(|x| x.name == IntegerType::SignedAddress.get_code_name())?;
+
+        Some(vec![type_])
     }
-    
+
     fn unpack(&self) -> Box<dyn Node> {
         Box::new(self.clone())
     }
 
     fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
         let mut instructions: Vec<Instruction> = vec![];
-        let mut left_side = self.left_side.generate_instructions(context);
-        let mut right_side = self.right_side.generate_instructions(context);
-
-        instructions.append(left_side.0.as_mut());
-        instructions.append(right_side.0.as_mut());
+        let mut objects: Vec<Uuid> = vec![];
+        let mut moves: Vec<(Uuid, Uuid)> = vec![];
 
-        // Look if the left side is in the list of mutable objects
-        if !context.mutable_objects.contains(&left_side.1.unwrap()) {
-            let display_info = DisplayCodeInfo::new(
-                self.position.0 as u32,
-                self.position.1.start as u32,
-                self.position.1.start as i32 + self.position.1.length as i32,
-                vec![
-                    "*hint:* consider making this variable mutable".to_string()
-                ],
-                DisplayCodeKind::InitialError
-            );
+        for arg in self.arguments.iter() {
+            let arg_result = arg.generate_instructions(context);
 
-            let notification = NotificationInfo::new(
-                "Attempt To Modify Immutable Variable".to_string(),
-                "This assignment tries to alter a left side that is immutable".to_string(),
-                vec![display_info],
-            );
+            instructions.append(&mut arg_result.0.clone());
 
-            context.line_map.display_error(notification);
+            if arg.output_is_randomly_mutable() == Some(true) {
+                objects.push(arg_result.1.unwrap());
+            } else {
+                let arg_uuid = Uuid::new_v4();
+                objects.push(arg_uuid);
+                moves.push((arg_uuid, arg_result.1.unwrap()));
+            }
+        }
 
-            return (instructions, None);
+        for move_ in moves {
+            instructions.push(Instruction::Move(move_.0, move_.1));
         }
 
+        let result = Uuid::new_v4();
 
-        instructions.push(Instruction::Move(left_side.1.unwrap(), right_side.1.unwrap()));
+        // A missing number is reported while checking the types, the result just has to exist.
+        let Some((number, arguments)) = objects.split_first() else {
+            return (vec![Instruction::MoveData(result, 0)], Some(result));
+        };
 
+        instructions.push(Instruction::Syscall(result, *number, arguments.to_vec()));
 
-        (instructions, None)
+        (instructions, Some(result))
     }
 
     fn output_is_randomly_mutable(&self) -> Option<bool> {
         None
     }
 
+    fn check_types(&self, context: &mut Context) {
+        for argument in self.arguments.iter() {
+            argument.check_types(context);
+        }
+
+        // The backend can only pass as many arguments as the target has registers for them.
+        let max_arguments = context.options.target.generate_architecture().syscall_convention.argument_registers.len();
+        if (1..=max_arguments + 1).contains(&self.arguments.len()) { return; }
+
+        let notification = NotificationInfo::new(
+            "Wrong Amount of Arguments".to_string(),
+            format!("`syscall` expected a number and up to {} argument(s), but received {} value(s).", max_arguments, self.arguments.len()),
+            vec![
+                context.line_map.display_code_for(self.position.1, vec![], DisplayCodeKind::InitialError),
+            ],
+        );
+
+        context.line_map.display_error(notification);
+    }
+
     #[cfg(test)]
     fn repeatedly_reset_position(&mut self) {
         self.position = (0, TokenPosition::test_value());
-        todo!();
-        self.right_side.repeatedly_reset_position();
     }
 }
 
 #[derive(Clone, Debug, new)]
-pub struct AssignmentSymbolNode {
-    position: (usize, TokenPosition),
+pub struct CodeBlockArray {
+    pub position: (usize, TokenPosition),
+    pub code_blocks: Vec<CodeBlockNode>
 }
 
-
-impl Node for AssignmentSymbolNode {
+impl Node for CodeBlockArray {
     fn get_position(&self) -> (usize, TokenPosition) {
         self.position.clone()
     }
-
+    
     fn get_future(&self, current: CodeFuture) -> CodeFuture {
         current
     }
-
+    
     fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
         vec![]
     }
-
-    fn get_datatypes(&self, _: Vec<ObjectType>, _: Context) -> Option<Vec<ObjectType>> {
+    
+    fn get_datatypes(&self, _all_types: Vec<ObjectType>, _context: Context) -> Option<Vec<ObjectType>> {
         None
     }
 
+    fn perform_early_context_changes(&mut self, context: &mut Context) {
+        for i in 0..self.code_blocks.len() {
+            self.code_blocks[i].perform_early_context_changes(context);
+        }
+    }
+
     fn unpack(&self) -> Box<dyn Node> {
-        Box::new(self.clone())
+        Box::new((*self).clone())
     }
 
-    fn generate_instructions(&self, _context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
-        todo!()
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let mut instructions: Vec<Instruction> = vec![];
+
+        for code_block in self.code_blocks.iter() {
+            instructions.append(code_block.generate_instructions(context).0.as_mut());
+        }
+
+        (
+            instructions,
+            None
+        )
     }
 
     fn output_is_randomly_mutable(&self) -> Option<bool> {
         None
     }
 
+    fn check_types(&self, context: &mut Context) {
+        for code_block in self.code_blocks.iter() {
+            code_block.check_types(context);
+        }
+    }
+
     #[cfg(test)]
     fn repeatedly_reset_position(&mut self) {
-        self.position = (0, TokenPosition::test_value());
+        todo!()
     }
 }
 
+
 #[derive(Clone, Debug, new)]
-pub struct LetNode {
-    identifier: String,
-    assigned_value: Option<Rc<dyn Node>>,
-    is_mutable: bool,
-    position: (usize, TokenPosition),
+pub struct FunctionDeclarationNode {
+    pub position: (usize, TokenPosition),
+    pub name: Rc<String>,
+    pub block: Rc<CodeBlockNode>,
+    pub parameters: Rc<Vec<ParameterDescriptor>>,
+
+    /// The types of the returned values (e.g. `-> (u32, u32)`), the block has to end by returning them.
+    pub return_types: Vec<TypeAnnotationNode>,
+
+    /// `C` for `extern "C"` functions, `Smisc` for all others.
+    pub style: FunctionStyle,
+
+    /// The generated parameters
+    #[new(default)]
+    parameter_function_args: Vec<FunctionArgument>
 }
 
-impl Node for LetNode {
+impl Node for FunctionDeclarationNode {
     fn get_position(&self) -> (usize, TokenPosition) {
         self.position.clone()
     }
-
+    
     fn get_future(&self, current: CodeFuture) -> CodeFuture {
         current
     }
-
+    
     fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
-        vec![]
+        vec![self.block.clone()]
     }
-
-    fn get_datatypes(&self, _: Vec<ObjectType>, _: Context) -> Option<Vec<ObjectType>> {
+    
+    fn get_datatypes(&self, _all_types: Vec<ObjectType>, _context: Context) -> Option<Vec<ObjectType>> {
         None
     }
 
     fn unpack(&self) -> Box<dyn Node> {
-        Box::new(self.clone())
+        Box::new((*self).clone())
     }
 
     fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let block = self.block.deref().clone();
         let mut instructions: Vec<Instruction> = vec![];
-        let mut result_uuid: Option<Uuid> = None;
 
-        if let Some(assigned_value) = self.assigned_value.clone() {
-            let assignment_result = assigned_value.clone().generate_instructions(context).clone();
-            println!("assigned value: {assigned_value:?} created: {assignment_result:?}");
+        // Structs passed in registers get stored on the stack once the function started.
+        let mut struct_arguments: Vec<Instruction> = vec![];
+        let mut register_index = 0;
 
+        for argument in self.parameter_function_args.iter() {
+            let parts = context.datatypes.get(&argument.type_uuid).and_then(get_struct_register_parts);
 
-            let mut assignment_instructions = assignment_result.0;
-            instructions.append(&mut assignment_instructions);
+            let Some(parts) = parts else {
+                instructions.push(Instruction::ReceiveArgument(argument.own_uuid, register_index, self.style));
+                register_index += 1;
+                continue;
+            };
 
-            if assigned_value.output_is_randomly_mutable() == Some(true) {
-                result_uuid = assignment_result.1;
-            } else {
-                result_uuid = Some(Uuid::new_v4());
-                instructions.push(Instruction::Move(result_uuid.unwrap(), assignment_result.1.unwrap()));
+            let size = context.datatypes[&argument.type_uuid].get_size().unwrap();
+            struct_arguments.push(Instruction::StackAllocate(argument.own_uuid, size as u64));
+
+            for (i, part_size) in parts.into_iter().enumerate() {
+                let part = Uuid::new_v4();
+                let (mut address_instructions, address) = generate_address(argument.own_uuid, i * 8);
+
+                instructions.push(Instruction::ReceiveArgument(part, register_index, self.style));
+                struct_arguments.append(&mut address_instructions);
+                struct_arguments.push(Instruction::Store(part, address, part_size as u8));
+                register_index += 1;
             }
         }
 
-        if result_uuid.is_none() {
-            // If there's no uuid, one needs to be assigned still
-            result_uuid = Some(Uuid::new_v4());
+        let receive_args_end = instructions.len();
+
+        // A struct too large for registers is returned in memory the caller provides.
+        let returns_indirectly = match &self.return_types[..] {
+            [return_type] => context.datatypes.get(&return_type.datatype)
+                .is_some_and(|x| get_struct_fields(x).is_some() && get_struct_register_parts(x).is_none()),
+            _ => false,
+        };
+
+        let indirect_result = returns_indirectly.then(Uuid::new_v4);
+
+        if let Some(indirect_result) = indirect_result {
+            struct_arguments.insert(0, Instruction::ReceiveIndirectResult(indirect_result));
         }
 
-        let value = self.assigned_value.clone().unwrap();
-        let datatypes = value.get_datatypes(context.datatypes.values().cloned().collect(), context.clone());
+        // Update the context, the parameters are known inside the function only.
+        context.enter_scope(vec![]);
+        let outer_function = context.current_function.replace(context.resolve_function(&self.name, self.position.0).cloned().unwrap());
+        let outer_indirect_result = std::mem::replace(&mut context.indirect_result, indirect_result);
+
+        for i in 0..self.parameters.len() {
+            if let Some(name) = self.parameters[i].internal_name.clone() {
+                let uuid = self.parameter_function_args[i].own_uuid;
+                let type_uuid = self.parameter_function_args[i].type_uuid;
 
-        println!("value: {:#?}, datatpyes: {datatypes:#?}", value);
 
-        context.objects.insert(result_uuid.unwrap(), datatypes.unwrap()[0].type_uuid);
-        context.name_map.insert(self.identifier.clone(), result_uuid.unwrap());
+                context.objects.insert(uuid, type_uuid);
+                context.declare(name.clone(), uuid);
 
-        if self.is_mutable {
-            context.mutable_objects.push(result_uuid.unwrap());
+                println!("inserted object named {name} with uuid {uuid} as having the type: {type_uuid}")
+            }
         }
 
+        instructions.append(&mut block.generate_instructions(context).0.to_vec());
+        context.exit_scope();
+        context.current_function = outer_function;
+        context.indirect_result = outer_indirect_result;
 
-        (instructions, None)
+        instructions.insert(receive_args_end + 1, Instruction::FunctionStart(self.style)); // Insert after label.
+        instructions.splice(receive_args_end + 2..receive_args_end + 2, struct_arguments);
+        println!("zero: {:?}", instructions[0]);
+
+        instructions.push(
+            Instruction::FunctionEnd
+        );
+
+
+        (
+            instructions,
+            None
+        )
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        None
+    }
+
+    fn perform_early_context_changes(&mut self, context: &mut Context) {
+        let mut block = self.block.deref().clone();
+
+        // C functions are found by the target's symbol for them, unless the declaration gave one.
+        if self.style == FunctionStyle::C && block.label.is_none() {
+            block.label = Some(Rc::new(context.options.target.get_c_symbol(&self.name)));
+        }
+
+        let asm_label = block.assign_label(context);
+        self.parameter_function_args = self.parameters.iter().map(|x|x.generate_function_argument()).collect();
+
+        context.function_metas.push(
+            FunctionMeta::new(
+                context.qualify(&self.name, self.position.0),
+                asm_label.deref().clone(),
+                self.style,
+                self.return_types.iter().map(|x| x.datatype).collect(),
+                self.parameter_function_args.clone(),
+                false
+            )
+        );
+
+
+
+        block.perform_early_context_changes(context);
+        self.block = Rc::new(block);
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        check_return_types(&self.name, self.style, &self.return_types, context);
+
+        for return_type in self.return_types.iter() {
+            let Some(datatype) = context.datatypes.get(&return_type.datatype) else { continue };
+
+            // Structs take up the registers of all return values (or none if they're returned in memory).
+            let message = match get_struct_fields(datatype) {
+                Some(_) if self.return_types.len() > 1 => "A struct can only be returned as the only value",
+                Some(_) => continue,
+                None if is_aggregate(datatype) => "Functions with a body can't return arrays yet",
+                None => continue,
+            };
+
+            let notification = NotificationInfo::new(
+                "Unsupported Return Type".to_string(),
+                message.to_string(),
+                vec![context.line_map.display_code_for(return_type.position.1, vec![], DisplayCodeKind::InitialError)],
+            );
+
+            context.line_map.display_error(notification);
+        }
+
+        // The values have to be returned explicitly, the function can't just end.
+        let ends_with_return = self.block.code.last().is_some_and(|x| x.is::<ReturnNode>() || x.is::<ExitNode>());
+
+        if !self.return_types.is_empty() && !ends_with_return {
+            let notification = NotificationInfo::new(
+                "Missing Return".to_string(),
+                format!("Function '{}' has to end by returning its value(s).", self.name),
+                vec![context.line_map.display_code_for(self.position.1, vec!["*hint:* end the function with `return`".to_string()], DisplayCodeKind::InitialError)],
+            );
+
+            context.line_map.display_error(notification);
+        }
+
+        // Make the parameters known inside the function
+        context.enter_scope(vec![]);
+        let outer_function = context.current_function.replace(context.resolve_function(&self.name, self.position.0).cloned().unwrap());
+
+        for (parameter, function_argument) in self.parameters.iter().zip(self.parameter_function_args.iter()) {
+            if let Some(name) = parameter.internal_name.clone() {
+                context.objects.insert(function_argument.own_uuid, function_argument.type_uuid);
+                context.declare(name, function_argument.own_uuid);
+            }
+        }
+
+        self.block.check_types(context);
+        context.exit_scope();
+        context.current_function = outer_function;
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+    }
+}
+
+/// A [node](Node) declaring a function without a body that is linked in from elsewhere
+/// (e.g. `extern "C" func write(fd: i32, buf: &u8, len: usize) -> isize;`). It's called
+/// with the C calling convention.
+#[derive(Clone, Debug, new)]
+pub struct ExternFunctionDeclarationNode {
+    pub position: (usize, TokenPosition),
+    pub name: Rc<String>,
+    pub parameters: Rc<Vec<ParameterDescriptor>>,
+
+    /// Whether the parameters end with `...` (e.g. `printf(format: &u8, ...)`).
+    pub is_variadic: bool,
+    pub return_types: Vec<TypeAnnotationNode>,
+
+    /// The symbol given in the declaration, the target's symbol for the C function otherwise.
+    pub assembly_name: Option<Rc<String>>,
+}
+
+impl Node for ExternFunctionDeclarationNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position
+    }
+
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![]
+    }
+
+    fn get_datatypes(&self, _all_types: Vec<ObjectType>, _context: Context) -> Option<Vec<ObjectType>> {
+        None
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn generate_instructions(&self, _context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        (vec![], None)
     }
 
     fn output_is_randomly_mutable(&self) -> Option<bool> {
         None
     }
 
+    fn perform_early_context_changes(&mut self, context: &mut Context) {
+        context.function_metas.push(
+            FunctionMeta::new(
+                context.qualify(&self.name, self.position.0),
+                self.assembly_name.as_deref().cloned().unwrap_or_else(|| context.options.target.get_c_symbol(&self.name)),
+                FunctionStyle::C,
+                self.return_types.iter().map(|x| x.datatype).collect(),
+                self.parameters.iter().map(|x| x.generate_function_argument()).collect(),
+                self.is_variadic
+            )
+        );
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        check_return_types(&self.name, FunctionStyle::C, &self.return_types, context);
+    }
+
     #[cfg(test)]
     fn repeatedly_reset_position(&mut self) {
         self.position = (0, TokenPosition::test_value());
-
-        if self.assigned_value.is_none() { return }
-        todo!()
-        //self.assigned_value.unwrap().repeatedly_reset_position();
     }
 }
 
-/// A node containing multiple lines of code. Note that this node is always an expression,
-/// never a statement. Use with caution.
+/// A type annotation like `: u32` (e.g. in `let a: u32 = 5;`).
 #[derive(Clone, Debug, new)]
-pub struct CodeBlockNode {
-    position: (usize, TokenPosition),
-    pub label: Option<Rc<String>>,
-    code: Vec<Rc<dyn Node>>,
-}
-
-impl CodeBlockNode {
-    pub fn push_code(&mut self, code: Rc<dyn Node>) {
-        self.code.push(code);
-    }
-    
-    pub fn append_code(&mut self, code: Vec<Rc<dyn Node>>) {
-        self.code.append(&mut code.clone());
-    }
-
-    pub fn assign_label(&mut self, context: &mut Context) -> Rc<String> {
-        let name: Rc<String> = {if let Some(label) = self.label.clone() { label.clone() } else { context.generate_label() }};
-
-        self.label = Some(name.clone());
-
-        name
-    }
+pub struct TypeAnnotationNode {
+    /// The uuid of the annotated [type](ObjectType).
+    pub datatype: Uuid,
+    pub position: (usize, TokenPosition),
 }
 
-impl Node for CodeBlockNode {
+impl Node for TypeAnnotationNode {
     fn get_position(&self) -> (usize, TokenPosition) {
-        self.position.clone()
+        self.position
     }
 
-    fn get_future(&self, _current: CodeFuture) -> CodeFuture {
-        todo!()
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
     }
 
     fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
-        self.code.clone()
+        vec![]
     }
 
-    fn get_datatypes(&self, _all_types: Vec<ObjectType>, _: Context) -> Option<Vec<ObjectType>> {
+    fn get_datatypes(&self, _: Vec<ObjectType>, _: Context) -> Option<Vec<ObjectType>> {
         None
     }
 
     fn unpack(&self) -> Box<dyn Node> {
-        todo!()
+        Box::new(self.clone())
     }
 
-    fn perform_early_context_changes(&mut self, context: &mut Context) {
-        for i in 0..self.code.iter().len() {
-            let clone = self.code[i].clone().downcast_rc::<FunctionDeclarationNode>();
+    fn generate_instructions(&self, _: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        (vec![], None)
+    }
 
-            if clone.is_err() {
-                continue;
-            }
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        None
+    }
 
-            let mut clone = clone.unwrap().deref().clone();
-            clone.perform_early_context_changes(context);
-            self.code[i] = Rc::new(clone);
-        }
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
     }
+}
 
-    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
-        let mut copy = self.clone();
-        let name: Rc<String> = copy.assign_label(context);
-        
-        let mut instructions: Vec<Instruction> = vec![];
+/// A [node](Node) declaring a struct (e.g. `struct Point { x: i32, y: i32 }`).
+///
+/// The type gets registered while parsing already, so the declaration
+/// doesn't do anything on its own.
+#[derive(Clone, Debug, new)]
+pub struct StructDeclarationNode {
+    pub position: (usize, TokenPosition),
+}
 
-        instructions.push(Instruction::Label(name, false));
+impl Node for StructDeclarationNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position
+    }
 
-        for code in self.code.iter() {
-            instructions.append(code.generate_instructions(context).0.as_mut());
-        }
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
 
-        (instructions, None)
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![]
     }
 
-    fn output_is_randomly_mutable(&self) -> Option<bool> {
+    fn get_datatypes(&self, _: Vec<ObjectType>, _: Context) -> Option<Vec<ObjectType>> {
         None
     }
 
+    fn unpack(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
 
+    fn generate_instructions(&self, _: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        (vec![], None)
+    }
 
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        None
+    }
 
     #[cfg(test)]
     fn repeatedly_reset_position(&mut self) {
@@ -821,45 +4547,38 @@ impl Node for CodeBlockNode {
     }
 }
 
-
-/// A node that exits the process. No dark web stuff.
-#[derive(Debug, new)]
-pub struct ExitNode {
-    return_value: Rc<dyn Node>,
-    position: (usize, TokenPosition),
+/// A [node](Node) importing a module (e.g. `import geometry::shapes;`).
+///
+/// The [module loader](crate::compiler::modules::load_modules) reads the imported files
+/// before parsing, so there's nothing left to do.
+#[derive(Clone, Debug, new)]
+pub struct ImportNode {
+    pub position: (usize, TokenPosition),
 }
 
-
-impl Node for ExitNode {
+impl Node for ImportNode {
     fn get_position(&self) -> (usize, TokenPosition) {
-        self.position.clone()
+        self.position
     }
 
-    fn get_future(&self, _current: CodeFuture) -> CodeFuture {
-        CodeFuture::Never
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
     }
 
     fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
         vec![]
     }
 
-    fn get_datatypes(&self, _all_types: Vec<ObjectType>, _: Context) -> Option<Vec<ObjectType>> {
+    fn get_datatypes(&self, _: Vec<ObjectType>, _: Context) -> Option<Vec<ObjectType>> {
         None
     }
 
     fn unpack(&self) -> Box<dyn Node> {
-        todo!()
+        Box::new(self.clone())
     }
 
-    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
-        let mut instructions: Vec<Instruction> = vec![];
-        let mut return_value = self.return_value.generate_instructions(context);
-
-        instructions.append(return_value.0.as_mut());
-
-        instructions.push(Instruction::Exit(return_value.1.unwrap()));
-
-        (instructions, None)
+    fn generate_instructions(&self, _: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        (vec![], None)
     }
 
     fn output_is_randomly_mutable(&self) -> Option<bool> {
@@ -872,16 +4591,111 @@ impl Node for ExitNode {
     }
 }
 
+/// An operand of [inline assembly](InlineAssemblyNode), which gets a register.
+#[derive(Clone, Debug)]
+pub enum AsmOperand {
+    /// A value that is in the register when the assembly starts (`in(reg) value`).
+    /// The assembly mustn't change the register.
+    Input(Rc<dyn Node>),
+
+    /// A variable that gets the value in the register once the assembly ran (`out(reg) variable`).
+    Output(IdentifierNode),
+}
+
+impl AsmOperand {
+    /// The name the operand can be referred to with in the assembly, which is the name
+    /// of its variable (e.g. `{a}` for `in(reg) a`).
+    fn get_name(&self) -> Option<String> {
+        match self {
+            AsmOperand::Input(value) => value.unpack().downcast_ref::<IdentifierNode>().map(|x| x.identifier.clone()),
+            AsmOperand::Output(variable) => Some(variable.identifier.clone()),
+        }
+    }
+}
+
+/// A [node](Node) placing assembly into the code (e.g. `asm { "mrs {ticks}, cntvct_el0", out(reg) ticks }`).
+///
+/// In the lines, an operand is referred to by its index (e.g. `{0}`) or the name of its
+/// variable (e.g. `{ticks}`), `{{` and `}}` stand for the braces themselves.
 #[derive(Clone, Debug, new)]
-pub struct FunctionCallNode {
-    name: Rc<String>,
-    arguments: Vec<Rc<dyn Node>>,
-    position: (usize, TokenPosition),
+pub struct InlineAssemblyNode {
+    pub position: (usize, TokenPosition),
+    pub lines: Vec<StringLiteralNode>,
+    pub operands: Vec<AsmOperand>,
+
+    /// The registers the lines change besides the outputs (e.g. `"x9"`).
+    pub clobbers: Vec<StringLiteralNode>,
 }
 
-impl Node for FunctionCallNode {
+impl InlineAssemblyNode {
+    /// ### Refers to the Operands in a Line by Their Index
+    ///
+    /// Replaces names in placeholders (e.g. `{ticks}`) by the index of their operand. If a
+    /// placeholder doesn't refer to an operand, its contents are returned as the error.
+    fn number_operands(&self, line: &str) -> Result<String, String> {
+        let names: Vec<Option<String>> = self.operands.iter().map(|x| x.get_name()).collect();
+        let mut result = String::new();
+        let mut characters = line.chars().peekable();
+
+        while let Some(character) = characters.next() {
+            match character {
+                '{' | '}' if characters.peek() == Some(&character) => {
+                    characters.next();
+                    result.push(character);
+                    result.push(character);
+                }
+
+                '{' => {
+                    let placeholder: String = characters.by_ref().take_while(|x| *x != '}').collect();
+
+                    let index = match placeholder.parse::<usize>() {
+                        Ok(index) if index < names.len() => index,
+                        Ok(_) => return Err(placeholder),
+                        Err(_) => names.iter().position(|x| x.as_deref() == Some(placeholder.as_str())).ok_or(placeholder)?,
+                    };
+
+                    result.push_str(&format!("{{{}}}", index));
+                }
+
+                _ => result.push(character),
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Assigns the value of an output operand to its variable once the assembly ran, just
+    /// like an [assignment](AssignmentNode) does.
+    fn generate_output_assignment(variable: &IdentifierNode, output: Uuid, context: &mut Context) -> Vec<Instruction> {
+        // Unknown and immutable variables are reported while checking the types.
+        let Some(object) = variable.resolve(context) else { return vec![] };
+
+        let datatype = context.datatypes.get(&context.objects[&object]).unwrap().clone();
+
+        if is_aggregate(&datatype) {
+            todo!("Throw an error: only values fitting into a register can be assembly outputs")
+        }
+
+        // The whole register was written, but smaller values are kept extended.
+        let size = datatype.get_size().unwrap() as u8;
+        let mut instructions = vec![Instruction::Extend(output, size, datatype.has_trait(Trait::SIGNED))];
+
+        if context.memory_objects.contains(&object) {
+            let (mut address_instructions, address) = generate_object_address(object, context);
+
+            instructions.append(&mut address_instructions);
+            instructions.append(&mut generate_store(output, address, &datatype));
+        } else {
+            instructions.push(Instruction::Move(object, output));
+        }
+
+        instructions
+    }
+}
+
+impl Node for InlineAssemblyNode {
     fn get_position(&self) -> (usize, TokenPosition) {
-        self.position.clone()
+        self.position
     }
 
     fn get_future(&self, current: CodeFuture) -> CodeFuture {
@@ -892,92 +4706,108 @@ impl Node for FunctionCallNode {
         vec![]
     }
 
-    fn get_datatypes(&self, _all_types: Vec<ObjectType>, context: Context) -> Option<Vec<ObjectType>> {
-        let function_metas = context.function_metas;
-
-        let function_meta = function_metas.iter().find(|&x|x.code_name.as_str()==self.name.as_str())?;
-        let type_uuid = function_meta.return_type_uuid?;
-        let type_ = context.datatypes.get(&type_uuid)?;
-
-        Some(vec![type_.clone()])
+    fn get_datatypes(&self, _: Vec<ObjectType>, _: Context) -> Option<Vec<ObjectType>> {
+        None
     }
 
     fn unpack(&self) -> Box<dyn Node> {
-        todo!()
+        Box::new(self.clone())
     }
 
     fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
-        let function_metas = context.function_metas.clone();
+        let mut instructions: Vec<Instruction> = vec![];
+        let mut assignments: Vec<Instruction> = vec![];
+        let mut inputs: Vec<(usize, Uuid)> = vec![];
+        let mut outputs: Vec<(usize, Uuid)> = vec![];
 
-        let function_meta = function_metas.iter().find(|&x|x.code_name.as_str()==self.name.as_str()).unwrap();
-        let asm_fn_name = function_meta.assembly_name.clone();
+        for (i, operand) in self.operands.iter().enumerate() {
+            match operand {
+                AsmOperand::Input(value) => {
+                    let mut value = value.generate_instructions(context);
 
-        let mut return_uuid: Option<Uuid> = None;
-        let mut return_uuids: Vec<Uuid> = vec![];
+                    instructions.append(&mut value.0);
+                    inputs.push((i, value.1.unwrap()));
+                }
 
-        if function_meta.return_type_uuid.is_some() {
-            return_uuid = Some(Uuid::new_v4());
-            return_uuids.push(return_uuid.unwrap());
+                AsmOperand::Output(variable) => {
+                    let output = Uuid::new_v4();
+
+                    outputs.push((i, output));
+                    assignments.append(&mut Self::generate_output_assignment(variable, output, context));
+                }
+            }
         }
 
+        // Unknown operands are reported while checking the types.
+        let lines = self.lines.iter().map(|x| self.number_operands(&x.string).unwrap_or_default()).collect();
+        let clobbers = self.clobbers.iter().map(|x| x.string.deref().clone()).collect();
 
-        let mut args: Vec<Uuid> = vec![];
-        let mut instructions: Vec<Instruction> = vec![];
-        let mut moves: Vec<(Uuid, Uuid)> = vec![];
+        instructions.push(Instruction::InlineAssembly(Rc::new(InlineAssembly::new(lines, inputs, outputs, clobbers))));
+        instructions.append(&mut assignments);
 
-        if self.arguments.len() != function_meta.arguments.len() {
-            let display_info = DisplayCodeInfo::new(
-                self.position.0 as u32,
-                self.position.1.start as u32,
-                (self.position.1.start + self.position.1.length) as i32,
-                vec![],
-                DisplayCodeKind::InitialError
-            );
+        (instructions, None)
+    }
 
-            let name = function_meta.code_name.clone();
-            let expected_args = function_meta.arguments.len();
-            let actual_args = self.arguments.len();
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        None
+    }
 
-            let notification = NotificationInfo::new(
-                "Wrong Amount of Arguments".to_string(),
-                format!("Function '{}' expected {} argument(s), but received {}.", name, expected_args, actual_args),
-                vec![display_info]
-            );
+    fn check_types(&self, context: &mut Context) {
+        for operand in self.operands.iter() {
+            match operand {
+                AsmOperand::Input(value) => value.check_types(context),
+                AsmOperand::Output(variable) => variable.check_types(context),
+            }
 
-            context.line_map.display_error(notification);
+            if let AsmOperand::Output(variable) = operand
+                && let Some(object) = variable.resolve(context)
+                && !context.mutable_objects.contains(&object) {
+                report_immutable_modification(&variable.identifier, object, variable.position.1, context);
+            }
         }
 
-        for arg in self.arguments.iter() {
-            let arg_result = arg.generate_instructions(context);
+        for line in self.lines.iter() {
+            let Err(placeholder) = self.number_operands(&line.string) else { continue };
+            let operand_count = self.operands.len();
 
-            instructions.append(&mut arg_result.0.clone());
+            let notification = NotificationInfo::new(
+                "Unknown Operand".to_string(),
+                format!("`{{{}}}` doesn't refer to an operand of the assembly", placeholder),
+                vec![
+                    context.line_map.display_code_for(
+                        line.position.1,
+                        vec![format!("*note:* operands are referred to by the name of their variable or their index (0 to {})", operand_count.max(1) - 1)],
+                        DisplayCodeKind::InitialError
+                    ),
+                ],
+            );
 
-            if arg.output_is_randomly_mutable() == Some(true) {
-                args.push(arg_result.1.unwrap());
-            } else {
-                let arg_uuid = Uuid::new_v4();
-                args.push(arg_uuid);
-                moves.push((arg_uuid, arg_result.1.unwrap()));
-            }
+            context.line_map.display_error(notification);
         }
 
-        for move_ in moves {
-            instructions.push(Instruction::Move(move_.0, move_.1));
-        }
+        let architecture = context.options.target.generate_architecture();
 
+        for clobber in self.clobbers.iter() {
+            let message = match architecture.get_register_by_name(&clobber.string) {
+                Some(register) if register.kind == RegisterKind::GeneralPurpose => continue,
+                Some(_) => format!("`{}` is reserved by the compiler, so it can't be clobbered", clobber.string),
+                None => format!("`{}` isn't a register the compiler places values in", clobber.string),
+            };
 
-        (
-            [
-                instructions,
-                vec![Instruction::Call(asm_fn_name, args, return_uuids)],
-            ].concat()
-            ,
-            return_uuid
-        )
-    }
+            let notification = NotificationInfo::new(
+                "Unknown Register".to_string(),
+                message,
+                vec![
+                    context.line_map.display_code_for(
+                        clobber.position.1,
+                        vec!["*note:* only general purpose registers can be clobbered (e.g. `x9`)".to_string()],
+                        DisplayCodeKind::InitialError
+                    ),
+                ],
+            );
 
-    fn output_is_randomly_mutable(&self) -> Option<bool> {
-        None
+            context.line_map.display_error(notification);
+        }
     }
 
     #[cfg(test)]
@@ -986,50 +4816,37 @@ impl Node for FunctionCallNode {
     }
 }
 
+/// A [node](Node) declaring an enum (e.g. `enum Color { Red, Green }`).
+///
+/// Just like with structs, the type gets registered while parsing already.
 #[derive(Clone, Debug, new)]
-pub struct CodeBlockArray {
+pub struct EnumDeclarationNode {
     pub position: (usize, TokenPosition),
-    pub code_blocks: Vec<CodeBlockNode>
 }
 
-impl Node for CodeBlockArray {
+impl Node for EnumDeclarationNode {
     fn get_position(&self) -> (usize, TokenPosition) {
-        self.position.clone()
+        self.position
     }
-    
+
     fn get_future(&self, current: CodeFuture) -> CodeFuture {
         current
     }
-    
-    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
-        vec![]
-    }
-    
-    fn get_datatypes(&self, _all_types: Vec<ObjectType>, _context: Context) -> Option<Vec<ObjectType>> {
-        None
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![]
     }
 
-    fn perform_early_context_changes(&mut self, context: &mut Context) {
-        for i in 0..self.code_blocks.len() {
-            self.code_blocks[i].perform_early_context_changes(context);
-        }
+    fn get_datatypes(&self, _: Vec<ObjectType>, _: Context) -> Option<Vec<ObjectType>> {
+        None
     }
 
     fn unpack(&self) -> Box<dyn Node> {
-        Box::new((*self).clone())
+        Box::new(self.clone())
     }
 
-    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
-        let mut instructions: Vec<Instruction> = vec![];
-
-        for code_block in self.code_blocks.iter() {
-            instructions.append(code_block.generate_instructions(context).0.as_mut());
-        }
-
-        (
-            instructions,
-            None
-        )
+    fn generate_instructions(&self, _: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        (vec![], None)
     }
 
     fn output_is_randomly_mutable(&self) -> Option<bool> {
@@ -1038,109 +4855,94 @@ impl Node for CodeBlockArray {
 
     #[cfg(test)]
     fn repeatedly_reset_position(&mut self) {
-        todo!()
+        self.position = (0, TokenPosition::test_value());
     }
 }
 
-
+/// A [node](Node) referring to a variant of an enum (e.g. `Color::Red`).
+/// Its value is the value of the variant.
 #[derive(Clone, Debug, new)]
-pub struct FunctionDeclarationNode {
-    pub position: (usize, TokenPosition),
-    pub name: Rc<String>,
-    pub block: Rc<CodeBlockNode>,
-    pub parameters: Rc<Vec<ParameterDescriptor>>,
+pub struct EnumVariantNode {
+    /// The uuid of the enum's type (e.g. **Color**::Red)
+    datatype: Uuid,
 
-    /// The generated parameters
-    #[new(default)]
-    parameter_function_args: Vec<FunctionArgument>
+    /// The name of the variant (e.g. Color::**Red**)
+    variant: String,
+    variant_position: TokenPosition,
+
+    position: (usize, TokenPosition),
 }
 
-impl Node for FunctionDeclarationNode {
+impl EnumVariantNode {
+    /// The value of the variant, if the enum has a variant of that name.
+    fn get_value(&self, context: &Context) -> Option<u32> {
+        let datatype = context.datatypes.get(&self.datatype)?;
+
+        Some(get_enum_variants(datatype)?.into_iter().find(|x| x.name == self.variant)?.value)
+    }
+}
+
+impl Node for EnumVariantNode {
     fn get_position(&self) -> (usize, TokenPosition) {
-        self.position.clone()
+        self.position
     }
-    
+
     fn get_future(&self, current: CodeFuture) -> CodeFuture {
         current
     }
-    
+
     fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
-        vec![self.block.clone()]
+        vec![]
     }
-    
-    fn get_datatypes(&self, _all_types: Vec<ObjectType>, _context: Context) -> Option<Vec<ObjectType>> {
-        None
+
+    fn get_datatypes(&self, all_types: Vec<ObjectType>, _context: Context) -> Option<Vec<ObjectType>> {
+        Some(all_types.into_iter().filter(|x| x.type_uuid == self.datatype).collect())
     }
 
     fn unpack(&self) -> Box<dyn Node> {
-        Box::new((*self).clone())
+        Box::new(self.clone())
     }
 
     fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
-        let block = self.block.deref().clone();
-        let parameter_uuids = self.parameter_function_args.iter().map(|x|x.own_uuid);
-        let mut instructions: Vec<Instruction> = parameter_uuids.enumerate().map(|x|Instruction::ReceiveArgument(x.1, x.0 as u8)).collect();
-
-        let receive_args_end = instructions.len();
-
-        // Update the context
-        for i in 0..self.parameters.len() {
-            if let Some(name) = self.parameters[i].internal_name.clone() {
-                let uuid = self.parameter_function_args[i].own_uuid;
-                let type_uuid = self.parameter_function_args[i].type_uuid;
-
-
-                context.objects.insert(uuid, type_uuid);
-                context.name_map.insert(name.clone(), uuid);
-
-                println!("inserted object named {name} with uuid {uuid} as having the type: {type_uuid}")
-            }
-        }
-
-        instructions.append(&mut block.generate_instructions(context).0.to_vec());
-
-        instructions.insert(receive_args_end + 1, Instruction::FunctionStart); // Insert after label.
-        println!("zero: {:?}", instructions[0]);
-
-        instructions.push(
-            Instruction::FunctionEnd
-        );
+        let uuid = Uuid::new_v4();
+        let value = self.get_value(context).unwrap();
 
+        (vec![Instruction::MoveData(uuid, value as i64)], Some(uuid))
+    }
 
-        (
-            instructions,
-            None
-        )
+    fn evaluate_constant(&self, context: &Context) -> Option<i128> {
+        Some(self.get_value(context)? as i128)
     }
 
     fn output_is_randomly_mutable(&self) -> Option<bool> {
-        None
+        Some(true)
     }
 
-    fn perform_early_context_changes(&mut self, context: &mut Context) {
-        let mut block = self.block.deref().clone();
-        let asm_label = block.assign_label(context);
-        self.parameter_function_args = self.parameters.iter().map(|x|x.generate_function_argument()).collect();
-
-        context.function_metas.push(
-            FunctionMeta::new(
-                self.name.deref().clone(),
-                asm_label.deref().clone(),
-                FunctionStyle::C,
-                None,
-                self.parameter_function_args.clone()
-            )
-        );
+    fn check_types(&self, context: &mut Context) {
+        if self.get_value(context).is_some() { return; }
 
+        let Some(datatype) = context.datatypes.get(&self.datatype).cloned() else { return };
+        let variant_names: Vec<String> = get_enum_variants(&datatype).unwrap_or_default().iter().map(|x| format!("`{}`", x.name)).collect();
 
+        let notification = NotificationInfo::new(
+            "Unknown Variant".to_string(),
+            format!("`{}` doesn't have a variant called `{}`", datatype.name, self.variant),
+            vec![
+                context.line_map.display_code_for(
+                    self.variant_position,
+                    vec![format!("*note:* the variants are {}", variant_names.join(", "))],
+                    DisplayCodeKind::InitialError
+                ),
+            ],
+        );
 
-        block.perform_early_context_changes(context);
-        self.block = Rc::new(block);
+        context.line_map.display_error(notification);
     }
 
     #[cfg(test)]
     fn repeatedly_reset_position(&mut self) {
         self.position = (0, TokenPosition::test_value());
+        self.variant_position = TokenPosition::test_value();
     }
 }
 
@@ -1186,7 +4988,10 @@ impl<T: 'static + Debug + Clone> Node for ArgumentsNode<T> {
 }
 
 
-#[derive(Clone, Debug, new)]
+/// A [node](Node) containing a string (e.g. `"Hello"`). The string is placed into the
+/// read-only data section with a [basic layout](Trait::BASIC_STRING), its value is the
+/// address of it.
+#[derive(Clone, Debug, new, PartialEq)]
 pub struct StringLiteralNode {
     pub position: (usize, TokenPosition),
     pub string: Rc<String>
@@ -1213,8 +5018,19 @@ impl Node for StringLiteralNode {
         Box::new((*self).clone())
     }
 
-    fn generate_instructions(&self, _context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
-        todo!()
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        // Symbols starting with `l` stay private to the object file, but unlike
+        // labels, the linker can still resolve the address of the data.
+        let symbol = Rc::new(format!("l_.str.{}", context.generate_label()));
+        let address = Uuid::new_v4();
+
+        (
+            vec![
+                Instruction::ReadOnlyData(symbol.clone(), encode_basic_string(&self.string), STRING_LENGTH_SIZE as u8),
+                Instruction::LoadAddress(address, symbol),
+            ],
+            Some(address)
+        )
     }
 
     fn output_is_randomly_mutable(&self) -> Option<bool> {
@@ -1276,11 +5092,12 @@ impl Node for IfNode {
             condition_instructions,
             vec![
                 Instruction::MoveData(zero, 0),
+                // A false condition skips the then branch.
                 Instruction::JumpConditional(
                     JumpCondition::new(
                         condition_value,
                         Some(zero),
-                        JumpComparisonType::NotEqual
+                        JumpComparisonType::Equal
                     ),
                     else_label_name.clone()
                 )
@@ -1307,8 +5124,374 @@ impl Node for IfNode {
     }
 
 
+    fn check_types(&self, context: &mut Context) {
+        self.condition.check_types(context);
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+
+        if let Some(datatypes) = self.condition.get_datatypes(all_types, context.clone())
+            && !datatypes.iter().any(|x| x.has_trait(Trait::BOOLEAN_COMPATIBLE)) {
+            let notification = NotificationInfo::new(
+                "Non-Boolean Condition".to_string(),
+                format!("The condition of an if statement has to be a boolean, but it's of type {}", describe_datatypes(&datatypes)),
+                vec![
+                    context.line_map.display_code_for(
+                        self.condition.get_position().1,
+                        vec!["*hint:* compare the value instead (e.g. `a == 0`)".to_string()],
+                        DisplayCodeKind::InitialError
+                    ),
+                ],
+            );
+
+            context.line_map.display_error(notification);
+        }
+
+        self.then_branch.check_types(context);
+
+        if let Some(else_branch) = self.else_branch.clone() {
+            else_branch.check_types(context);
+        }
+    }
+
+    #[cfg(test)]
+    fn repeatedly_reset_position(&mut self) {
+        self.position = (0, TokenPosition::test_value());
+    }
+}
+/// The number of constant patterns a [match](MatchNode) needs at least to be
+/// lowered to a [jump table](Instruction::JumpTable).
+const JUMP_TABLE_MIN_PATTERNS: usize = 4;
+
+/// An arm of a [match](MatchNode) (e.g. `1, 2 => { ... }`).
+#[derive(Clone, Debug, new)]
+pub struct MatchArm {
+    /// The patterns, any of which leads to this arm (e.g. **1**, **2** => { ... })
+    pub patterns: Vec<Rc<dyn Node>>,
+    pub block: Rc<CodeBlockNode>,
+}
+
+/// A [node](Node) running the first arm that has a pattern matching a value
+/// (e.g. `match a { 1, 2 => { ... }, _ => { ... } }`). Arms never fall through.
+///
+/// Patterns are values known at compile time (e.g. integers, booleans, variants of
+/// enums or constants) or `_`, which matches any value. Dense patterns are looked up in a jump table, others get compared to
+/// the value one after another.
+#[derive(Debug, new)]
+pub struct MatchNode {
+    /// The value being matched (e.g. match **a** { ... })
+    value: Rc<dyn Node>,
+    arms: Vec<MatchArm>,
+    position: (usize, TokenPosition),
+}
+
+impl MatchNode {
+    /// Whether the pattern is `_`, which matches any value.
+    fn is_wildcard(pattern: &Rc<dyn Node>) -> bool {
+        let Ok(value_node) = pattern.clone().downcast_rc::<ValueNode>() else { return false };
+
+        matches!(value_node.deref(), ValueNode::Identifier(x) if x.identifier == "_")
+    }
+
+    /// Whether the pattern is a variant of an enum. Unknown variants don't have
+    /// a value, but they have been reported already.
+    fn is_enum_variant(pattern: &Rc<dyn Node>) -> bool {
+        let Ok(value_node) = pattern.clone().downcast_rc::<ValueNode>() else { return false };
+
+        matches!(value_node.deref(), ValueNode::EnumVariant(_))
+    }
+
+    /// Compares the value to each constant (.0) and jumps to the label of its arm (.1).
+    fn generate_compare_chain(value: Uuid, constants: &[(i128, usize)], arm_labels: &[Rc<String>], default_label: Rc<String>) -> Vec<Instruction> {
+        let mut instructions: Vec<Instruction> = vec![];
+
+        for (constant, arm) in constants {
+            let constant_uuid = Uuid::new_v4();
+
+            instructions.push(Instruction::MoveData(constant_uuid, *constant as i64));
+            instructions.push(Instruction::JumpConditional(
+                JumpCondition::new(Some(value), Some(constant_uuid), JumpComparisonType::Equal),
+                arm_labels[*arm].clone()
+            ));
+        }
+
+        instructions.push(Instruction::Jump(default_label));
+
+        instructions
+    }
+
+    /// Looks up the label of the arm in a table containing an entry for every value
+    /// between the smallest and the largest constant.
+    fn generate_jump_table(value: Uuid, constants: &[(i128, usize)], arm_labels: &[Rc<String>], default_label: Rc<String>, context: &mut Context) -> Vec<Instruction> {
+        let minimum = constants.iter().map(|x| x.0).min().unwrap();
+        let maximum = constants.iter().map(|x| x.0).max().unwrap();
+
+        let targets: Vec<Rc<String>> = (minimum..=maximum)
+            .map(|x| constants.iter().find(|y| y.0 == x).map_or(default_label.clone(), |y| arm_labels[y.1].clone()))
+            .collect();
+
+        let index = Uuid::new_v4();
+        let offset = Uuid::new_v4();
+        let length = Uuid::new_v4();
+
+        let in_bounds_label = context.generate_label();
+        let table_label = context.generate_label();
+
+        // Values below the minimum wrap around, so a single unsigned comparison checks both bounds.
+        vec![
+            Instruction::Move(index, value),
+            Instruction::MoveData(offset, minimum as i64),
+            Instruction::Sub(index, offset),
+            Instruction::MoveData(length, targets.len() as i64),
+            Instruction::JumpConditional(JumpCondition::new(Some(index), Some(length), JumpComparisonType::UnsignedLess), in_bounds_label.clone()),
+            Instruction::Jump(default_label),
+            Instruction::Label(in_bounds_label, false),
+            Instruction::JumpTable(index, table_label, targets),
+        ]
+    }
+
+    fn report_unreachable_pattern(position: TokenPosition, earlier_position: TokenPosition, context: &mut Context) {
+        let notification = NotificationInfo::new(
+            "Unreachable Pattern".to_string(),
+            "An earlier pattern already matches every value this one matches".to_string(),
+            vec![
+                context.line_map.display_code_for(
+                    position,
+                    vec![],
+                    DisplayCodeKind::InitialError
+                ),
+                context.line_map.display_code_for(
+                    earlier_position,
+                    vec!["*note:* already matched here".to_string()],
+                    DisplayCodeKind::AdditionalInfo
+                ),
+            ],
+        );
+
+        context.line_map.display_error(notification);
+    }
+
+    /// Checks that the patterns are constants of the value's type, that each of them can
+    /// be reached and that every possible value is covered.
+    fn check_patterns(&self, value_types: &[ObjectType], enum_type: Option<(ObjectType, Vec<EnumVariant>)>, is_boolean: bool, context: &mut Context) {
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+
+        let mut matched: Vec<(i128, TokenPosition)> = vec![];
+        let mut wildcard: Option<TokenPosition> = None;
+
+        for pattern in self.arms.iter().flat_map(|x| x.patterns.iter()) {
+            let position = pattern.get_position().1;
+
+            if let Some(wildcard_position) = wildcard {
+                MatchNode::report_unreachable_pattern(position, wildcard_position, context);
+                continue;
+            }
+
+            if MatchNode::is_wildcard(pattern) {
+                wildcard = Some(position);
+                continue;
+            }
+
+            pattern.check_types(context);
+
+            let value = pattern.evaluate_constant(context);
+
+            if value.is_none() && !MatchNode::is_enum_variant(pattern) {
+                let notification = NotificationInfo::new(
+                    "Non-Constant Pattern".to_string(),
+                    "Patterns have to be known at compile time (e.g. integers, booleans, variants of enums or constants)".to_string(),
+                    vec![
+                        context.line_map.display_code_for(
+                            position,
+                            vec!["*hint:* use `_` to match any value".to_string()],
+                            DisplayCodeKind::InitialError
+                        ),
+                    ],
+                );
+
+                context.line_map.display_error(notification);
+                continue;
+            }
+
+            let pattern_types = pattern.get_datatypes(all_types.clone(), context.clone()).unwrap_or_default();
+
+            if !datatypes_overlap(&pattern_types, value_types) {
+                let notification = NotificationInfo::new(
+                    "Mismatched Types".to_string(),
+                    format!("The pattern is of type {}, but the matched value is of type {}", describe_datatypes(&pattern_types), describe_datatypes(value_types)),
+                    vec![
+                        context.line_map.display_code_for(
+                            position,
+                            vec![],
+                            DisplayCodeKind::InitialError
+                        ),
+                        context.line_map.display_code_for(
+                            self.value.get_position().1,
+                            vec![format!("*note:* this is of type {}", describe_datatypes(value_types))],
+                            DisplayCodeKind::AdditionalInfo
+                        ),
+                    ],
+                );
+
+                context.line_map.display_error(notification);
+                continue;
+            }
+
+            // Unknown variants have been reported already
+            let Some(value) = value else { continue };
+
+            if let Some((_, earlier_position)) = matched.iter().find(|x| x.0 == value) {
+                MatchNode::report_unreachable_pattern(position, *earlier_position, context);
+                continue;
+            }
+
+            matched.push((value, position));
+        }
+
+        if wildcard.is_some() { return; }
+
+        let is_matched = |value: i128| matched.iter().any(|x| x.0 == value);
+
+        let missing: Vec<String> = if let Some((enum_type, variants)) = enum_type {
+            variants.iter()
+                .filter(|x| !is_matched(x.value as i128))
+                .map(|x| format!("`{}::{}`", enum_type.name, x.name))
+                .collect()
+        } else if is_boolean {
+            [(1, "`true`"), (0, "`false`")].iter()
+                .filter(|x| !is_matched(x.0))
+                .map(|x| x.1.to_string())
+                .collect()
+        } else {
+            vec!["any other integer".to_string()]
+        };
+
+        if missing.is_empty() { return; }
+
+        let notification = NotificationInfo::new(
+            "Non-Exhaustive Match".to_string(),
+            format!("Every possible value has to be matched, but {} isn't", missing.join(", ")),
+            vec![
+                context.line_map.display_code_for(
+                    self.value.get_position().1,
+                    vec!["*hint:* add arms for the missing values or one for `_`".to_string()],
+                    DisplayCodeKind::InitialError
+                ),
+            ],
+        );
+
+        context.line_map.display_error(notification);
+    }
+}
+
+impl Node for MatchNode {
+    fn get_position(&self) -> (usize, TokenPosition) {
+        self.position
+    }
+
+    fn get_future(&self, current: CodeFuture) -> CodeFuture {
+        current
+    }
+
+    fn get_sub_nodes(&self) -> Vec<Rc<dyn Node>> {
+        vec![]
+    }
+
+    fn get_datatypes(&self, _: Vec<ObjectType>, _: Context) -> Option<Vec<ObjectType>> {
+        None
+    }
+
+    fn unpack(&self) -> Box<dyn Node> {
+        todo!("Not implemented yet")
+    }
+
+    fn generate_instructions(&self, context: &mut Context) -> (Vec<Instruction>, Option<Uuid>) {
+        let value = self.value.generate_instructions(context);
+        let value_uuid = value.1.unwrap();
+
+        let arm_labels: Vec<Rc<String>> = self.arms.iter().map(|_| context.generate_label()).collect();
+        let end_label = context.generate_label();
+
+        // Values without a pattern lead to the arm of `_` (or nowhere). Arms following it are unreachable.
+        let mut constants: Vec<(i128, usize)> = vec![];
+        let mut default_label = end_label.clone();
+
+        'arms: for (i, arm) in self.arms.iter().enumerate() {
+            for pattern in arm.patterns.iter() {
+                if MatchNode::is_wildcard(pattern) {
+                    default_label = arm_labels[i].clone();
+                    break 'arms;
+                }
+
+                let constant = pattern.evaluate_constant(context).unwrap();
+
+                if !constants.iter().any(|x| x.0 == constant) {
+                    constants.push((constant, i));
+                }
+            }
+        }
+
+        let range = constants.iter().map(|x| x.0).max().unwrap_or(0) - constants.iter().map(|x| x.0).min().unwrap_or(0);
+        let is_dense = constants.len() >= JUMP_TABLE_MIN_PATTERNS && range < 2 * constants.len() as i128;
+
+        let dispatch = if is_dense {
+            MatchNode::generate_jump_table(value_uuid, &constants, &arm_labels, default_label, context)
+        } else {
+            MatchNode::generate_compare_chain(value_uuid, &constants, &arm_labels, default_label)
+        };
+
+        let mut instructions = [value.0, dispatch].concat();
+
+        for (arm, label) in self.arms.iter().zip(arm_labels) {
+            instructions.push(Instruction::Label(label, false));
+            instructions.append(&mut arm.block.generate_instructions(context).0);
+            instructions.push(Instruction::Jump(end_label.clone()));
+        }
+
+        instructions.push(Instruction::Label(end_label, false));
+
+        (instructions, None)
+    }
+
+    fn output_is_randomly_mutable(&self) -> Option<bool> {
+        None
+    }
+
+    fn check_types(&self, context: &mut Context) {
+        self.value.check_types(context);
+
+        let all_types: Vec<ObjectType> = context.datatypes.values().cloned().collect();
+
+        if let Some(value_types) = self.value.get_datatypes(all_types.clone(), context.clone()) {
+            let enum_type = value_types.iter().find_map(|x| Some((x.clone(), get_enum_variants(x)?)));
+            let is_boolean = value_types.iter().any(|x| x.has_trait(Trait::BOOLEAN_COMPATIBLE));
+            let is_integer = value_types.iter().any(|x| x.has_trait(Trait::INTEGER));
+
+            if enum_type.is_none() && !is_boolean && !is_integer {
+                let notification = NotificationInfo::new(
+                    "Unsupported Match Value".to_string(),
+                    format!("Only integers, booleans and enums can be matched, but the value is of type {}", describe_datatypes(&value_types)),
+                    vec![
+                        context.line_map.display_code_for(
+                            self.value.get_position().1,
+                            vec![],
+                            DisplayCodeKind::InitialError
+                        ),
+                    ],
+                );
+
+                context.line_map.display_error(notification);
+            } else {
+                self.check_patterns(&value_types, enum_type, is_boolean, context);
+            }
+        }
+
+        for arm in self.arms.iter() {
+            arm.block.check_types(context);
+        }
+    }
+
     #[cfg(test)]
     fn repeatedly_reset_position(&mut self) {
         self.position = (0, TokenPosition::test_value());
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,115 @@
+use crate::compiler::line_map::{DisplayCodeKind, LineMap, NotificationInfo, TokenPosition};
+
+/// The escape sequences that can be used in string and character literals.
+const VALID_ESCAPES: &str = "`\\n`, `\\t`, `\\\\`, `\\\"`, `\\'`, `\\0`, `\\xNN` and `\\u{...}`";
+
+/// ### Resolves the Escape Sequences of a Literal
+///
+/// `text` is the content of a string or character literal without its delimiters,
+/// `start` is the position of its first character in the line. Malformed escape
+/// sequences are reported (underlining just the sequence) and left out.
+pub fn unescape(text: &str, start: usize, line: usize, line_map: &mut LineMap) -> String {
+    let mut result = String::new();
+    let mut characters = text.char_indices().peekable();
+
+    while let Some((index, character)) = characters.next() {
+        if character != '\\' {
+            result.push(character);
+            continue;
+        }
+
+        // The escaped character and whatever belongs to the sequence
+        let Some((_, kind)) = characters.next() else {
+            report_escape(index, text.len(), "A backslash has to be followed by the character it escapes", start, line, line_map);
+            break;
+        };
+
+        match kind {
+            'n' => result.push('\n'),
+            't' => result.push('\t'),
+            '\\' => result.push('\\'),
+            '"' => result.push('"'),
+            '\'' => result.push('\''),
+            '0' => result.push('\0'),
+
+            'x' => {
+                let digits: String = (0..2).map_while(|_| characters.next_if(|x| x.1.is_ascii_hexdigit()).map(|x| x.1)).collect();
+                let end = index + 2 + digits.len();
+
+                match u8::from_str_radix(&digits, 16) {
+                    Ok(value) if digits.len() == 2 && value.is_ascii() => result.push(value as char),
+                    Ok(_) if digits.len() == 2 => report_escape(index, end, "`\\x` can only encode ASCII characters (up to `\\x7f`), use `\\u{...}` for others", start, line, line_map),
+                    _ => report_escape(index, end, "`\\x` has to be followed by exactly two hexadecimal digits (e.g. `\\x41`)", start, line, line_map),
+                }
+            }
+
+            'u' => {
+                if characters.next_if(|x| x.1 == '{').is_none() {
+                    report_escape(index, index + 2, "`\\u` has to be followed by a code point in braces (e.g. `\\u{1F600}`)", start, line, line_map);
+                    continue;
+                }
+
+                let digits: String = std::iter::from_fn(|| characters.next_if(|x| x.1.is_ascii_hexdigit()).map(|x| x.1)).collect();
+                let closed = characters.next_if(|x| x.1 == '}').is_some();
+                let end = index + 3 + digits.len() + closed as usize;
+
+                let value = u32::from_str_radix(&digits, 16).ok().filter(|_| closed && digits.len() <= 6);
+
+                match value.map(char::from_u32) {
+                    Some(Some(value)) => result.push(value),
+                    Some(None) => report_escape(index, end, &format!("`{}` isn't a valid unicode code point", &digits), start, line, line_map),
+                    None => report_escape(index, end, "`\\u{...}` has to contain one to six hexadecimal digits followed by `}`", start, line, line_map),
+                }
+            }
+
+            _ => report_escape(index, index + 1 + kind.len_utf8(), &format!("`\\{}` isn't a known escape sequence", kind), start, line, line_map),
+        }
+    }
+
+    result
+}
+
+/// Reports the malformed escape sequence reaching from `from` to `to` (both relative to the literal's content).
+fn report_escape(from: usize, to: usize, message: &str, start: usize, line: usize, line_map: &mut LineMap) {
+    let mut position = TokenPosition::new(start + from, to - from);
+    position.line = line;
+
+    let notification = NotificationInfo::new(
+        "Invalid Escape Sequence".to_string(),
+        message.to_string(),
+        vec![
+            line_map.display_characters_for(
+                position,
+                vec![format!("*hint:* valid escape sequences are {}", VALID_ESCAPES)],
+                DisplayCodeKind::InitialError
+            ),
+        ],
+    );
+
+    line_map.display_error(notification);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compiler::line_map::{LineMap, LmFile, TokenPosition};
+    use crate::compiler::tokenization::escape::unescape;
+
+    fn unescape_counting_errors(text: &str) -> (String, u32) {
+        let mut line_map = LineMap::new();
+        line_map.add_file(LmFile::new("n/a".to_string(), 1, vec![TokenPosition::new(0, text.len() + 2)], 0, format!("\"{}\"", text)));
+
+        let result = unescape(text, 1, 0, &mut line_map);
+
+        (result, line_map.error_count)
+    }
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!(unescape_counting_errors("a\\tb\\n\\\\\\\"\\0"), ("a\tb\n\\\"\0".to_string(), 0));
+        assert_eq!(unescape_counting_errors("\\x41\\u{e4}\\u{1F600}"), ("Aä😀".to_string(), 0));
+        assert_eq!(unescape_counting_errors("\\q"), (String::new(), 1));
+        assert_eq!(unescape_counting_errors("\\x4g\\xff"), ("g".to_string(), 2));
+        assert_eq!(unescape_counting_errors("\\u{d800}\\u{1234567}\\u{41"), (String::new(), 3));
+        assert_eq!(unescape_counting_errors("a\\"), ("a".to_string(), 1));
+    }
+}
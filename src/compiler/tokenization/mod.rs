@@ -1,4 +1,5 @@
 pub mod tokenizer;
 pub mod token;
 mod tokenization_tests;
-mod token_prototype;
\ No newline at end of file
+mod token_prototype;
+mod escape;
\ No newline at end of file
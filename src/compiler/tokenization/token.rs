@@ -19,6 +19,9 @@ pub enum Token {
     /// A text that could be classified as being either true or false.
     BoolLiteral(bool, TokenPosition),
 
+    /// A single character in single quotes (e.g. `'a'` or `'\n'`).
+    CharLiteral(char, TokenPosition),
+
     /// A keyword (such "var", "let" or "if").
     KeywordType(Keyword, TokenPosition),
 
@@ -32,6 +35,10 @@ pub enum Token {
     /// An operation that sets the left side to the right side
     /// This is like "=" in basically every (non-esoteric) programming language.
     Assignment(TokenPosition),
+
+    /// An assignment that applies an operation to the left side first
+    /// (e.g. "+=", where `a += b` is equivalent to `a = a + b`).
+    CompoundAssignment(operator::Operation, TokenPosition),
     
     /// Something that separates two arguments, e.g. in a function call or array.
     /// This correlates with a "," in most languages.
@@ -47,6 +54,12 @@ pub enum Token {
     /// The closing part of the arithmetic/default parenthesis (")")
     ArithmeticParenthesisClose(TokenPosition),
 
+    /// The array parenthesis ("["), used for array types, literals and indexing
+    ArrayParenthesisOpen(TokenPosition),
+
+    /// The closing part of the array parenthesis ("]")
+    ArrayParenthesisClose(TokenPosition),
+
     /// A newline that doesn't force a logical newline (e.g. \n)
     SoftNewline(TokenPosition),
 
@@ -61,6 +74,20 @@ pub enum Token {
     
     Colon(TokenPosition),
 
+    /// A dot accessing a member of a value (e.g. the field of a struct in `a.b`)
+    Dot(TokenPosition),
+
+    /// Two colons separating the parts of a path (e.g. the enum and its variant in `Color::Red`)
+    PathSeparator(TokenPosition),
+
+    /// An arrow ("=>") separating the patterns of a match arm from its code
+    FatArrow(TokenPosition),
+
+    /// An arrow ("->") separating the parameters of a function from its return type
+    Arrow(TokenPosition),
+
+    /// Three dots ("...") standing for the variadic arguments of a function
+    Ellipsis(TokenPosition),
 }
 
 
@@ -71,16 +98,20 @@ impl Token {
             Token::StringLiteral(_, pos) => { pos.clone() }
             Token::IntegerLiteral(_, _, pos) => { pos.clone() }
             Token::BoolLiteral(_, pos) => { pos.clone() }
+            Token::CharLiteral(_, pos) => { *pos }
             Token::KeywordType(_, pos) => { pos.clone() }
             Token::Identifier(_, pos) => { pos.clone() }
             Token::Operator(_, pos) => { pos.clone() }
             Token::ArithmeticParenthesisOpen(pos) => { pos.clone() }
             Token::Assignment(pos) => { pos.clone() }
+            Token::CompoundAssignment(_, pos) => { *pos }
             Token::ArithmeticParenthesisClose(pos) => { pos.clone() }
+            Token::ArrayParenthesisOpen(pos) | Token::ArrayParenthesisClose(pos) => *pos,
             Token::ArgumentSeparator(pos) => { pos.clone() },
             Token::SoftNewline(pos) | Token::HardNewline(pos) => { pos.clone() },
             Token::CodeBlockParenthesisOpen(pos) | Token::CodeBlockParenthesisClose(pos) => pos.clone(),
-            Token::Colon(pos) => pos.clone()
+            Token::Colon(pos) | Token::Dot(pos) => *pos,
+            Token::PathSeparator(pos) | Token::FatArrow(pos) | Token::Arrow(pos) | Token::Ellipsis(pos) => *pos,
         }
     }
 
@@ -90,10 +121,17 @@ impl Token {
             Token::StringLiteral(text, _) => { Some(text.clone()) }
             Token::Identifier(text, _) => { Some(text.clone()) }
             Token::BoolLiteral(value, _) => { Some( if *value { BOOL_STATE_NAMES.0.to_string().clone() } else { BOOL_STATE_NAMES.1.to_string().clone() }) }
+            Token::CharLiteral(character, _) => { Some(character.to_string()) }
             Token::UnspecifiedString(text, _) => { Some(text.clone()) }
             Token::Operator(op, _) => { Some(op.clone().as_ref().to_string()) }
             Token::Assignment(_) => { Some(ASSIGNMENT_OPERATION.to_string()) }
+            Token::CompoundAssignment(op, _) => { Some(op.clone().as_ref().to_string() + ASSIGNMENT_OPERATION) }
             Token::Colon(_) => Some(":".to_string()),
+            Token::Dot(_) => Some(".".to_string()),
+            Token::PathSeparator(_) => Some("::".to_string()),
+            Token::FatArrow(_) => Some("=>".to_string()),
+            Token::Arrow(_) => Some("->".to_string()),
+            Token::Ellipsis(_) => Some("...".to_string()),
 
             _ => None,
         }
@@ -102,13 +140,19 @@ impl Token {
     /// Whether this is a reasonable token to expect in an arithmetic operation.
     /// If it's not, it might interrupt/stop tokens from being parsed in the parser.
     pub fn is_expected_in_arithmetic(&self) -> bool {
+        if matches!(self, Token::KeywordType(Keyword::As | Keyword::New, _)) {
+            return true;
+        }
+
         !matches!(
             self,
             Token::KeywordType(_, _) |
             Token::Assignment(_) |
+            Token::CompoundAssignment(_, _) |
             Token::SoftNewline(_) |
             Token::HardNewline(_) |
-            Token::Colon(_)
+            Token::Colon(_) |
+            Token::FatArrow(_)
         )
     }
 
@@ -130,18 +174,27 @@ impl Token {
             Token::StringLiteral(_, a) => { *a = new_pos }
             Token::IntegerLiteral(_, _, a) => {*a = new_pos }
             Token::BoolLiteral(_, a) => { *a = new_pos }
+            Token::CharLiteral(_, a) => { *a = new_pos }
             Token::KeywordType(_, a) => {*a = new_pos }
             Token::Identifier(_, a) => {*a = new_pos }
             Token::Operator(_, a) => {*a = new_pos}
             Token::Assignment(a) => {*a = new_pos}
+            Token::CompoundAssignment(_, a) => {*a = new_pos}
             Token::ArgumentSeparator(a) => {*a = new_pos}
             Token::ArithmeticParenthesisOpen(a) => {*a = new_pos}
             Token::ArithmeticParenthesisClose(a) => {*a = new_pos}
+            Token::ArrayParenthesisOpen(a) => {*a = new_pos}
+            Token::ArrayParenthesisClose(a) => {*a = new_pos}
             Token::SoftNewline(a) => {*a = new_pos}
             Token::HardNewline(a) => {*a = new_pos}
             Token::CodeBlockParenthesisOpen(a) => {*a = new_pos}
             Token::CodeBlockParenthesisClose(a) => {*a = new_pos}
             Token::Colon(a) => {*a = new_pos}
+            Token::Dot(a) => {*a = new_pos}
+            Token::PathSeparator(a) => {*a = new_pos}
+            Token::FatArrow(a) => {*a = new_pos}
+            Token::Arrow(a) => {*a = new_pos}
+            Token::Ellipsis(a) => {*a = new_pos}
         }
     }
 }
\ No newline at end of file
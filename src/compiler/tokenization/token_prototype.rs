@@ -3,7 +3,8 @@ use logos::{Logos, Span};
 use strum::IntoEnumIterator;
 use crate::compiler::data_types::integer::{generate_integer, IntegerType};
 use crate::compiler::data_types::object::ObjectType;
-use crate::compiler::line_map::{LineMap, TokenPosition};
+use crate::compiler::line_map::{DisplayCodeKind, LineMap, NotificationInfo, TokenPosition};
+use crate::compiler::tokenization::escape::unescape;
 use crate::compiler::tokenization::token::Token;
 use crate::compiler::tokenization::token::Token::{IntegerLiteral, UnspecifiedString};
 use crate::config::tokenization_options::Keyword;
@@ -13,12 +14,19 @@ use crate::util::operator;
 #[logos(skip r"[ \t\f]+")]
 pub enum TokenPrototype {
     /// A string literal in the original code.
-    /// At this stage, this still contains the leading and trailing delimiters.
-    #[regex("\"[^\"]*\"")]
+    /// At this stage, this still contains the leading and trailing delimiters
+    /// and the escape sequences (e.g. `\n`) haven't been resolved yet.
+    #[regex(r#""([^"\\]|\\.)*""#)]
     StringLiteral,
 
-    /// An integer literal. Type might be specified in the corresponding &str.
-    #[regex("[0-9](x|d|o)?[0-9a-fA-Fu]*")]
+    /// A character literal (e.g. `'a'`), just like string literals
+    /// still with its delimiters and escape sequences.
+    #[regex(r"'([^'\\]|\\.)*'")]
+    CharLiteral,
+
+    /// An integer literal. Type might be specified in the corresponding &str
+    /// using a suffix (e.g. `10u8`, `0xffi16` or `3usize`).
+    #[regex("[0-9](x|d|o)?[0-9a-fA-F_]*((u|i)(8|16|32|64|size))?")]
     IntegerLiteral,
 
     #[token("true")]
@@ -31,12 +39,16 @@ pub enum TokenPrototype {
     #[token("=")]
     Assignment,
 
+    /// An assignment combined with an operation (+=, -=, *=, /=, %=)
+    #[regex(r"(\+|-|\*|/|%)=")]
+    CompoundAssignment,
+
     /// A newline with no ';'
     #[token("\n")]
     SoftNewline,
 
     /// Any operator (+-*/%, etc.)
-    #[regex(r"\+|-|\*|/|(<<?<?)|(>>?>?)|(==)")]
+    #[regex(r"\+|-|\*|/|%|(<<?<?)|(>>?>?)|(==)|(&&?)|(\|\|?)|(\^\^?)|~|!")]
     Operation,
 
     /// A newline that always terminates a line (';')
@@ -56,6 +68,12 @@ pub enum TokenPrototype {
     
     #[token(")")]
     ArithmeticParenthesisClose,
+
+    #[token("[")]
+    ArrayParenthesisOpen,
+
+    #[token("]")]
+    ArrayParenthesisClose,
     
     #[token("{")]
     CurlyParenthesisOpen,
@@ -66,26 +84,66 @@ pub enum TokenPrototype {
     #[token(":")]
     Colon,
 
+    /// A dot accessing a member (e.g. `point.x`)
+    #[token(".")]
+    Dot,
+
+    /// Two colons separating the parts of a path (e.g. `Color::Red`)
+    #[token("::")]
+    PathSeparator,
+
+    /// An arrow separating the patterns of a match arm from its code (e.g. `1 => { ... }`)
+    #[token("=>")]
+    FatArrow,
+
+    /// An arrow separating the parameters of a function from its return type (e.g. `-> isize`)
+    #[token("->")]
+    Arrow,
+
+    /// Three dots marking the variadic arguments of a C function (e.g. `fmt: &u8, ...`)
+    #[token("...")]
+    Ellipsis,
+
     #[regex("[a-zA-Z_][a-zA-Z0-9_]*")]
     Identifier,
 }
 
 
 impl TokenPrototype {
-    pub fn into_token(self, pos: Span, string_contents: &str, file_number: usize, integer_types: Rc<Vec<(IntegerType, ObjectType)>>, line_map: &mut LineMap) -> Token {
-        let position = TokenPosition::new(pos.start, pos.end - pos.start);
+    /// Turns the prototype into a [token](Token). The line index refers to the
+    /// line's position in the [line map](LineMap), the token index to the token's
+    /// position in that line.
+    pub fn into_token(self, pos: Span, string_contents: &str, line_index: usize, token_index: usize, integer_types: Rc<Vec<(IntegerType, ObjectType)>>, line_map: &mut LineMap) -> Token {
+        let mut position = TokenPosition::new(pos.start, pos.end - pos.start);
+        position.line = line_index;
         match self {
             TokenPrototype::BoolLiteralTrue => Token::BoolLiteral(true, position),
             TokenPrototype::BoolLiteralFalse => Token::BoolLiteral(false, position),
             TokenPrototype::Assignment => Token::Assignment(position),
+            TokenPrototype::CompoundAssignment => {
+                // Cut the trailing "=" to receive the operation
+                let operation_string = &string_contents[..string_contents.len() - 1];
+
+                match operator::Operation::iter().find(|operation| operation.as_ref() == operation_string) {
+                    Some(operation) => Token::CompoundAssignment(operation, position),
+                    None => todo!("Unknown compound assignment: {} passed regex", string_contents),
+                }
+            }
             TokenPrototype::SoftNewline => Token::SoftNewline(position),
             TokenPrototype::HardNewline => Token::HardNewline(position),
             TokenPrototype::ArgumentSeparator => Token::ArgumentSeparator(position),
             TokenPrototype::ArithmeticParenthesisOpen => Token::ArithmeticParenthesisOpen(position),
             TokenPrototype::ArithmeticParenthesisClose => Token::ArithmeticParenthesisClose(position),
+            TokenPrototype::ArrayParenthesisOpen => Token::ArrayParenthesisOpen(position),
+            TokenPrototype::ArrayParenthesisClose => Token::ArrayParenthesisClose(position),
             TokenPrototype::CurlyParenthesisOpen => Token::CodeBlockParenthesisOpen(position),
             TokenPrototype::CurlyParenthesisClose => Token::CodeBlockParenthesisClose(position),
             TokenPrototype::Colon => Token::Colon(position),
+            TokenPrototype::Dot => Token::Dot(position),
+            TokenPrototype::PathSeparator => Token::PathSeparator(position),
+            TokenPrototype::FatArrow => Token::FatArrow(position),
+            TokenPrototype::Arrow => Token::Arrow(position),
+            TokenPrototype::Ellipsis => Token::Ellipsis(position),
             
             #[cfg(test)]
             TokenPrototype::Test => panic!("test token is not meant to be converted from a prototype into a token"),
@@ -109,17 +167,46 @@ impl TokenPrototype {
                 // to receive its true/expected contents.
                 let inner_string = &string_contents[1..string_contents.len() - 1];
 
-                let token = Token::StringLiteral(inner_string.to_string(), position);
+                let token = Token::StringLiteral(unescape(inner_string, pos.start + 1, line_index, line_map), position);
 
                 token
             }
 
+            TokenPrototype::CharLiteral => {
+                let inner_string = unescape(&string_contents[1..string_contents.len() - 1], pos.start + 1, line_index, line_map);
+                let mut characters = inner_string.chars();
+
+                match (characters.next(), characters.next()) {
+                    (Some(character), None) => Token::CharLiteral(character, position),
+                    (first, _) => {
+                        let message = if first.is_none() { "The character literal is empty" } else { "A character literal can only contain a single character, use double quotes for strings" };
+
+                        let notification = NotificationInfo::new(
+                            "Invalid Character Literal".to_string(),
+                            message.to_string(),
+                            vec![
+                                line_map.display_code_for(
+                                    position,
+                                    vec![],
+                                    DisplayCodeKind::InitialError
+                                ),
+                            ],
+                        );
+
+                        line_map.display_error(notification);
+
+                        // Prevents follow-up errors
+                        Token::CharLiteral(first.unwrap_or('\0'), position)
+                    }
+                }
+            }
+
             TokenPrototype::IntegerLiteral => {
                 let integer_value = generate_integer(
                     UnspecifiedString(string_contents.to_string(), position),
                     integer_types.clone(),
-                    file_number as u32,
-                    0u32,
+                    line_index as u32,
+                    token_index as u32,
                     line_map,
                 );
 
@@ -187,4 +274,17 @@ mod tests {
         assert_eq!(tokens.slice(), "134356");
         assert_eq!(tokens.next(), None);
     }
+
+    #[test]
+    fn test_logos_escaped_literals() {
+        let mut tokens = TokenPrototype::lexer(r#""say \"hi\"" '\'' 'a'"#);
+
+        assert_eq!(tokens.next().unwrap().ok(), Some(TokenPrototype::StringLiteral));
+        assert_eq!(tokens.slice(), r#""say \"hi\"""#);
+        assert_eq!(tokens.next().unwrap().ok(), Some(TokenPrototype::CharLiteral));
+        assert_eq!(tokens.slice(), r"'\''");
+        assert_eq!(tokens.next().unwrap().ok(), Some(TokenPrototype::CharLiteral));
+        assert_eq!(tokens.slice(), "'a'");
+        assert_eq!(tokens.next(), None);
+    }
 }
\ No newline at end of file
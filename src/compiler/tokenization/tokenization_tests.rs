@@ -56,7 +56,7 @@ mod tests {
             /*7*/vec![Token::ArithmeticParenthesisOpen(TokenPosition::test_value()), Token::IntegerLiteral(6, None, TokenPosition::test_value()), Token::Operator(Operation::Addition, TokenPosition::test_value()), Token::IntegerLiteral(7, None, TokenPosition::test_value()), Token::ArithmeticParenthesisClose(TokenPosition::test_value()), Token::Operator(Operation::Multiplication, TokenPosition::test_value()), Token::IntegerLiteral(67, None, TokenPosition::test_value())],
         ].concat();
 
-        let actual_output = tokenize_file(input_text.to_string(), 0, Rc::new(build_integer_types()), &mut LineMap::test_map());
+        let actual_output = tokenize_file(input_text.to_string(), "n/a", 0, Rc::new(build_integer_types()), &mut LineMap::test_map());
 
         for i in 0..expected_output.len() {
             //matches!(actual_output[i], expected_output[i]);
@@ -70,12 +70,17 @@ mod tests {
     #[test]
     fn test_generate_integer() {
         let types = vec![IntegerType::Signed32BitInteger, IntegerType::Signed32BitInteger];
-        let test_cases = ["0x45u32", "0b1011i32", "57", "rumänien"];
-        let expected_results: [Option<(i128, Option<IntegerType>)>; 4] = [
+        let test_cases = ["0x45u32", "0b1011i32", "57", "rumänien", "10u8", "0x7fffi16", "5u64", "12usize", "3isize"];
+        let expected_results: [Option<(i128, Option<IntegerType>)>; 9] = [
             Some((0x45, Some(IntegerType::Unsigned32BitInteger))),
             Some((0b1011, Some(IntegerType::Signed32BitInteger))),
             Some((57, None)),
             None,
+            Some((10, Some(IntegerType::Unsigned8BitInteger))),
+            Some((0x7fff, Some(IntegerType::Signed16BitInteger))),
+            Some((5, Some(IntegerType::Unsigned64BitInteger))),
+            Some((12, Some(IntegerType::Address))),
+            Some((3, Some(IntegerType::SignedAddress))),
         ];
 
         let integer_types = Rc::new(build_integer_types());
@@ -93,4 +98,72 @@ mod tests {
             assert_eq!(result, expected_results[case.0]);
         }
     }
+
+    #[test]
+    fn test_tokenize_integer_suffixes() {
+        let input_text = "10u8 + 0xffi16 - 7usize * 2";
+
+        let expected_literals = [
+            (10, Some(IntegerType::Unsigned8BitInteger)),
+            (0xff, Some(IntegerType::Signed16BitInteger)),
+            (7, Some(IntegerType::Address)),
+            (2, None),
+        ];
+
+        let actual_literals: Vec<(i128, Option<IntegerType>)> = tokenize_file(input_text.to_string(), "n/a", 0, Rc::new(build_integer_types()), &mut LineMap::test_map())
+            .into_iter()
+            .filter_map(|token| if let Token::IntegerLiteral(value, kind, _) = token { Some((value, kind)) } else { None })
+            .collect();
+
+        assert_eq!(actual_literals, expected_literals);
+    }
+
+    #[test]
+    fn test_tokenize_bitwise_operators() {
+        let input_text = "a >>> 2 & ~b ^ c << d | e >> f || g && h ^^ i";
+
+        let expected_operations = [
+            Operation::LogicalShiftRight,
+            Operation::BitwiseAnd,
+            Operation::BitwiseNot,
+            Operation::BitwiseXor,
+            Operation::ShiftLeft,
+            Operation::BitwiseOr,
+            Operation::ShiftRight,
+            Operation::Or,
+            Operation::And,
+            Operation::Xor,
+        ];
+
+        let actual_operations: Vec<Operation> = tokenize_file(input_text.to_string(), "n/a", 0, Rc::new(build_integer_types()), &mut LineMap::test_map())
+            .into_iter()
+            .filter_map(|token| if let Token::Operator(operation, _) = token { Some(operation) } else { None })
+            .collect();
+
+        assert_eq!(actual_operations, expected_operations);
+    }
+
+    #[test]
+    fn test_tokenize_compound_assignments() {
+        let input_text = "a += 1; b -= c; d *= e % f; g /= 2; h %= i; j = k == l;";
+
+        let expected_tokens = [
+            Token::CompoundAssignment(Operation::Addition, TokenPosition::test_value()),
+            Token::CompoundAssignment(Operation::Subtraction, TokenPosition::test_value()),
+            Token::CompoundAssignment(Operation::Multiplication, TokenPosition::test_value()),
+            Token::Operator(Operation::Modulo, TokenPosition::test_value()),
+            Token::CompoundAssignment(Operation::Division, TokenPosition::test_value()),
+            Token::CompoundAssignment(Operation::Modulo, TokenPosition::test_value()),
+            Token::Assignment(TokenPosition::test_value()),
+            Token::Operator(Operation::Equals, TokenPosition::test_value()),
+        ];
+
+        let actual_tokens: Vec<Token> = tokenize_file(input_text.to_string(), "n/a", 0, Rc::new(build_integer_types()), &mut LineMap::test_map())
+            .into_iter()
+            .filter(|token| matches!(token, Token::Assignment(_) | Token::CompoundAssignment(_, _) | Token::Operator(_, _)))
+            .map(|mut token| { token.reset_position(); token })
+            .collect();
+
+        assert_eq!(actual_tokens, expected_tokens);
+    }
 }
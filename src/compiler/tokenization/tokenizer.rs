@@ -1,10 +1,10 @@
 use std::rc::Rc;
 use clap::builder::Str;
-use logos::Logos;
+use logos::{Logos, Span};
 use strum::IntoEnumIterator;
 use crate::compiler::data_types::integer::*;
 use crate::compiler::data_types::object::ObjectType;
-use crate::compiler::line_map::LineMap;
+use crate::compiler::line_map::{LineMap, LmFile, TokenPosition};
 use crate::compiler::trimmer::trim;
 use crate::compiler::tokenization::token::{Token, Token::* };
 use crate::compiler::tokenization::token_prototype::TokenPrototype;
@@ -28,18 +28,18 @@ use crate::util::operator;
 ///
 /// This can then be used by the [parser](crate::compiler::parser::parse::parse)
 /// to perform the next step.
-pub fn tokenize_file(contents: String, file_number: usize, integer_types: Rc<Vec<(IntegerType, ObjectType)>>, line_map: &mut LineMap) -> Vec<Token> {
+pub fn tokenize_file(contents: String, source_file_name: &str, _file_number: usize, integer_types: Rc<Vec<(IntegerType, ObjectType)>>, line_map: &mut LineMap) -> Vec<Token> {
     // Remove the comments
 
-    let mut lines: Vec<String> = Vec::new();
+    let mut lines: Vec<(u32, String)> = Vec::new();
 
     {
         let mut cursor_in_block_comment = false;
-        for line in contents.lines() {
+        for (line_number, line) in contents.lines().enumerate() {
             let line_commentless = trim(line, &mut cursor_in_block_comment);
 
             if !line.is_empty() {
-                lines.push(line_commentless)
+                lines.push((line_number as u32 + 1, line_commentless))
             }
         }
     }
@@ -47,7 +47,11 @@ pub fn tokenize_file(contents: String, file_number: usize, integer_types: Rc<Vec
     // Turn it into tokens with help of logos
     let mut resulting_tokens: Vec<Token> = Vec::new();
 
-    for line in lines.iter() {
+    for (line_number, line) in lines.iter() {
+        // Collect the tokens of the line first, so the line can be registered
+        // in the line map before errors are displayed for its tokens.
+        let mut prototypes: Vec<(TokenPrototype, Span, &str)> = Vec::new();
+
         let mut tokens = TokenPrototype::lexer(line);
         while let Some(token_prototype) = tokens.next() {
             if token_prototype.is_err() {
@@ -56,10 +60,25 @@ pub fn tokenize_file(contents: String, file_number: usize, integer_types: Rc<Vec
 
             println!("current: {:?}, prot: {:?}", tokens.slice(), token_prototype);
 
-            let token = token_prototype.unwrap().into_token(
-                tokens.span(),
-                tokens.slice(),
-                file_number,
+            prototypes.push((token_prototype.unwrap(), tokens.span(), tokens.slice()));
+        }
+
+        let line_index = line_map.files.len();
+
+        line_map.files.push(LmFile::new(
+            source_file_name.to_string(),
+            *line_number,
+            prototypes.iter().map(|x| TokenPosition::new(x.1.start, x.1.end - x.1.start)).collect(),
+            0,
+            line.clone(),
+        ));
+
+        for (token_index, (token_prototype, span, slice)) in prototypes.into_iter().enumerate() {
+            let token = token_prototype.into_token(
+                span,
+                slice,
+                line_index,
+                token_index,
                 integer_types.clone(),
                 line_map,
             );
@@ -70,4 +89,3 @@ pub fn tokenize_file(contents: String, file_number: usize, integer_types: Rc<Vec
 
     resulting_tokens
 }
-
@@ -0,0 +1,196 @@
+use std::rc::Rc;
+use crate::compiler::backend::context::Context;
+use crate::compiler::parser::tree::node::Node;
+
+/// ### Checks the types of the entire tree
+///
+/// This runs after the early context changes and before flattening.
+/// Every node checks whether the types of its subnodes fit the operation
+/// it performs and displays errors through the line map if they don't.
+///
+/// The objects declared while checking only live in a copy of the context,
+/// so [flattening](crate::compiler::backend::flattener::flatten) starts out
+/// with the same context as before. Only the line map with the notification
+/// counts and the [addressed variables](Context::addressed_variables) are kept.
+pub fn check_types(tree: Rc<dyn Node>, context: &mut Context) {
+    let mut checking_context = context.clone();
+
+    tree.check_types(&mut checking_context);
+
+    context.line_map = checking_context.line_map;
+    context.addressed_variables = checking_context.addressed_variables;
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Deref;
+    use std::rc::Rc;
+    use crate::compiler::backend::context::Context;
+    use crate::compiler::data_types::integer::build_integer_types;
+    use crate::compiler::data_types::object::ObjectType;
+    use crate::compiler::line_map::LineMap;
+    use crate::compiler::parser::parse::parse;
+    use crate::compiler::parser::tree::node::{CodeBlockArray, Node};
+    use crate::compiler::tokenization::tokenizer::tokenize_file;
+    use crate::compiler::type_checker::check_types;
+
+    /// Runs everything up to the type checker on the code and returns the title
+    /// and the underlined code of every error, in the order they were reported.
+    fn reported_type_errors(code: &str) -> Vec<(String, String)> {
+        let mut line_map = LineMap::new();
+        let tokens = tokenize_file(code.to_string(), "n/a", 0, Rc::new(build_integer_types()), &mut line_map);
+        let mut object_types = Rc::new(ObjectType::generate_built_ins());
+
+        let parsed = parse(vec![tokens], &mut line_map, &mut object_types).unwrap();
+
+        let mut context = Context::clear(line_map);
+        object_types.iter().for_each(|object_type| {
+            context.datatypes.insert(object_type.type_uuid, object_type.clone());
+        });
+
+        let mut parsed = parsed.downcast_rc::<CodeBlockArray>().unwrap().deref().clone();
+        parsed.perform_early_context_changes(&mut context);
+
+        check_types(Rc::new(parsed), &mut context);
+
+        context.line_map.reported_errors
+    }
+
+    #[test]
+    fn test_reported_type_errors() {
+        let functions = "func add(a: u32, b: u32) {\nlet c = a + b;\n}\n";
+        let point = "struct Point { x: i32, y: i32 }\n";
+        let color = "enum Color { Red, Green, Blue = 5 }\nlet c = Color::Green;\nlet a: u8 = 3;\n";
+        let globals = "const LIMIT: u32 = 4 * 1024;\nconst FLAG: bool = !false;\nstatic count: u64 = 0;\n";
+        let report = "func report(message: str) { }\n";
+        let counters = "let offset: u64 = 5;\nvar ticks: u64 = 0;\n";
+        let divide = "func divide(a: u32, b: u32) -> (u32, u32) {\nreturn a / b, a % b;\n}\n";
+
+        // The code and the errors it causes, each with its title and the code it underlines
+        let cases: Vec<(String, Vec<(&str, &str)>)> = vec![
+            // Conditions and operators
+            ("let a = 5;\nlet b = a == 3 || false;\nif b {\nexit a + 1;\n}".to_string(), vec![]),
+            ("let a = 5;\nif a {\nexit 1;\n}".to_string(), vec![("Non-Boolean Condition", "a")]),
+            ("let a = true;\nlet b = a + 5;".to_string(), vec![("Unsupported Operation", "a")]),
+            ("let a: bool = 5;".to_string(), vec![("Mismatched Types", "5")]),
+
+            // Function calls
+            (format!("{functions}add(1, 2);"), vec![]),
+            (format!("{functions}add(1);"), vec![("Wrong Amount of Arguments", "add(1)")]),
+            (format!("{functions}add(1, 2, 3);"), vec![("Wrong Amount of Arguments", "add(1, 2, 3)")]),
+            (format!("{functions}subtract(2, 1);"), vec![("Unknown Function", "subtract(2, 1)")]),
+
+            // Integer ranges
+            ("let a: u8 = 255;\nlet b: i8 = -128;".to_string(), vec![]),
+            ("let a: u8 = 256;".to_string(), vec![("Integer Literal Out Of Range", "256")]),
+            ("let a: i8 = -129;".to_string(), vec![("Integer Literal Out Of Range", "-129")]),
+            ("let a = -5u8;".to_string(), vec![("Integer Literal Out Of Range", "-5u8")]),
+
+            // Casts
+            ("let a: i16 = -300;\nlet b: u8 = a as u8;\nlet c: bool = b as bool;\nlet d = c as u32 + 1;".to_string(), vec![]),
+            ("let a: u8 = 5;\nlet b: u16 = a;".to_string(), vec![("Mismatched Types", "a")]),
+
+            // Arrays
+            ("let a: [u8; 3] = [1, 2, 3];\nlet b: u8 = a[2];\nlet c = [[1, 2], [3, 4]];\nlet d: u32 = c[1][0];".to_string(), vec![]),
+            ("let a: [u8; 3] = [1, 2];".to_string(), vec![("Mismatched Types", "[1, 2]")]),
+            ("let a = [1, true];".to_string(), vec![("Mismatched Array Elements", "true")]),
+            ("let a = [1, 2];\nlet b = a[2];".to_string(), vec![("Index Out Of Bounds", "2")]),
+
+            // References
+            ("let a: &u8 = new 15;\nvar b = 10;\nlet c = &b;\n*c = *c + 1;\nlet d: u8 = *a;".to_string(), vec![]),
+            ("let a: &u8 = new 300;".to_string(), vec![("Mismatched Types", "new 300")]),
+            ("let a = 5;\nlet b = *a;".to_string(), vec![("Dereferencing Non-Reference", "a")]),
+            ("let a: &u16 = new 5;\nlet b: u8 = *a;".to_string(), vec![("Mismatched Types", "*a")]),
+
+            // Structs
+            (format!("{point}var a = Point {{ x: 1, y: -2 }};\nlet b = &a;\nb.x = a.y + 1;\nlet c: i32 = b.x;"), vec![]),
+            (format!("{point}let a = Point {{ x: 1 }};"), vec![("Missing Fields", "Point { x: 1 }")]),
+            (format!("{point}let a: Point = Point {{ x: 1, y: true }};"), vec![("Mismatched Types", "true")]),
+            (format!("{point}let a = Point {{ x: 1, y: 2 }};\nlet b = a.z;"), vec![("Unknown Field", "z")]),
+            (format!("{point}func origin() -> Point {{\nreturn Point {{ x: 0, y: 0 }};\n}}\nlet a = origin();\nlet b: i32 = a.x;"), vec![]),
+            (format!("{point}func origin() -> (Point, u32) {{\nreturn Point {{ x: 0, y: 0 }}, 0;\n}}"), vec![("Unsupported Return Type", "-> (Point, u32)")]),
+            ("func pair() -> [u8; 2] {\nreturn [1, 2];\n}".to_string(), vec![("Unsupported Return Type", "-> [u8; 2]")]),
+
+            // Match statements
+            (format!("{color}match c {{ Color::Red => {{ }}, Color::Green, Color::Blue => {{ }} }}\nmatch a {{ 1, 2 => {{ }}, _ => {{ }} }}\nlet b = c as u8;"), vec![]),
+            (format!("{color}match c {{ Color::Red => {{ }}, Color::Blue => {{ }} }}"), vec![("Non-Exhaustive Match", "c")]),
+            (format!("{color}match a {{ 1 => {{ }}, 2 => {{ }} }}"), vec![("Non-Exhaustive Match", "a")]),
+            (format!("{color}match a {{ 1 => {{ }}, 1 => {{ }}, _ => {{ }}, 2 => {{ }} }}"), vec![("Unreachable Pattern", "1"), ("Unreachable Pattern", "2")]),
+            (format!("{color}match c {{ Color::Red => {{ }}, 1 => {{ }}, _ => {{ }} }}"), vec![("Mismatched Types", "1")]),
+            (format!("{color}match c {{ Color::Purple => {{ }}, _ => {{ }} }}"), vec![("Unknown Variant", "Purple")]),
+            (format!("{color}match true {{ true => {{ }}, a => {{ }}, false => {{ }} }}"), vec![("Non-Constant Pattern", "a")]),
+
+            // Globals
+            (format!("{globals}const HALF: u32 = LIMIT / 2;\ncount = 5;\nlet a: u32 = LIMIT;\nmatch a {{ LIMIT => {{ }}, _ => {{ }} }}"), vec![]),
+            (format!("{globals}const A: u8 = 200 + 100;"), vec![("Value Out Of Range", "200 + 100")]),
+            (format!("{globals}let b = 5;\nconst A: i32 = b;"), vec![("Non-Constant Initializer", "b")]),
+            (format!("{globals}static A: u32 = FLAG;"), vec![("Mismatched Types", "FLAG")]),
+            (format!("{globals}const A: [u8; 2] = [1, 2];"), vec![("Unsupported Global Type", "[1, 2]")]),
+
+            // Strings
+            (format!("{report}let a: str = \"Hello\";\nreport(a);\nreport(\"World\");"), vec![]),
+            (format!("{report}let a: u32 = \"Hello\";"), vec![("Mismatched Types", "\"Hello\"")]),
+            (format!("{report}report(5);"), vec![("Mismatched Argument Type", "5")]),
+
+            // Characters
+            ("let a: char = 'a';\nlet b = a as u32;\nlet c: str = \"tab\\there\";".to_string(), vec![]),
+            ("let a: u32 = 'a';".to_string(), vec![("Mismatched Types", "'a'")]),
+            ("let a = 97 as char;".to_string(), vec![("Invalid Cast", "97 as char")]),
+
+            // Scopes
+            ("let a = 5;\nif a == 5 {\nlet a = true;\nif a {\nexit 1;\n}\n}\nexit a + 1;".to_string(), vec![]),
+            ("func first(x: u32) {\nexit x;\n}\nfunc second(x: bool) {\nif x {\nexit 1;\n}\n}".to_string(), vec![]),
+            ("let a = 5;\nif a == 5 {\nlet b = a;\n}\nexit b;".to_string(), vec![("Unknown Identifier", "b")]),
+            ("exit a;\nlet a = 5;".to_string(), vec![("Use Before Declaration", "a")]),
+
+            // Inline assembly
+            (format!("{counters}asm {{\n\"mrs {{ticks}}, cntvct_el0\"\n\"add {{1}}, {{1}}, {{0}}\"\nin(reg) offset, out(reg) ticks\n}}"), vec![]),
+            (format!("{counters}asm {{ \"add {{ticks}}, {{ticks}}, {{2}}\", in(reg) offset, out(reg) ticks }}"), vec![("Unknown Operand", "\"add {ticks}, {ticks}, {2}\"")]),
+            (format!("{counters}asm {{ \"mov {{count}}, #1\", out(reg) ticks, clobber(\"x9\") }}"), vec![("Unknown Operand", "\"mov {count}, #1\"")]),
+            (format!("{counters}asm {{ \"mov x9, #1\", clobber(\"x9\", \"x99\") }}"), vec![("Unknown Register", "\"x99\"")]),
+            (format!("{counters}asm {{ \"mov sp, x9\", clobber(\"sp\") }}"), vec![("Unknown Register", "\"sp\"")]),
+            (format!("{counters}asm {{ \"mov {{count}}, #1\", out(reg) count }}"), vec![("Unknown Identifier", "count")]),
+
+            // System calls
+            ("let pid: isize = syscall(20);".to_string(), vec![]),
+            ("let written = syscall(4, 1, 0, 0);\nexit written;".to_string(), vec![]),
+            ("let result: bool = syscall(20);".to_string(), vec![("Mismatched Types", "syscall(20)")]),
+            ("syscall(1, 2, 3, 4, 5, 6, 7);".to_string(), vec![]),
+            ("syscall(1, 2, 3, 4, 5, 6, 7, 8);".to_string(), vec![("Wrong Amount of Arguments", "syscall(1, 2, 3, 4, 5, 6, 7, 8)")]),
+            ("syscall();".to_string(), vec![("Wrong Amount of Arguments", "syscall()")]),
+
+            // Return values
+            (format!("{divide}let (q, r) = divide(7, 2);\nlet s: u32 = q + r;\nfunc half(a: u32) -> u32 {{\nif a == 0 {{\nreturn 0;\n}}\nreturn a / 2;\n}}"), vec![]),
+            (format!("{divide}let (q, r, s) = divide(7, 2);"), vec![("Wrong Amount of Names", "let (q, r, s) = divide(7, 2)")]),
+            (format!("{divide}let (q, r) = 5;"), vec![("Expected a Function Call", "5")]),
+            (format!("{divide}let q = divide(7, 2);"), vec![("Wrong Amount of Names", "let q = divide(7, 2)")]),
+            ("func half(a: u32) -> u32 {\nreturn a, a;\n}".to_string(), vec![("Wrong Amount of Return Values", "return a, a;")]),
+            ("func half(a: u32) -> u32 {\nreturn a == 0;\n}".to_string(), vec![("Mismatched Return Type", "a == 0")]),
+            ("func half(a: u32) -> u32 {\nlet b = a / 2;\n}".to_string(), vec![("Missing Return", "func")]),
+            ("extern \"C\" func div(a: u32, b: u32) -> (u32, u32);".to_string(), vec![("Too Many Return Values", "-> (u32, u32)")]),
+            ("return 5;".to_string(), vec![("Return Outside of a Function", "return 5;")]),
+
+            // Assignments
+            ("var a = 5;\na = 6;\na += 1;\nexit a;".to_string(), vec![]),
+            ("b = 1;".to_string(), vec![("Unknown Identifier", "b")]),
+            ("if true {\nvar a = 1;\n}\na = 2;".to_string(), vec![("Unknown Identifier", "a")]),
+            ("a = 2;\nvar a = 1;".to_string(), vec![("Use Before Declaration", "a")]),
+            ("let a = 5;\na = 6;".to_string(), vec![("Attempt To Modify Immutable Variable", "a = 6")]),
+            ("let a = [1, 2];\na[0] = 3;".to_string(), vec![("Attempt To Modify Immutable Variable", "a[0] = 3")]),
+            ("let a = 0;\nasm { \"mov {a}, #1\", out(reg) a }".to_string(), vec![("Attempt To Modify Immutable Variable", "a")]),
+
+            // Unknown types
+            ("let a: foo = 5;\nexit a;".to_string(), vec![("Unknown Datatype 'foo'", "foo")]),
+            ("let a: [&foo; 2] = [1, 2];\nlet b = a[0];".to_string(), vec![("Unknown Datatype 'foo'", "foo")]),
+            ("let a = 5 as foo;\nexit a;".to_string(), vec![("Unknown Datatype 'foo'", "foo")]),
+            ("func first(a: foo, b: u32) {\nexit b;\n}".to_string(), vec![("Unknown Datatype 'foo'", "foo")]),
+        ];
+
+        for (code, expected) in cases {
+            let expected: Vec<(String, String)> = expected.into_iter().map(|(title, code)| (title.to_string(), code.to_string())).collect();
+
+            assert_eq!(reported_type_errors(&code), expected, "in:\n{code}");
+        }
+    }
+}
@@ -140,6 +140,16 @@ pub mod tokenization_options {
         #[strum(serialize = "func")]
         Function,
 
+        /// ### Leave the Current Function
+        ///
+        /// Like Rust's "return", this ends the function and hands the values
+        /// following it to the caller. Smisc functions can return several
+        /// values at once, which are received by listing names in a
+        /// declaration. For example: `return quotient, remainder;` and
+        /// `let (q, r) = divide(a, b);`
+        #[strum(serialize = "return")]
+        Return,
+
 
         /// ### Modify Assembly Output
         ///
@@ -165,6 +175,85 @@ pub mod tokenization_options {
         /// from left to right.
         #[strum(serialize = "if")]
         If,
+
+        /// ### Convert a Value to Another Type
+        ///
+        /// Like Rust's "as", this explicitly converts between integer types
+        /// (truncating or extending the value) as well as between integers
+        /// and booleans. For example: `a as u8`
+        #[strum(serialize = "as")]
+        As,
+
+        /// ### Allocate a Value on the Heap
+        ///
+        /// Similar to C++'s "new", this stores the value following it in newly
+        /// allocated memory and results in a reference to it. The memory is
+        /// requested from the allocator (`--allocator`).
+        /// For example: `let c: &u32 = new 15;`
+        #[strum(serialize = "new")]
+        New,
+
+        /// ### Define a New Type Composed of Fields
+        ///
+        /// Like C's "struct", this declares a type with named fields that are
+        /// laid out the way C would lay them out. For example:
+        /// `struct Point { x: i32, y: i32 }`, which can then be created using
+        /// `Point { x: 1, y: 2 }` and accessed using `point.x`.
+        #[strum(serialize = "struct")]
+        Struct,
+
+        /// ### Define a New Type With Named Variants
+        ///
+        /// Like C's "enum", this declares a type whose values are one of the
+        /// listed variants, each represented by an integer. For example:
+        /// `enum Color { Red, Green = 5, Blue }`, where `Color::Blue` is 6.
+        #[strum(serialize = "enum")]
+        Enum,
+
+        /// ### Choose Code Depending on a Value
+        ///
+        /// Similar to Rust's "match" (or a "switch" without fallthrough), this
+        /// compares a value to the patterns of each arm and runs the code of the
+        /// first arm that matches. Patterns are integers, booleans, variants of
+        /// enums or "_", which matches anything. Every possible value has to be
+        /// covered. For example: `match a { 1, 2 => { ... }, _ => { ... } }`
+        #[strum(serialize = "match")]
+        Match,
+
+        /// ### Define a Value Known at Compile Time
+        ///
+        /// Similar to Rust's "const", this gives a name to a value that gets
+        /// calculated while compiling and inserted wherever it's used. The type
+        /// has to be given. For example: `const LIMIT: u32 = 4 * 1024;`
+        #[strum(serialize = "const")]
+        Const,
+
+        /// ### Define a Global Variable
+        ///
+        /// Similar to C's global variables (or Rust's "static mut"), this places
+        /// a modifiable value into the data section of the program, so it's
+        /// shared by all functions. The initial value has to be known at compile
+        /// time and the type has to be given. For example: `static count: u64 = 0;`
+        #[strum(serialize = "static")]
+        Static,
+
+        /// ### Use Code From Other Files
+        ///
+        /// Loads the module at the path (relative to the importing file, e.g.
+        /// `geometry::shapes` is `geometry/shapes.txt`), whose functions, constants and
+        /// statics can then be used via its name (e.g. `shapes::area(...)`). With `*`
+        /// (e.g. `import std::*;`), they can be used without it.
+        #[strum(serialize = "import")]
+        Import,
+
+        /// ### Write Assembly Directly
+        ///
+        /// Similar to Rust's "asm!", this places the given lines of assembly into
+        /// the program. Values are passed in and out through registers the compiler
+        /// chooses, and registers the assembly changes have to be listed. For example:
+        /// `asm { "mrs {ticks}, cntvct_el0", out(reg) ticks }`
+        #[strum(serialize = "asm")]
+        Asm,
     }
 
 
@@ -205,6 +294,13 @@ pub mod misc {
     /// All characters in this array will be ignored when trying to build
     /// an integer via [this function](crate::util::math::convert_to_int)
     pub const INTEGER_CONVERSION_IGNORED_CHARACTERS: [char; 1] = ['_'];
+
+    /// ### Name of the System Call Intrinsic
+    ///
+    /// Calls of this name (e.g. `syscall(20)`) perform a system call of the
+    /// target instead of calling a function. The first argument is the
+    /// number of the call, up to six arguments of the call can follow.
+    pub const SYSCALL_INTRINSIC: &str = "syscall";
 }
 
 
@@ -222,5 +318,19 @@ pub mod target {
     ///
     /// **Note:** Don't use the address type here, as this will
     /// lead to an infinite recursion.
-    pub const ADDRESS_INTEGER_TYPE: IntegerType = IntegerType::Unsigned32BitInteger;
+    pub const ADDRESS_INTEGER_TYPE: IntegerType = IntegerType::Unsigned64BitInteger;
+
+    /// ### The signed integer type with the width of an address
+    ///
+    /// This should be the signed counterpart of the
+    /// [address integer type](ADDRESS_INTEGER_TYPE).
+    ///
+    /// **Note:** Don't use the address types here either.
+    pub const SIGNED_ADDRESS_INTEGER_TYPE: IntegerType = IntegerType::Signed64BitInteger;
+
+    /// ### The integer type used when none is specified
+    ///
+    /// Integer literals without a suffix (e.g. `10` instead of `10u8`) that
+    /// aren't required to have a specific type will be of this type.
+    pub const DEFAULT_INTEGER_TYPE: IntegerType = IntegerType::Unsigned32BitInteger;
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
 #![warn(unused_extern_crates)]
 
 use crate::compiler::compile;
+use crate::compiler::{OverflowBehaviour, Target};
 use clap::Parser;
 use crate::help::print_help;
 
@@ -34,6 +35,27 @@ pub struct ArgumentList{
 
     #[clap(long)]
     pub show_tokens: bool,                          // --show-tokens
+
+    #[clap(long, value_enum, default_value_t = Target::Aarch64MacOs)]
+    pub target: Target,                             // --target=aarch64-macos|aarch64-linux
+
+    #[clap(long, value_enum, default_value_t = OverflowBehaviour::Trap)]
+    pub overflow: OverflowBehaviour,                // --overflow=trap|wrap
+
+    #[clap(long)]
+    pub check_bounds: bool,                         // --check-bounds
+
+    #[clap(long)]
+    pub allocator: Option<String>,                  // --allocator=<symbol>
+
+    #[clap(long)]
+    pub warn_shadowing: bool,                       // --warn-shadowing
+
+    #[clap(short = 'l', long = "link")]
+    pub link: Vec<String>,                          // -l or --link=<library>
+
+    #[clap(short = 'L', long = "library-path")]
+    pub library_path: Vec<String>,                  // -L or --library-path=<directory>
 }
 
 fn main() {
@@ -31,6 +31,48 @@ pub enum Operation {
     /// Equal (==) in comparisons only
     #[strum(to_string = "==")]
     Equals,
+
+    /// Logical and (&&). The right side is only evaluated if the left
+    /// side is true.
+    #[strum(to_string = "&&")]
+    And,
+
+    /// Logical or (||). The right side is only evaluated if the left
+    /// side is false.
+    #[strum(to_string = "||")]
+    Or,
+
+    /// Logical exclusive or (^^). Both sides are always evaluated.
+    #[strum(to_string = "^^")]
+    Xor,
+
+    #[strum(to_string = "&")]
+    BitwiseAnd,
+
+    #[strum(to_string = "|")]
+    BitwiseOr,
+
+    #[strum(to_string = "^")]
+    BitwiseXor,
+
+    /// Flips every bit of its only operand (~x).
+    #[strum(to_string = "~")]
+    BitwiseNot,
+
+    /// Inverts its only operand, which has to be a boolean (!x).
+    #[strum(to_string = "!")]
+    LogicalNot,
+
+    #[strum(to_string = "<<")]
+    ShiftLeft,
+
+    /// Arithmetic shift to the right (>>), keeping the sign bit.
+    #[strum(to_string = ">>")]
+    ShiftRight,
+
+    /// Logical shift to the right (>>>), filling up with zeros.
+    #[strum(to_string = ">>>")]
+    LogicalShiftRight,
 }
 
 
@@ -38,19 +80,52 @@ impl Operation {
     /// Whether the resulting value is of boolean type.
     /// If not, it should be the type of the object this is performed on.
     pub fn is_boolean(&self) -> bool {
-        matches!(self, Self::Equals)
+        matches!(self, Self::Equals | Self::And | Self::Or | Self::Xor)
+    }
+
+    /// The [operation order](Self::get_operation_order) of all unary operations.
+    /// They bind stronger than any binary operation, so `-a * b` is `(-a) * b`.
+    pub const UNARY_OPERATION_ORDER: u8 = 10;
+
+    /// Whether the operation takes only one operand (e.g. `~x`).
+    pub fn is_unary(&self) -> bool {
+        matches!(self, Self::BitwiseNot | Self::LogicalNot)
+    }
+
+    /// Whether the operation can be used with only one operand. This
+    /// includes operations that are binary as well, like `-` in `-x`.
+    /// Used with one operand, `*` dereferences and `&` takes the address.
+    pub fn can_be_unary(&self) -> bool {
+        matches!(self, Self::Subtraction | Self::Multiplication | Self::BitwiseAnd | Self::BitwiseNot | Self::LogicalNot)
     }
 
     /// A value determining the importance of the operation (like PEMDAS).
     /// A higher value indicates higher significance.
+    ///
+    /// Logical operators bind the weakest, with `&&` binding stronger
+    /// than `||` and `^^` (which share the same priority). Comparisons
+    /// come next, so `a + 1 == b && c` means `((a + 1) == b) && c`.
+    /// Bitwise operators bind stronger than comparisons, which allows
+    /// for masks like `x & 0x80 == 0`.
     pub fn get_operation_order(&self) -> u8 {
         match self {
-            Operation::Addition => 1,
-            Operation::Subtraction => 1,
-            Operation::Multiplication => 2,
-            Operation::Division => 2,
-            Operation::Modulo => 2,
+            Operation::Or => 1,
+            Operation::Xor => 1,
+            Operation::And => 2,
             Operation::Equals => 3,
+            Operation::BitwiseOr => 4,
+            Operation::BitwiseXor => 5,
+            Operation::BitwiseAnd => 6,
+            Operation::ShiftLeft => 7,
+            Operation::ShiftRight => 7,
+            Operation::LogicalShiftRight => 7,
+            Operation::Addition => 8,
+            Operation::Subtraction => 8,
+            Operation::Multiplication => 9,
+            Operation::Division => 9,
+            Operation::Modulo => 9,
+            Operation::BitwiseNot => Self::UNARY_OPERATION_ORDER,
+            Operation::LogicalNot => Self::UNARY_OPERATION_ORDER,
         }
     }
     
@@ -58,8 +133,13 @@ impl Operation {
     /// used in any order (x + y == y + x). or not (x / y != y / x).
     pub fn is_commutative(&self) -> bool {
         match self {
-            Operation::Addition | Operation::Multiplication | Operation::Equals => true,
+            Operation::Addition | Operation::Multiplication | Operation::Equals | Operation::Xor => true,
+            Operation::BitwiseAnd | Operation::BitwiseOr | Operation::BitwiseXor => true,
+            // The right side of those may not be evaluated at all.
+            Operation::And | Operation::Or => false,
             Operation::Subtraction | Operation::Division | Operation::Modulo => false,
+            Operation::ShiftLeft | Operation::ShiftRight | Operation::LogicalShiftRight => false,
+            Operation::BitwiseNot | Operation::LogicalNot => false,
         }
     }
 }
\ No newline at end of file